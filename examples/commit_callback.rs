@@ -0,0 +1,38 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gitlogue::{Order, Session};
+
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| ".".to_string());
+
+    // Record every commit hash the session loads, e.g. to drive an external dashboard
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let recorder = Rc::clone(&seen);
+
+    let mut session = Session::open(&path)?
+        .order(Order::Asc)
+        .on_commit(move |meta| recorder.borrow_mut().push(meta.hash.clone()))
+        .on_commit(|meta| {
+            let summary = meta.message.lines().next().unwrap_or("");
+            println!(
+                "{} {} ({} files)",
+                &meta.hash[..7],
+                summary,
+                meta.changes.len()
+            );
+        });
+
+    for _ in 0..5 {
+        if session.next_commit().is_err() {
+            break;
+        }
+    }
+
+    println!("Captured {} commit hashes:", seen.borrow().len());
+    for hash in seen.borrow().iter() {
+        println!("  {}", hash);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use gitlogue::{Order, Player, Session, Theme};
+use ratatui::{buffer::Buffer, layout::Rect};
+
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| ".".to_string());
+
+    let mut session = Session::open(&path)?.order(Order::Desc);
+    let mut player = Player::new(Theme::default()).speed(1);
+    player.load_commit(&session.next_commit()?);
+
+    // Play the latest commit off-screen, as a host app would inside its own draw loop
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 120, 40));
+    while !player.is_finished() {
+        if player.tick() {
+            player.render_to_buffer(&mut buffer)?;
+        }
+        std::thread::sleep(Duration::from_millis(8));
+    }
+
+    // Print the last frame as plain text
+    for y in 0..buffer.area.height {
+        let line: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        println!("{}", line.trim_end());
+    }
+
+    Ok(())
+}
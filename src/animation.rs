@@ -1,11 +1,15 @@
 use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::ops::Range;
 use std::time::{Duration, Instant};
 
 use globset::{Glob, GlobMatcher};
 use rand::Rng;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::UnicodeWidthChar;
 
-use crate::git::{CommitMetadata, DiffHunk, FileChange, FileStatus, LineChangeType};
+use crate::git::{
+    path_matches_filter, CommitMetadata, DiffHunk, FileChange, FileStatus, LineChangeType,
+};
 use crate::syntax::Highlighter;
 
 /// A rule that specifies typing speed for files matching a glob pattern
@@ -36,6 +40,50 @@ impl SpeedRule {
     }
 }
 
+/// Restricts animation to a single file, optionally to the hunks overlapping a line range
+#[derive(Debug, Clone)]
+pub struct FileFocus {
+    pub path: String,
+    /// Inclusive new-file line range, 1-indexed
+    pub lines: Option<(usize, usize)>,
+}
+
+/// Range the typing speed can be adjusted to at runtime
+pub const MIN_SPEED_MS: u64 = 1;
+pub const MAX_SPEED_MS: u64 = 500;
+
+/// One-line overview of a commit's changes, like `3 files changed, +58 -12`
+fn diff_stat_summary(metadata: &CommitMetadata) -> String {
+    let (additions, deletions) = metadata
+        .changes
+        .iter()
+        .map(FileChange::line_counts)
+        .fold((0, 0), |(a, d), (additions, deletions)| {
+            (a + additions, d + deletions)
+        });
+    let files = metadata.changes.len();
+    format!(
+        "{} file{} changed, +{} -{}",
+        files,
+        if files == 1 { "" } else { "s" },
+        additions,
+        deletions
+    )
+}
+
+/// Number of columns a tab starting at display column `col` spans
+pub fn tab_stop_width(col: usize, tab_width: usize) -> usize {
+    tab_width - col % tab_width
+}
+
+/// Display width of a line with each tab expanded to the next tab stop
+pub fn expanded_width(line: &str, tab_width: usize) -> usize {
+    line.chars().fold(0, |col, ch| match ch {
+        '\t' => col + tab_stop_width(col, tab_width),
+        _ => col + ch.width().unwrap_or(0),
+    })
+}
+
 // Duration multipliers relative to typing speed
 const CURSOR_MOVE_PAUSE: f64 = 0.5; // Cursor movement between lines (base speed)
 const CURSOR_MOVE_SHORT_MULTIPLIER: f64 = 1.0; // Speed for short distances (1-50 lines)
@@ -60,6 +108,41 @@ const COMMIT_OUTPUT_PAUSE: f64 = 33.3; // After commit output
 const GIT_PUSH_PAUSE: f64 = 16.7; // After git push command
 const PUSH_OUTPUT_PAUSE: f64 = 10.0; // Between push output lines
 const PUSH_FINAL_PAUSE: f64 = 66.7; // After final push output
+const TINY_FILE_SETTLE_PAUSE: f64 = 40.0; // After editing a tiny file, so it registers visually
+const SCRAMBLE_FRAME_SPEED: f64 = 0.5; // Each scramble glyph flickers faster than typing
+const PUNCTUATION_PAUSE: f64 = 1.5; // Extra delay after punctuation, scaled by the jitter
+
+/// Glyphs shown while a character scrambles before resolving
+const SCRAMBLE_GLYPHS: &[u8] = b"!<>-_\\/[]{}=+*^?#%&$@0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Default spread of each typed character's delay around the typing speed (off)
+pub const DEFAULT_JITTER: f64 = 0.0;
+
+/// Files with at most this many lines get a settle pause by default (off)
+pub const DEFAULT_TINY_FILE_LINES: usize = 0;
+
+/// Columns between tab stops in the editor by default
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// How long the diff stat summary is shown before a commit's files are animated, by default (off)
+pub const DEFAULT_STAT_PREROLL_MS: u64 = 0;
+
+/// How long the card explaining why a file is skipped is shown, by default (off)
+pub const DEFAULT_EXCLUDED_CARD_MS: u64 = 0;
+
+/// How long the static view shows each file before moving on, by default
+pub const DEFAULT_STATIC_DWELL_MS: u64 = 3000;
+
+/// How a buffer line relates to the file before the change
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LineMark {
+    #[default]
+    Unchanged,
+    /// Inserted by the change
+    Added,
+    /// Kept from the old file, with deleted lines removed right above it
+    Deleted,
+}
 
 /// Represents the current state of the editor buffer
 #[derive(Debug, Clone)]
@@ -78,6 +161,12 @@ pub struct EditorBuffer {
     /// Pre-calculated byte offsets for each line (handles CRLF correctly)
     pub old_content_line_offsets: Vec<usize>,
     pub new_content_line_offsets: Vec<usize>,
+    /// Original file line numbers for each buffer line (changes-only view)
+    pub line_numbers: Option<Vec<usize>>,
+    /// Changed word ranges of inserted lines, by buffer line; missing entries have none
+    pub intraline: Vec<Vec<Range<usize>>>,
+    /// How each buffer line was changed; missing entries are unchanged
+    pub line_marks: Vec<LineMark>,
 }
 
 impl EditorBuffer {
@@ -95,6 +184,9 @@ impl EditorBuffer {
             new_content_lines: Vec::new(),
             old_content_line_offsets: Vec::new(),
             new_content_line_offsets: Vec::new(),
+            line_numbers: None,
+            intraline: Vec::new(),
+            line_marks: Vec::new(),
         }
     }
 
@@ -118,6 +210,9 @@ impl EditorBuffer {
             new_content_lines: Vec::new(),
             old_content_line_offsets: Vec::new(),
             new_content_line_offsets: Vec::new(),
+            line_numbers: None,
+            intraline: Vec::new(),
+            line_marks: Vec::new(),
         }
     }
 
@@ -138,23 +233,99 @@ impl EditorBuffer {
         line_str.insert(byte_idx, ch);
     }
 
+    /// Replaces the character at the specified line and column position.
+    pub fn replace_char(&mut self, line: usize, col: usize, ch: char) {
+        let Some(line_str) = self.lines.get_mut(line) else {
+            return;
+        };
+
+        if let Some((byte_idx, old)) = line_str.char_indices().nth(col) {
+            line_str.replace_range(
+                byte_idx..byte_idx + old.len_utf8(),
+                ch.encode_utf8(&mut [0; 4]),
+            );
+        }
+    }
+
     /// Inserts a new line with the given content at the specified position.
-    pub fn insert_line(&mut self, line: usize, content: String) {
+    pub fn insert_line(&mut self, line: usize, content: String, line_no: Option<usize>) {
         if line > self.lines.len() {
             self.lines.resize(line, String::new());
         }
         self.lines.insert(line, content);
+
+        if let Some(numbers) = self.line_numbers.as_mut() {
+            numbers.insert(line.min(numbers.len()), line_no.unwrap_or(0));
+        }
+        if line <= self.intraline.len() {
+            self.intraline.insert(line, Vec::new());
+        }
+        if line > self.line_marks.len() {
+            self.line_marks.resize(line, LineMark::Unchanged);
+        }
+        // A line typed where others were just deleted replaces them
+        if let Some(mark @ LineMark::Deleted) = self.line_marks.get_mut(line) {
+            *mark = LineMark::Unchanged;
+        }
+        self.line_marks.insert(line, LineMark::Added);
+    }
+
+    /// Records which character ranges of a line changed within it.
+    pub fn set_intraline(&mut self, line: usize, ranges: Vec<Range<usize>>) {
+        if line >= self.intraline.len() {
+            self.intraline.resize(line + 1, Vec::new());
+        }
+        self.intraline[line] = ranges;
     }
 
     /// Deletes the line at the specified position.
     pub fn delete_line(&mut self, line: usize) {
         if line < self.lines.len() {
             self.lines.remove(line);
+            if let Some(numbers) = self.line_numbers.as_mut() {
+                if line < numbers.len() {
+                    numbers.remove(line);
+                }
+            }
+            if line < self.intraline.len() {
+                self.intraline.remove(line);
+            }
+            if line < self.line_marks.len() {
+                self.line_marks.remove(line);
+            }
+            // Flag the deletion on the line that moved up into its place
+            if line < self.lines.len() {
+                if line >= self.line_marks.len() {
+                    self.line_marks.resize(line + 1, LineMark::Unchanged);
+                }
+                if self.line_marks[line] == LineMark::Unchanged {
+                    self.line_marks[line] = LineMark::Deleted;
+                }
+            }
         }
         if self.lines.is_empty() {
             self.lines.push(String::new());
         }
     }
+
+    /// Returns how the line was changed from the old file.
+    pub fn line_mark(&self, line: usize) -> LineMark {
+        self.line_marks.get(line).copied().unwrap_or_default()
+    }
+
+    /// Returns the line number to display for the given buffer line (1-indexed).
+    pub fn display_line_number(&self, line: usize) -> usize {
+        self.line_numbers
+            .as_ref()
+            .and_then(|numbers| numbers.get(line).copied())
+            .unwrap_or(line + 1)
+    }
+}
+
+impl Default for EditorBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Individual animation step
@@ -165,9 +336,16 @@ pub enum AnimationStep {
         col: usize,
         ch: char,
     },
+    ReplaceChar {
+        line: usize,
+        col: usize,
+        ch: char,
+    },
     InsertLine {
         line: usize,
         content: String,
+        line_no: Option<usize>,
+        intraline: Vec<Range<usize>>,
     },
     DeleteLine {
         line: usize,
@@ -179,11 +357,16 @@ pub enum AnimationStep {
     Pause {
         multiplier: f64,
     },
+    /// Pause for a fixed duration, independent of typing speed
+    PauseMs {
+        duration_ms: u64,
+    },
     SwitchFile {
         file_index: usize,
         old_content: String,
         new_content: String,
         path: String,
+        line_numbers: Option<Vec<usize>>,
     },
     OpenFileDialogStart,
     DialogTypeChar {
@@ -197,6 +380,16 @@ pub enum AnimationStep {
         text: String,
     },
     ResetState,
+    /// Show (or with None, hide) the diff stat summary over the editor
+    DiffStat {
+        text: Option<String>,
+    },
+    /// Show (or with None, hide) the card explaining why a file is skipped
+    ExcludedCard {
+        text: Option<String>,
+    },
+    /// Stop until the viewer moves on to another file or commit
+    WaitForKey,
 }
 
 /// Animation state machine
@@ -223,6 +416,8 @@ pub struct AnimationEngine {
     last_update: Instant,
     speed_ms: u64,
     base_speed_ms: u64,
+    /// Runtime speed adjustment from the +/- keys, applied on top of file speeds
+    speed_adjust_ms: i64,
     next_step_delay: u64,
     pause_until: Option<Instant>,
     pub cursor_visible: bool,
@@ -247,12 +442,55 @@ pub struct AnimationEngine {
     pub dialog_title: Option<String>,
     /// Text being typed in the dialog
     pub dialog_typing_text: String,
+    /// Summary of the commit's changes shown before its files are animated
+    pub diff_stat: Option<String>,
+    /// Why the excluded file that is open isn't animated, shown over the editor
+    pub excluded_card: Option<String>,
     /// Current metadata being displayed
     current_metadata: Option<CommitMetadata>,
     /// Pending metadata to be applied on ResetState
     pending_metadata: Option<CommitMetadata>,
     /// Speed rules for different file patterns
     speed_rules: Vec<SpeedRule>,
+    /// Animate only changed lines, skipping unchanged file content
+    changes_only: bool,
+    /// Play each file's edit backwards, from its new content to its old one
+    reverse_file: bool,
+    /// Highlight file content with tree-sitter; off renders it as plain text
+    syntax_highlighting: bool,
+    /// Apply each file's change at once instead of typing it
+    static_view: bool,
+    /// How long the static view shows each file, 0 to wait for a key
+    static_dwell_ms: u64,
+    /// Number of random glyphs each typed character cycles through before resolving
+    scramble_frames: usize,
+    /// Fraction by which each typed character's delay randomly varies (0 for constant timing)
+    jitter: f64,
+    /// Files with at most this many lines get an extra settle pause (0 disables)
+    tiny_file_lines: usize,
+    /// Columns between tab stops when tabs are expanded for display
+    tab_width: usize,
+    /// Only animate this file (and optionally line range) of each commit
+    focus: Option<FileFocus>,
+    /// Extra pause after inserting or deleting a blank line (0 disables)
+    blank_line_pause_ms: u64,
+    /// How long the diff stat summary is shown after a commit loads (0 disables)
+    stat_preroll_ms: u64,
+    /// How long the card for an excluded file is shown (0 disables)
+    excluded_card_ms: u64,
+    /// Growth mode: every file path in the codebase as of the current commit
+    codebase: Option<BTreeSet<String>>,
+    /// Codebase of the loaded commit, shown once its intro has played
+    pending_codebase: Option<BTreeSet<String>>,
+    /// Index of the last editing step of the current file, if it has any
+    last_edit_step: Option<usize>,
+    /// First step of each file's animation, as (file index, step index)
+    file_steps: Vec<(usize, usize)>,
+    /// Step right after the last file's animation
+    files_end: usize,
+    /// Only files whose path contains this (ignoring case) are animated; the rest are
+    /// skipped as playback reaches them
+    file_filter: String,
 }
 
 impl AnimationEngine {
@@ -269,6 +507,7 @@ impl AnimationEngine {
             last_update: now,
             speed_ms,
             base_speed_ms: speed_ms,
+            speed_adjust_ms: 0,
             next_step_delay: speed_ms,
             pause_until: None,
             cursor_visible: true,
@@ -286,9 +525,30 @@ impl AnimationEngine {
             last_frame: now,
             dialog_title: None,
             dialog_typing_text: String::new(),
+            diff_stat: None,
+            excluded_card: None,
             current_metadata: None,
             pending_metadata: None,
             speed_rules: Vec::new(),
+            changes_only: false,
+            reverse_file: false,
+            syntax_highlighting: true,
+            static_view: false,
+            static_dwell_ms: DEFAULT_STATIC_DWELL_MS,
+            scramble_frames: 0,
+            jitter: DEFAULT_JITTER,
+            tiny_file_lines: DEFAULT_TINY_FILE_LINES,
+            tab_width: DEFAULT_TAB_WIDTH,
+            focus: None,
+            blank_line_pause_ms: 0,
+            stat_preroll_ms: DEFAULT_STAT_PREROLL_MS,
+            excluded_card_ms: DEFAULT_EXCLUDED_CARD_MS,
+            codebase: None,
+            pending_codebase: None,
+            last_edit_step: None,
+            file_steps: Vec::new(),
+            files_end: 0,
+            file_filter: String::new(),
         }
     }
 
@@ -297,15 +557,195 @@ impl AnimationEngine {
         self.speed_rules = rules;
     }
 
+    /// Enable or disable the changes-only view (hunk lines without surrounding content)
+    pub fn set_changes_only(&mut self, changes_only: bool) {
+        self.changes_only = changes_only;
+    }
+
+    /// Enable or disable reverse playback, which removes added lines and restores deleted ones
+    pub fn set_reverse_file(&mut self, reverse_file: bool) {
+        self.reverse_file = reverse_file;
+    }
+
+    /// Enable or disable syntax highlighting; without it files are never parsed
+    pub fn set_syntax_highlighting(&mut self, syntax_highlighting: bool) {
+        self.syntax_highlighting = syntax_highlighting;
+    }
+
+    /// Enable or disable the static view, which shows each file's final content at once
+    pub fn set_static_view(&mut self, static_view: bool) {
+        self.static_view = static_view;
+    }
+
+    /// Set how long the static view shows each file before moving on (0 = until a key)
+    pub fn set_static_dwell_ms(&mut self, static_dwell_ms: u64) {
+        self.static_dwell_ms = static_dwell_ms;
+    }
+
+    /// Set how many files' highlights are kept for when the same content shows again
+    pub fn set_highlight_cache_size(&mut self, size: usize) {
+        self.highlighter.borrow_mut().set_cache_size(size);
+    }
+
+    /// Set how many random glyphs each typed character shows before settling (0 disables)
+    pub fn set_scramble_frames(&mut self, scramble_frames: usize) {
+        self.scramble_frames = scramble_frames;
+    }
+
+    /// Set how much typing delays randomly vary around the speed, from 0 (constant) to 1
+    pub fn set_jitter(&mut self, jitter: f64) {
+        self.jitter = jitter.clamp(0.0, 1.0);
+    }
+
+    /// Set the line count at or below which a file gets an extra settle pause (0 disables)
+    pub fn set_tiny_file_lines(&mut self, tiny_file_lines: usize) {
+        self.tiny_file_lines = tiny_file_lines;
+    }
+
+    /// Set the columns between tab stops, used to expand tabs in the editor (at least 1)
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+    }
+
+    /// Columns between tab stops in the editor
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Set the extra pause after blank line insertions and deletions (0 disables)
+    pub fn set_blank_line_pause_ms(&mut self, blank_line_pause_ms: u64) {
+        self.blank_line_pause_ms = blank_line_pause_ms;
+    }
+
+    /// Set how long the diff stat summary is shown before the files are animated (0 disables)
+    pub fn set_stat_preroll_ms(&mut self, stat_preroll_ms: u64) {
+        self.stat_preroll_ms = stat_preroll_ms;
+    }
+
+    /// Set how long an excluded file shows a card with the reason it's skipped (0 disables)
+    pub fn set_excluded_card_ms(&mut self, excluded_card_ms: u64) {
+        self.excluded_card_ms = excluded_card_ms;
+    }
+
+    /// Enable growth mode, starting from the given file paths. Each commit loaded with
+    /// [`load_commit_in_codebase`](Self::load_commit_in_codebase) replaces them with the files
+    /// of its tree, and the editor carries over between commits instead of being cleared.
+    pub fn set_growth_codebase(&mut self, codebase: BTreeSet<String>) {
+        self.codebase = Some(codebase);
+    }
+
+    /// Files in the codebase as of the current commit, in growth mode
+    pub fn codebase(&self) -> Option<&BTreeSet<String>> {
+        self.codebase.as_ref()
+    }
+
+    /// Restrict animation to one file and optionally to hunks overlapping a line range
+    pub fn set_focus(&mut self, focus: Option<FileFocus>) {
+        self.focus = focus;
+    }
+
+    /// Animate only the files whose path contains `filter`, ignoring case (empty animates
+    /// every file). Takes effect right away, moving on if the file being animated is left out.
+    pub fn set_file_filter(&mut self, filter: &str) {
+        self.file_filter = filter.to_string();
+        if self.state == AnimationState::Playing && self.pending_metadata.is_none() {
+            self.skip_filtered_file();
+        }
+    }
+
+    // Whether the file filter keeps the given file of the current commit
+    fn keeps_file(&self, file_index: usize) -> bool {
+        self.current_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.changes.get(file_index))
+            .is_none_or(|change| path_matches_filter(&change.path, &self.file_filter))
+    }
+
+    // Moves past the file being animated while the filter leaves it out, landing on the next
+    // file it keeps or on the steps after the last file
+    fn skip_filtered_file(&mut self) {
+        while let Some(position) = self
+            .file_steps
+            .iter()
+            .rposition(|&(_, step)| step <= self.current_step)
+        {
+            let end = self
+                .file_steps
+                .get(position + 1)
+                .map_or(self.files_end, |&(_, step)| step);
+            if self.current_step >= end || self.keeps_file(self.file_steps[position].0) {
+                break;
+            }
+            self.restart_at_step(end);
+        }
+    }
+
+    /// Push the steps that type one character, scrambling through random glyphs first if enabled
+    fn push_typed_char(&mut self, line: usize, col: usize, ch: char) {
+        if self.scramble_frames == 0 || ch.is_whitespace() {
+            self.steps.push(AnimationStep::InsertChar { line, col, ch });
+            return;
+        }
+
+        let mut rng = rand::rng();
+        let mut glyph = || SCRAMBLE_GLYPHS[rng.random_range(0..SCRAMBLE_GLYPHS.len())] as char;
+
+        self.steps.push(AnimationStep::InsertChar {
+            line,
+            col,
+            ch: glyph(),
+        });
+        for _ in 1..self.scramble_frames {
+            self.steps.push(AnimationStep::ReplaceChar {
+                line,
+                col,
+                ch: glyph(),
+            });
+        }
+        // Always finish on the real character so the content converges
+        self.steps
+            .push(AnimationStep::ReplaceChar { line, col, ch });
+    }
+
     /// Get the speed for a given file path based on speed rules
     /// Returns the first matching rule's speed, or the base speed if no match
     fn get_speed_for_file(&self, path: &str) -> u64 {
-        for rule in &self.speed_rules {
-            if rule.matches(path) {
-                return rule.speed_ms;
-            }
+        let speed_ms = self
+            .speed_rules
+            .iter()
+            .find(|rule| rule.matches(path))
+            .map_or(self.base_speed_ms, |rule| rule.speed_ms);
+        if self.speed_adjust_ms == 0 {
+            return speed_ms;
         }
-        self.base_speed_ms
+        Self::clamp_speed(speed_ms as i64 + self.speed_adjust_ms)
+    }
+
+    fn clamp_speed(speed_ms: i64) -> u64 {
+        speed_ms.clamp(MIN_SPEED_MS as i64, MAX_SPEED_MS as i64) as u64
+    }
+
+    /// Set the base typing speed in milliseconds per character, dropping any runtime adjustment
+    pub fn set_speed(&mut self, speed_ms: u64) {
+        self.speed_ms = speed_ms;
+        self.base_speed_ms = speed_ms;
+        self.speed_adjust_ms = 0;
+        self.next_step_delay = speed_ms;
+    }
+
+    /// Adjust the typing speed at runtime by `delta_ms` (negative is faster).
+    /// The change applies from the next character and carries over to later files.
+    /// Returns the new effective speed in milliseconds.
+    pub fn adjust_speed(&mut self, delta_ms: i64) -> u64 {
+        let speed_ms = Self::clamp_speed(self.speed_ms as i64 + delta_ms);
+        self.speed_adjust_ms += speed_ms as i64 - self.speed_ms as i64;
+        self.speed_ms = speed_ms;
+        speed_ms
+    }
+
+    /// Current effective typing speed in milliseconds
+    pub fn speed_ms(&self) -> u64 {
+        self.speed_ms
     }
 
     /// Sets the viewport height for scroll calculations.
@@ -343,16 +783,39 @@ impl AnimationEngine {
         }
     }
 
+    /// Like [`load_commit`](Self::load_commit), with the paths of every file in the commit's
+    /// tree to list in growth mode once its intro has played
+    pub fn load_commit_in_codebase(
+        &mut self,
+        metadata: &CommitMetadata,
+        codebase: BTreeSet<String>,
+    ) {
+        self.load_commit(metadata);
+        self.pending_codebase = Some(codebase);
+    }
+
     /// Load a commit and generate animation steps
     pub fn load_commit(&mut self, metadata: &CommitMetadata) {
         // Store pending metadata to be applied on ResetState
         self.pending_metadata = Some(metadata.clone());
+        self.pending_codebase = None;
 
         self.steps.clear();
+        self.file_steps.clear();
+        self.files_end = 0;
         self.current_step = 0;
+        self.last_edit_step = None;
         self.state = AnimationState::Playing;
         self.last_update = Instant::now();
         self.pause_until = None;
+        self.diff_stat = None;
+        self.excluded_card = None;
+
+        if self.static_view {
+            self.generate_static_steps(metadata);
+            self.buffer = EditorBuffer::new();
+            return;
+        }
 
         // Check if this is a working tree diff (not a real commit)
         let is_working_tree = metadata.hash == "working-tree";
@@ -376,6 +839,18 @@ impl AnimationEngine {
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_OUTPUT_PAUSE,
             });
+        } else if self.codebase.is_some() {
+            // Growth mode flows straight from one commit into the next
+            self.steps.push(AnimationStep::TerminalOutput {
+                text: format!(
+                    "📈 {} {}",
+                    &metadata.hash[..7.min(metadata.hash.len())],
+                    metadata.message.lines().next().unwrap_or("")
+                ),
+            });
+            self.steps.push(AnimationStep::Pause {
+                multiplier: CHECKOUT_OUTPUT_PAUSE,
+            });
         } else {
             // Time travel to commit date
             let datetime_str = metadata.date.format("%Y-%m-%d %H:%M:%S").to_string();
@@ -413,12 +888,54 @@ impl AnimationEngine {
         // Apply new metadata after intro animation
         self.steps.push(AnimationStep::ResetState);
 
-        // Sort file changes to match FileTree display order (directory -> filename)
-        let sorted_indices = metadata.sorted_file_indices();
+        // A commit that only touches excluded files (e.g. a lock file bump) gets a short note
+        // instead of stepping through every skipped file
+        let only_excluded = metadata.only_excluded_changes();
+
+        // Overview of the whole commit before the first file opens
+        if self.stat_preroll_ms > 0 && !only_excluded && !metadata.changes.is_empty() {
+            self.steps.push(AnimationStep::DiffStat {
+                text: Some(diff_stat_summary(metadata)),
+            });
+            self.steps.push(AnimationStep::PauseMs {
+                duration_ms: self.stat_preroll_ms,
+            });
+            self.steps.push(AnimationStep::DiffStat { text: None });
+        }
+        if only_excluded {
+            self.steps.push(AnimationStep::TerminalOutput {
+                text: format!(
+                    "📦 commit only touched excluded files ({} skipped)",
+                    metadata.changes.len()
+                ),
+            });
+            self.steps.push(AnimationStep::Pause {
+                multiplier: OPEN_CMD_PAUSE,
+            });
+        }
+
+        // Files follow FileTree display order (directory -> filename) unless another order was requested
+        let sorted_indices = if only_excluded {
+            Vec::new()
+        } else {
+            metadata.playback_file_indices()
+        };
 
         // Process all file changes in sorted order
         for &index in &sorted_indices {
             let change = &metadata.changes[index];
+
+            // With a focus file, every other file is left out of the animation
+            if self
+                .focus
+                .as_ref()
+                .is_some_and(|focus| focus.path != change.path)
+            {
+                continue;
+            }
+
+            self.file_steps.push((index, self.steps.len()));
+
             match (change.is_excluded, &change.status) {
                 // Skip excluded files (lock files and generated files)
                 (true, _) => {
@@ -430,15 +947,26 @@ impl AnimationEngine {
                         old_content,
                         new_content,
                         path: change.path.clone(),
+                        line_numbers: None,
                     });
 
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_FILE_PAUSE,
-                    });
                     let reason = change
                         .exclusion_reason
                         .as_deref()
                         .unwrap_or("excluded file");
+                    if self.excluded_card_ms > 0 {
+                        self.steps.push(AnimationStep::ExcludedCard {
+                            text: Some(format!("⏭ {} skipped: {}", change.path, reason)),
+                        });
+                        self.steps.push(AnimationStep::PauseMs {
+                            duration_ms: self.excluded_card_ms,
+                        });
+                        self.steps.push(AnimationStep::ExcludedCard { text: None });
+                    } else {
+                        self.steps.push(AnimationStep::Pause {
+                            multiplier: OPEN_FILE_PAUSE,
+                        });
+                    }
                     self.steps.push(AnimationStep::TerminalOutput {
                         text: format!("📦 {} (skipped - {})", change.path, reason),
                     });
@@ -455,6 +983,7 @@ impl AnimationEngine {
                         old_content,
                         new_content: String::new(),
                         path: change.path.clone(),
+                        line_numbers: None,
                     });
 
                     self.steps.push(AnimationStep::Pause {
@@ -469,8 +998,8 @@ impl AnimationEngine {
                         multiplier: GIT_ADD_CMD_PAUSE,
                     });
                 }
-                // For renamed/moved files, skip editor animation and only run mv + git add
-                (false, FileStatus::Renamed) => {
+                // For files moved without edits, skip editor animation and only run mv + git add
+                (false, FileStatus::Renamed) if change.hunks.is_empty() => {
                     // Switch to the renamed file to show in file tree
                     let old_content = change.old_content.clone().unwrap_or_default();
                     let new_content = change.new_content.clone().unwrap_or_default();
@@ -479,6 +1008,7 @@ impl AnimationEngine {
                         old_content,
                         new_content,
                         path: change.path.clone(),
+                        line_numbers: None,
                     });
 
                     self.steps.push(AnimationStep::Pause {
@@ -497,6 +1027,20 @@ impl AnimationEngine {
                 }
                 // Normal files (Added, Modified, etc.) - full editor animation
                 (false, _) => {
+                    // Edited renames and copies start from the file at its old path
+                    if let Some(old_path) = &change.old_path {
+                        let command = match change.status {
+                            FileStatus::Copied => "cp",
+                            _ => "mv",
+                        };
+                        self.add_terminal_command(&format!(
+                            "{} {} {}",
+                            command, old_path, change.path
+                        ));
+                        self.steps.push(AnimationStep::Pause {
+                            multiplier: GIT_ADD_CMD_PAUSE,
+                        });
+                    }
                     // Open file in editor
                     if index == 0 {
                         self.steps.push(AnimationStep::Pause {
@@ -520,6 +1064,15 @@ impl AnimationEngine {
                         multiplier: OPEN_CMD_PAUSE,
                     });
 
+                    let is_tiny_file = self.tiny_file_lines > 0
+                        && change
+                            .new_content
+                            .as_deref()
+                            .map_or(0, |content| content.lines().count())
+                            <= self.tiny_file_lines;
+
+                    let (change, line_numbers) = self.prepare_change(change);
+
                     // Add file switch step with both old and new content
                     let old_content = change.old_content.clone().unwrap_or_default();
                     let new_content = change.new_content.clone().unwrap_or_default();
@@ -528,6 +1081,7 @@ impl AnimationEngine {
                         old_content,
                         new_content,
                         path: change.path.clone(),
+                        line_numbers,
                     });
 
                     // Add pause before starting file animation
@@ -536,7 +1090,14 @@ impl AnimationEngine {
                     });
 
                     // Generate animation steps for this file
-                    self.generate_steps_for_file(change);
+                    self.generate_steps_for_file(&change);
+
+                    // Tiny files finish in a blink, so let them linger before moving on
+                    if is_tiny_file {
+                        self.steps.push(AnimationStep::Pause {
+                            multiplier: TINY_FILE_SETTLE_PAUSE,
+                        });
+                    }
 
                     // Git add this file after editing
                     self.steps.push(AnimationStep::Pause {
@@ -549,6 +1110,7 @@ impl AnimationEngine {
                 }
             }
         }
+        self.files_end = self.steps.len();
 
         // Skip git commit/push animation for working tree diffs
         if is_working_tree {
@@ -623,6 +1185,82 @@ impl AnimationEngine {
         self.buffer = EditorBuffer::new();
     }
 
+    /// The change as it is animated, with the focus, reverse and changes-only settings
+    /// applied, and the original line numbers for a changes-only view
+    fn prepare_change(&self, change: &FileChange) -> (FileChange, Option<Vec<usize>>) {
+        // Only type the hunks overlapping the focused lines, if any overlap
+        let focused = self
+            .focus
+            .as_ref()
+            .and_then(|focus| focus.lines)
+            .and_then(|(start, end)| change.focus_lines(start, end));
+        let change = focused.as_ref().unwrap_or(change);
+
+        // Rewind the file by animating the inverse change, so the new content
+        // (and its highlighting) comes first and the old content is typed back
+        let reversed = self.reverse_file.then(|| change.reversed());
+        let change = reversed.as_ref().unwrap_or(change);
+
+        // In changes-only mode, animate a compact view of just the hunk lines
+        if self.changes_only {
+            (change.changes_only(), Some(change.deleted_line_numbers()))
+        } else {
+            (change.clone(), None)
+        }
+    }
+
+    /// Steps for the static view: no terminal session or typing, each file's change is
+    /// applied in one go and shown for the dwell time or until a key moves on
+    fn generate_static_steps(&mut self, metadata: &CommitMetadata) {
+        self.steps.push(AnimationStep::TerminalOutput {
+            text: format!(
+                "📍 {} {}",
+                &metadata.hash[..7.min(metadata.hash.len())],
+                metadata.message.lines().next().unwrap_or("")
+            ),
+        });
+        self.steps.push(AnimationStep::ResetState);
+
+        for index in metadata.playback_file_indices() {
+            let change = &metadata.changes[index];
+            let unfocused = self
+                .focus
+                .as_ref()
+                .is_some_and(|focus| focus.path != change.path);
+            if unfocused || change.is_excluded {
+                continue;
+            }
+
+            self.file_steps.push((index, self.steps.len()));
+            if change.status == FileStatus::Deleted {
+                // Nothing to apply; the file is shown as it was before being removed
+                self.steps.push(AnimationStep::SwitchFile {
+                    file_index: index,
+                    old_content: change.old_content.clone().unwrap_or_default(),
+                    new_content: String::new(),
+                    path: change.path.clone(),
+                    line_numbers: None,
+                });
+            } else {
+                let (change, line_numbers) = self.prepare_change(change);
+                self.steps.push(AnimationStep::SwitchFile {
+                    file_index: index,
+                    old_content: change.old_content.clone().unwrap_or_default(),
+                    new_content: change.new_content.clone().unwrap_or_default(),
+                    path: change.path.clone(),
+                    line_numbers,
+                });
+                self.generate_steps_for_file(&change);
+            }
+
+            self.steps.push(match self.static_dwell_ms {
+                0 => AnimationStep::WaitForKey,
+                duration_ms => AnimationStep::PauseMs { duration_ms },
+            });
+        }
+        self.files_end = self.steps.len();
+    }
+
     /// Generate animation steps for a file change
     fn generate_steps_for_file(&mut self, change: &FileChange) {
         let mut current_cursor_line = 0;
@@ -761,6 +1399,15 @@ impl AnimationEngine {
         }
     }
 
+    /// Linger on blank line edits so spacing-only changes stay visible
+    fn push_blank_line_pause(&mut self, content: &str) {
+        if self.blank_line_pause_ms > 0 && content.trim().is_empty() {
+            self.steps.push(AnimationStep::PauseMs {
+                duration_ms: self.blank_line_pause_ms,
+            });
+        }
+    }
+
     /// Generate animation steps for a diff hunk
     /// Returns (final_cursor_line, final_buffer_line)
     fn generate_steps_for_hunk(
@@ -782,6 +1429,7 @@ impl AnimationEngine {
                     self.steps.push(AnimationStep::Pause {
                         multiplier: DELETE_LINE_PAUSE,
                     });
+                    self.push_blank_line_pause(&line_change.content);
                     cursor_line = buffer_line;
                     // After deletion, buffer_line stays the same
                     // (the next line moves up to this position)
@@ -795,15 +1443,13 @@ impl AnimationEngine {
                     self.steps.push(AnimationStep::InsertLine {
                         line: buffer_line,
                         content: indentation,
+                        line_no: line_change.new_line_no,
+                        intraline: line_change.intraline.clone().unwrap_or_default(),
                     });
 
                     // Type each character after the indentation
                     for (i, ch) in content.chars().skip(indentation_len).enumerate() {
-                        self.steps.push(AnimationStep::InsertChar {
-                            line: buffer_line,
-                            col: indentation_len + i,
-                            ch,
-                        });
+                        self.push_typed_char(buffer_line, indentation_len + i, ch);
                     }
 
                     cursor_line = buffer_line;
@@ -812,6 +1458,7 @@ impl AnimationEngine {
                     self.steps.push(AnimationStep::Pause {
                         multiplier: INSERT_LINE_PAUSE,
                     });
+                    self.push_blank_line_pause(content);
                 }
                 LineChangeType::Context => {
                     // Move cursor to next line if needed
@@ -895,6 +1542,19 @@ impl AnimationEngine {
                 break;
             }
 
+            // Files left out by the filter are skipped as playback reaches them
+            if !self.file_filter.is_empty()
+                && self
+                    .file_steps
+                    .binary_search_by_key(&self.current_step, |&(_, step)| step)
+                    .is_ok()
+            {
+                self.skip_filtered_file();
+                if self.current_step >= self.steps.len() {
+                    break;
+                }
+            }
+
             let step_delay = self.next_step_delay;
             let step = self.steps[self.current_step].clone();
 
@@ -902,6 +1562,14 @@ impl AnimationEngine {
             self.current_step += 1;
             executed_any = true;
             accumulated_delay += step_delay;
+
+            // Steps take no time in the static view, so stop at its dwell pause or wait. The
+            // card for a skipped file is held the same way, so it's drawn even at full speed.
+            let dwelling =
+                self.pause_until.is_some() && (self.static_view || self.excluded_card.is_some());
+            if dwelling || self.state != AnimationState::Playing {
+                break;
+            }
         }
 
         if executed_any {
@@ -922,19 +1590,35 @@ impl AnimationEngine {
         accumulated_delay + self.next_step_delay <= self.frame_interval_ms
     }
 
+    // Delay after typing `ch`, varied by up to ±jitter and lengthened after punctuation
+    // to mimic the rhythm of human typing. Constant when jitter is 0.
+    fn typing_delay(&self, base_ms: f64, ch: char, rng: &mut impl Rng) -> u64 {
+        if self.jitter <= 0.0 {
+            return base_ms as u64;
+        }
+        let variation = rng.random_range(1.0 - self.jitter..=1.0 + self.jitter);
+        let rhythm = if matches!(ch, '.' | ',' | ';' | ':' | '!' | '?') {
+            1.0 + PUNCTUATION_PAUSE * self.jitter
+        } else {
+            1.0
+        };
+        (base_ms * variation * rhythm) as u64
+    }
+
     fn execute_step(&mut self, step: AnimationStep) {
         // Calculate delay for next step with randomization for typing steps
-        let mut rng = rand::rng();
         self.next_step_delay = match &step {
-            AnimationStep::InsertChar { .. } | AnimationStep::TerminalTypeChar { .. } => {
-                // Add 70-130% variation to typing speed
-                let variation = rng.random_range(0.7..=1.3);
-                ((self.speed_ms as f64) * variation) as u64
+            // The static view applies a file's edits in one go; only its dwell pauses take time
+            _ if self.static_view => 0,
+            AnimationStep::InsertChar { ch, .. } | AnimationStep::TerminalTypeChar { ch } => {
+                self.typing_delay(self.speed_ms as f64, *ch, &mut rand::rng())
             }
-            AnimationStep::DialogTypeChar { .. } => {
-                // Dialog typing is slower (2x speed with variation)
-                let variation = rng.random_range(0.7..=1.3);
-                ((self.speed_ms as f64) * 2.0 * variation) as u64
+            AnimationStep::DialogTypeChar { ch } => {
+                // Dialog typing is slower (2x speed)
+                self.typing_delay(self.speed_ms as f64 * 2.0, *ch, &mut rand::rng())
+            }
+            AnimationStep::ReplaceChar { .. } => {
+                ((self.speed_ms as f64) * SCRAMBLE_FRAME_SPEED) as u64
             }
             AnimationStep::Pause { .. } => {
                 // Pause timing is driven by `pause_until`; don't add extra delay
@@ -953,10 +1637,24 @@ impl AnimationEngine {
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = col + 1;
             }
-            AnimationStep::InsertLine { line, content } => {
+            AnimationStep::ReplaceChar { line, col, ch } => {
+                self.active_pane = ActivePane::Editor;
+                self.buffer.replace_char(line, col, ch);
+                self.buffer.cursor_line = line;
+                self.buffer.cursor_col = col + 1;
+            }
+            AnimationStep::InsertLine {
+                line,
+                content,
+                line_no,
+                intraline,
+            } => {
                 self.active_pane = ActivePane::Editor;
                 let content_len = content.chars().count();
-                self.buffer.insert_line(line, content);
+                self.buffer.insert_line(line, content, line_no);
+                if !intraline.is_empty() {
+                    self.buffer.set_intraline(line, intraline);
+                }
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = content_len;
 
@@ -983,10 +1681,17 @@ impl AnimationEngine {
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = col;
             }
+            AnimationStep::Pause { .. } if self.static_view => {}
             AnimationStep::Pause { multiplier } => {
                 let duration_ms = (self.speed_ms as f64 * multiplier) as u64;
                 self.pause_until = Some(Instant::now() + Duration::from_millis(duration_ms));
             }
+            AnimationStep::PauseMs { duration_ms } => {
+                self.pause_until = Some(Instant::now() + Duration::from_millis(duration_ms));
+            }
+            AnimationStep::WaitForKey => {
+                self.state = AnimationState::Idle;
+            }
             AnimationStep::OpenFileDialogStart => {
                 self.dialog_typing_text = String::new();
                 self.dialog_title = Some("Open File...".to_string());
@@ -999,6 +1704,7 @@ impl AnimationEngine {
                 old_content,
                 new_content,
                 path,
+                line_numbers,
             } => {
                 self.active_pane = ActivePane::Editor;
                 // Clear dialog when file is actually switched
@@ -1008,17 +1714,31 @@ impl AnimationEngine {
                 self.current_file_index = file_index;
                 self.current_file_path = Some(path.clone());
                 self.buffer = EditorBuffer::from_content(&old_content);
+                self.buffer.line_numbers = line_numbers;
+
+                self.last_edit_step = self.find_last_edit_step();
 
                 // Update typing speed based on file-specific rules
                 self.speed_ms = self.get_speed_for_file(&path);
 
-                // Update syntax highlighter for new file
-                // This will clear language settings if not supported
-                self.highlighter.borrow_mut().set_language_from_path(&path);
+                if self.syntax_highlighting {
+                    // Update syntax highlighter for new file
+                    // This will clear language settings if not supported
+                    let mut highlighter = self.highlighter.borrow_mut();
+                    if !highlighter.set_language_from_path(&path) {
+                        // Extensionless scripts: fall back to the shebang line
+                        let content = if new_content.is_empty() {
+                            &old_content
+                        } else {
+                            &new_content
+                        };
+                        highlighter.set_language_from_content(content);
+                    }
 
-                // Pre-calculate highlights for both old and new content
-                self.buffer.old_highlights = self.highlighter.borrow_mut().highlight(&old_content);
-                self.buffer.new_highlights = self.highlighter.borrow_mut().highlight(&new_content);
+                    // Pre-calculate highlights for both old and new content
+                    self.buffer.old_highlights = highlighter.highlight(&old_content);
+                    self.buffer.new_highlights = highlighter.highlight(&new_content);
+                }
 
                 // Store content lines for byte offset calculation
                 self.buffer.old_content_lines = if old_content.is_empty() {
@@ -1062,14 +1782,28 @@ impl AnimationEngine {
             AnimationStep::ResetState => {
                 // Apply pending metadata and reset UI state after time-travel animation
                 if let Some(metadata) = self.pending_metadata.take() {
+                    if let Some(codebase) = self.pending_codebase.take() {
+                        if self.codebase.is_some() {
+                            self.codebase = Some(codebase);
+                        }
+                    }
                     self.current_metadata = Some(metadata);
                 }
                 self.current_file_index = 0;
-                // Keep terminal_lines to preserve time-travel command and output
-                self.buffer = EditorBuffer::new();
-                self.current_file_path = None;
+                // Keep terminal_lines to preserve time-travel command and output.
+                // Growth mode keeps the last file on screen until the next one opens.
+                if self.codebase.is_none() {
+                    self.buffer = EditorBuffer::new();
+                    self.current_file_path = None;
+                }
                 self.active_pane = ActivePane::Terminal;
             }
+            AnimationStep::DiffStat { text } => {
+                self.diff_stat = text;
+            }
+            AnimationStep::ExcludedCard { text } => {
+                self.excluded_card = text;
+            }
         }
 
         // Update scroll to keep cursor centered
@@ -1095,7 +1829,7 @@ impl AnimationEngine {
         }
 
         // Calculate how many lines this text will take when wrapped (using display width)
-        let display_width = line.width();
+        let display_width = expanded_width(line, self.tab_width);
         display_width.div_ceil(text_width).max(1)
     }
 
@@ -1143,8 +1877,234 @@ impl AnimationEngine {
         self.buffer.scroll_offset = logical_offset;
     }
 
+    /// Scrolls the editor by `delta` lines (negative is up), within the current file.
+    /// The view follows the cursor again once the next edit is typed.
+    pub fn scroll_editor(&mut self, delta: isize) {
+        let max_offset = self.buffer.lines.len().saturating_sub(self.viewport_height);
+        self.buffer.scroll_offset = self
+            .buffer
+            .scroll_offset
+            .saturating_add_signed(delta)
+            .min(max_offset);
+    }
+
+    /// Restarts the animation from the given file of the current commit, even if playback
+    /// had already finished. Returns false if the file isn't part of the animation or the
+    /// commit's intro is still playing.
+    pub fn jump_to_file(&mut self, file_index: usize) -> bool {
+        if self.pending_metadata.is_some() {
+            return false;
+        }
+        let Some(&(_, step)) = self
+            .file_steps
+            .iter()
+            .find(|(index, _)| *index == file_index)
+        else {
+            return false;
+        };
+
+        self.restart_at_step(step);
+        true
+    }
+
+    // Continues playback from `step`, dropping whatever the step being left had on screen
+    fn restart_at_step(&mut self, step: usize) {
+        self.current_step = step;
+        self.dialog_title = None;
+        self.dialog_typing_text = String::new();
+        self.diff_stat = None;
+        self.excluded_card = None;
+        self.pause_until = None;
+        self.state = AnimationState::Playing;
+    }
+
+    /// Restarts the animation from the next file of the current commit in playback order,
+    /// or from the previous one when `forward` is false. Past the last or first file it
+    /// wraps around if `wrap` is set. Returns false if there is no file to move to.
+    pub fn jump_to_adjacent_file(&mut self, forward: bool, wrap: bool) -> bool {
+        if self.pending_metadata.is_some() || self.file_steps.is_empty() {
+            return false;
+        }
+
+        // The file being animated is the last one whose first step has been reached
+        let current = self
+            .file_steps
+            .iter()
+            .rposition(|&(_, step)| step <= self.current_step);
+        // Only files the filter keeps can be moved to
+        let kept: Vec<usize> = (0..self.file_steps.len())
+            .filter(|&position| self.keeps_file(self.file_steps[position].0))
+            .collect();
+        let target = if forward {
+            kept.iter()
+                .find(|&&position| current.is_none_or(|current| position > current))
+                .or(kept.first().filter(|_| wrap))
+        } else {
+            kept.iter()
+                .rev()
+                .find(|&&position| current.is_some_and(|current| position < current))
+                .or(kept.last().filter(|_| wrap))
+        };
+
+        match target {
+            Some(&position) => self.jump_to_file(self.file_steps[position].0),
+            None => false,
+        }
+    }
+
+    /// Returns true while the editor still has edits left to type in the current file.
+    pub fn is_typing(&self) -> bool {
+        self.active_pane == ActivePane::Editor
+            && self
+                .last_edit_step
+                .is_some_and(|last| self.current_step <= last)
+    }
+
+    // Scan the steps of the file being switched to for its last edit
+    fn find_last_edit_step(&self) -> Option<usize> {
+        let mut last = None;
+        for (index, step) in self.steps.iter().enumerate().skip(self.current_step + 1) {
+            match step {
+                AnimationStep::SwitchFile { .. } | AnimationStep::OpenFileDialogStart => break,
+                AnimationStep::InsertChar { .. }
+                | AnimationStep::ReplaceChar { .. }
+                | AnimationStep::InsertLine { .. }
+                | AnimationStep::DeleteLine { .. } => last = Some(index),
+                _ => {}
+            }
+        }
+        last
+    }
+
     /// Returns true if the animation has completed.
     pub fn is_finished(&self) -> bool {
         self.state == AnimationState::Finished
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::LineChange;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // A commit changing each of `paths` from "a" to "b"
+    fn commit_with_files(paths: &[&str]) -> CommitMetadata {
+        let line = |change_type, content: &str, old_line_no, new_line_no| LineChange {
+            change_type,
+            content: format!("{}\n", content),
+            old_line_no,
+            new_line_no,
+            intraline: None,
+        };
+        let changes = paths
+            .iter()
+            .map(|path| FileChange {
+                hunks: vec![DiffHunk {
+                    old_start: 1,
+                    old_lines: 1,
+                    new_start: 1,
+                    new_lines: 1,
+                    lines: vec![
+                        line(LineChangeType::Deletion, "a", Some(1), None),
+                        line(LineChangeType::Addition, "b", None, Some(1)),
+                    ],
+                }],
+                ..FileChange::test_modified(path)
+            })
+            .collect();
+        CommitMetadata::test_with(changes)
+    }
+
+    // Executes steps without waiting out their delays until `done` holds or the steps run out
+    fn step_until(engine: &mut AnimationEngine, done: impl Fn(&AnimationEngine) -> bool) {
+        while !done(engine) && engine.current_step < engine.steps.len() {
+            let step = engine.steps[engine.current_step].clone();
+            engine.execute_step(step);
+            engine.current_step += 1;
+        }
+    }
+
+    #[test]
+    fn test_typing_delay_stays_within_the_jitter() {
+        let mut engine = AnimationEngine::new(100);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(engine.typing_delay(100.0, 'a', &mut rng), 100);
+
+        engine.set_jitter(0.5);
+        let letters: Vec<u64> = (0..500)
+            .map(|_| engine.typing_delay(100.0, 'a', &mut rng))
+            .collect();
+        assert!(letters.iter().all(|&delay| (50..=150).contains(&delay)));
+        assert!(letters.iter().min() < letters.iter().max());
+
+        // Punctuation pauses 1.75 times longer at this jitter
+        assert!((0..500)
+            .map(|_| engine.typing_delay(100.0, '.', &mut rng))
+            .all(|delay| (87..=262).contains(&delay)));
+    }
+
+    #[test]
+    fn test_is_typing_until_the_last_edit_of_a_file() {
+        let mut engine = AnimationEngine::new(10);
+        engine.load_commit(&commit_with_files(&["a.rs", "b.rs"]));
+        assert!(!engine.is_typing());
+
+        step_until(&mut engine, AnimationEngine::is_typing);
+        assert_eq!(engine.active_pane, ActivePane::Editor);
+        let last_edit = engine.last_edit_step.unwrap();
+        assert!(engine.current_step <= last_edit);
+
+        // Typing stops right after the file's last edit, while it's still on screen
+        step_until(&mut engine, |engine| !engine.is_typing());
+        assert_eq!(engine.current_step, last_edit + 1);
+        assert_eq!(engine.current_file_index, 0);
+    }
+
+    #[test]
+    fn test_files_are_not_highlighted_without_syntax_highlighting() {
+        let mut commit = commit_with_files(&["main.rs"]);
+        commit.changes[0].old_content = Some("fn a() {}\n".to_string());
+        let opened = |engine: &AnimationEngine| engine.current_file_path.is_some();
+
+        let mut engine = AnimationEngine::new(10);
+        engine.load_commit(&commit);
+        step_until(&mut engine, opened);
+        assert!(!engine.buffer.old_highlights.is_empty());
+
+        let mut engine = AnimationEngine::new(10);
+        engine.set_syntax_highlighting(false);
+        engine.load_commit(&commit);
+        step_until(&mut engine, opened);
+        assert!(engine.buffer.old_highlights.is_empty());
+        assert!(engine.buffer.cached_highlights.is_empty());
+    }
+
+    #[test]
+    fn test_jump_to_adjacent_file_wraps_only_when_asked() {
+        let mut engine = AnimationEngine::new(10);
+        engine.load_commit(&commit_with_files(&["a.rs", "b.rs", "c.rs"]));
+        // Nothing to jump between while the commit's intro plays
+        assert!(!engine.jump_to_adjacent_file(true, true));
+
+        step_until(&mut engine, |engine| engine.current_file_path.is_some());
+        let starts: Vec<usize> = engine.file_steps.iter().map(|&(_, step)| step).collect();
+        assert_eq!(starts.len(), 3);
+
+        assert!(engine.jump_to_adjacent_file(true, false));
+        assert_eq!(engine.current_step, starts[1]);
+        assert!(engine.jump_to_adjacent_file(true, false));
+        assert_eq!(engine.current_step, starts[2]);
+        assert!(!engine.jump_to_adjacent_file(true, false));
+        assert_eq!(engine.current_step, starts[2]);
+        assert!(engine.jump_to_adjacent_file(true, true));
+        assert_eq!(engine.current_step, starts[0]);
+
+        assert!(!engine.jump_to_adjacent_file(false, false));
+        assert!(engine.jump_to_adjacent_file(false, true));
+        assert_eq!(engine.current_step, starts[2]);
+        assert!(engine.jump_to_adjacent_file(false, false));
+        assert_eq!(engine.current_step, starts[1]);
+    }
+}
@@ -1,26 +1,326 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::animation::{
+    DEFAULT_EXCLUDED_CARD_MS, DEFAULT_JITTER, DEFAULT_STATIC_DWELL_MS, DEFAULT_STAT_PREROLL_MS,
+    DEFAULT_TAB_WIDTH, DEFAULT_TINY_FILE_LINES,
+};
+use crate::git::{
+    DEFAULT_MAX_BLOB_SIZE, DEFAULT_MAX_CHANGE_LINES, DEFAULT_RANDOM_HISTORY,
+    DEFAULT_RENAME_THRESHOLD,
+};
+use crate::syntax::DEFAULT_HIGHLIGHT_CACHE_SIZE;
+use crate::theme::Theme;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default = "default_theme")]
     pub theme: String,
+    #[serde(default)]
+    pub syntax_theme: Option<String>,
     #[serde(default = "default_speed")]
     pub speed: u64,
+    #[serde(default)]
+    pub speed_cps: Option<f64>,
     #[serde(default = "default_background")]
     pub background: bool,
     #[serde(default = "default_order")]
     pub order: String,
-    #[serde(default = "default_loop")]
+    #[serde(default = "default_loop", rename = "loop", alias = "loop_playback")]
     pub loop_playback: bool,
     #[serde(default = "default_ignore_patterns")]
     pub ignore_patterns: Vec<String>,
     #[serde(default)]
     pub speed_rules: Vec<String>,
+    #[serde(default)]
+    pub changes_only: bool,
+    #[serde(default)]
+    pub reverse_file: bool,
+    #[serde(default = "default_syntax_highlighting")]
+    pub syntax_highlighting: bool,
+    #[serde(default = "default_highlight_cache_size")]
+    pub highlight_cache_size: usize,
+    #[serde(default)]
+    pub static_view: bool,
+    #[serde(default = "default_static_dwell_ms")]
+    pub static_dwell_ms: u64,
+    #[serde(default)]
+    pub max_line_length: Option<usize>,
+    #[serde(default)]
+    pub max_files: Option<usize>,
+    #[serde(default)]
+    pub root_max_files: Option<usize>,
+    #[serde(default)]
+    pub hide_excluded: bool,
+    #[serde(default = "default_max_blob_size")]
+    pub max_blob_size: usize,
+    #[serde(default = "default_max_change_lines")]
+    pub max_change_lines: usize,
+    #[serde(default)]
+    pub scramble_frames: usize,
+    #[serde(default = "default_jitter")]
+    pub jitter: f64,
+    #[serde(default = "default_tiny_file_lines")]
+    pub tiny_file_lines: usize,
+    #[serde(default)]
+    pub blank_line_pause_ms: u64,
+    #[serde(default = "default_stat_preroll_ms")]
+    pub stat_preroll_ms: u64,
+    #[serde(default = "default_excluded_card_ms")]
+    pub excluded_card_ms: u64,
+    #[serde(default)]
+    pub respect_gitignore: bool,
+    #[serde(default = "default_normalize_line_endings")]
+    pub normalize_line_endings: bool,
+    #[serde(default)]
+    pub include_merges: bool,
+    #[serde(default)]
+    pub skip_root_commits: bool,
+    #[serde(default)]
+    pub resume: bool,
+    #[serde(default = "default_random_history")]
+    pub random_history: usize,
+    #[serde(default = "default_rename_threshold")]
+    pub rename_threshold: u8,
+    #[serde(default)]
+    pub set_terminal_title: bool,
+    #[serde(default)]
+    pub dim_typed_lines: bool,
+    #[serde(default)]
+    pub show_whitespace: bool,
+    #[serde(default = "default_tab_width")]
+    pub tab_width: usize,
+    #[serde(default)]
+    pub show_minimap: bool,
+    #[serde(default = "default_match_brackets")]
+    pub match_brackets: bool,
+    #[serde(default = "default_show_line_numbers")]
+    pub show_line_numbers: bool,
+    #[serde(default = "default_show_sidebar")]
+    pub show_sidebar: bool,
+    #[serde(default = "default_wrap_file_jumps")]
+    pub wrap_file_jumps: bool,
+    #[serde(default = "default_date_display")]
+    pub date_display: String,
+    #[serde(default = "default_color")]
+    pub color: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default = "default_sort")]
+    pub sort: String,
+    #[serde(default = "default_file_order")]
+    pub file_order: String,
+    #[serde(default = "default_file_tree_sort")]
+    pub file_tree_sort: String,
+    #[serde(default = "default_on_end")]
+    pub on_end: String,
+    #[serde(default)]
+    pub hold: Option<u64>,
 }
 
+/// Name of the per-repository config file, looked up in the repository root
+pub const REPO_CONFIG_FILE: &str = ".gitlogue.toml";
+
+// Comment written above each setting by `config init`, in the order they are written
+const SETTING_COMMENTS: &[(&str, &str)] = &[
+    ("theme", "Theme to use for syntax highlighting"),
+    (
+        "syntax_theme",
+        "Theme whose code colors replace those of theme, keeping its UI colors",
+    ),
+    ("speed", "Typing speed in milliseconds per character"),
+    (
+        "speed_cps",
+        "Typing speed in characters per second, used instead of speed when set",
+    ),
+    (
+        "background",
+        "Show background colors (set to false for transparent background)",
+    ),
+    ("color", "Colors the terminal supports: auto, truecolor, 256 or 16"),
+    ("order", "Commit playback order: random, asc, or desc"),
+    ("loop", "Loop the animation continuously"),
+    (
+        "on_end",
+        "When asc/desc playback runs out of commits: \"exit\", \"loop\" or \"hold\"",
+    ),
+    (
+        "hold",
+        "Milliseconds a finished commit stays on screen before the next one (default: 100x speed)",
+    ),
+    (
+        "resume",
+        "Continue asc/desc playback where the previous run stopped",
+    ),
+    (
+        "random_history",
+        "Number of recent commits random playback won't repeat (0 allows repeats)",
+    ),
+    (
+        "sort",
+        "Order of commits for asc/desc playback: \"topo\" (history order) or \"date\"",
+    ),
+    (
+        "author",
+        "Only play commits whose author name or email contains this text",
+    ),
+    (
+        "include_merges",
+        "Also play merge commits, diffed against their first parent",
+    ),
+    (
+        "skip_root_commits",
+        "Leave out root commits (which add the whole initial tree) when picking random commits",
+    ),
+    (
+        "ignore_patterns",
+        "Ignore patterns (gitignore syntax)\nExamples: [\"*.png\", \"*.ipynb\", \"dist/**\"]",
+    ),
+    (
+        "respect_gitignore",
+        "Skip tracked files that match the repository's .gitignore rules",
+    ),
+    (
+        "normalize_line_endings",
+        "Treat CRLF line endings as LF (set to false to keep literal carriage returns)",
+    ),
+    (
+        "max_blob_size",
+        "Largest file in bytes whose content is animated",
+    ),
+    (
+        "max_change_lines",
+        "Skip files with more changed lines than this",
+    ),
+    ("max_files", "Animate at most this many files of each commit"),
+    (
+        "root_max_files",
+        "Animate at most this many files of a root commit, such as an initial import",
+    ),
+    (
+        "hide_excluded",
+        "Leave excluded and binary files out of the file tree and playback",
+    ),
+    (
+        "rename_threshold",
+        "Similarity (0-100) for a removed and an added file to count as a rename (0 = off)",
+    ),
+    (
+        "file_order",
+        "Order files within a commit: \"path\" (file tree order) or \"recency\"",
+    ),
+    (
+        "file_tree_sort",
+        "Sort the file tree by \"path\", \"magnitude\" (most changed first) or \"status\"",
+    ),
+    (
+        "speed_rules",
+        "Speed rules for different file types (pattern:milliseconds)\nExamples: [\"*.java:50\", \"*.xml:5\", \"*.rs:30\"]",
+    ),
+    (
+        "jitter",
+        "Random spread of each character's typing delay, 0.0 (constant) to 1.0",
+    ),
+    (
+        "scramble_frames",
+        "Random glyphs each typed character cycles through before resolving (0 = off)",
+    ),
+    (
+        "tiny_file_lines",
+        "Files with at most this many lines pause briefly after editing (0 = off)",
+    ),
+    (
+        "blank_line_pause_ms",
+        "Extra pause in milliseconds after adding or removing a blank line (0 = off)",
+    ),
+    (
+        "stat_preroll_ms",
+        "Milliseconds the diff stat summary shows before a commit's files (0 = off)",
+    ),
+    (
+        "excluded_card_ms",
+        "Milliseconds a card shows why an excluded file is skipped (0 = off)",
+    ),
+    (
+        "changes_only",
+        "Show only changed lines in the editor (no surrounding file content)",
+    ),
+    (
+        "reverse_file",
+        "Play each file backwards, from the new content to the old one",
+    ),
+    (
+        "syntax_highlighting",
+        "Highlight file content; turn off to save CPU on large commits or slow devices",
+    ),
+    (
+        "highlight_cache_size",
+        "Highlighted files kept for reuse when the same content shows again (0 = off)",
+    ),
+    (
+        "static_view",
+        "Show each file's final content at once instead of typing it",
+    ),
+    (
+        "static_dwell_ms",
+        "Milliseconds the static view shows each file (0 = until a key is pressed)",
+    ),
+    (
+        "max_line_length",
+        "Clip editor lines longer than this many characters instead of wrapping them",
+    ),
+    (
+        "dim_typed_lines",
+        "Dim lines above the cursor while a file is being typed",
+    ),
+    (
+        "show_whitespace",
+        "Mark trailing whitespace and show tabs as arrows on added lines",
+    ),
+    ("tab_width", "Columns between tab stops in the editor"),
+    (
+        "show_minimap",
+        "Show an overview of the file with its changes next to the editor",
+    ),
+    (
+        "match_brackets",
+        "Highlight the pair of brackets enclosing the cursor",
+    ),
+    (
+        "show_line_numbers",
+        "Show the line number gutter in the editor (toggle with `l`)",
+    ),
+    (
+        "show_sidebar",
+        "Show the file tree and commit info column (toggle with `t`)",
+    ),
+    (
+        "wrap_file_jumps",
+        "Jumping past the last or first file with `]`/`[` wraps around",
+    ),
+    (
+        "date_display",
+        "How the commit date is shown: \"absolute\", \"relative\" (3 days ago) or \"both\"",
+    ),
+    (
+        "set_terminal_title",
+        "Show the current commit in the terminal window title",
+    ),
+];
+
+// Example values for settings that are unset by default, written commented out
+const UNSET_EXAMPLES: &[(&str, &str)] = &[
+    ("author", "\"alice\""),
+    ("hold", "3000"),
+    ("max_files", "20"),
+    ("max_line_length", "120"),
+    ("root_max_files", "10"),
+    ("speed_cps", "40"),
+    ("syntax_theme", "\"github-dark\""),
+];
+
 fn default_theme() -> String {
     "tokyo-night".to_string()
 }
@@ -41,6 +341,98 @@ fn default_loop() -> bool {
     false
 }
 
+fn default_show_line_numbers() -> bool {
+    true
+}
+
+fn default_color() -> String {
+    "auto".to_string()
+}
+
+fn default_date_display() -> String {
+    "absolute".to_string()
+}
+
+fn default_show_sidebar() -> bool {
+    true
+}
+
+fn default_wrap_file_jumps() -> bool {
+    true
+}
+
+fn default_jitter() -> f64 {
+    DEFAULT_JITTER
+}
+
+fn default_tiny_file_lines() -> usize {
+    DEFAULT_TINY_FILE_LINES
+}
+
+fn default_tab_width() -> usize {
+    DEFAULT_TAB_WIDTH
+}
+
+fn default_normalize_line_endings() -> bool {
+    true
+}
+
+fn default_match_brackets() -> bool {
+    false
+}
+
+fn default_syntax_highlighting() -> bool {
+    true
+}
+
+fn default_highlight_cache_size() -> usize {
+    DEFAULT_HIGHLIGHT_CACHE_SIZE
+}
+
+fn default_static_dwell_ms() -> u64 {
+    DEFAULT_STATIC_DWELL_MS
+}
+
+fn default_stat_preroll_ms() -> u64 {
+    DEFAULT_STAT_PREROLL_MS
+}
+
+fn default_excluded_card_ms() -> u64 {
+    DEFAULT_EXCLUDED_CARD_MS
+}
+
+fn default_rename_threshold() -> u8 {
+    DEFAULT_RENAME_THRESHOLD
+}
+
+fn default_max_blob_size() -> usize {
+    DEFAULT_MAX_BLOB_SIZE
+}
+
+fn default_max_change_lines() -> usize {
+    DEFAULT_MAX_CHANGE_LINES
+}
+
+fn default_random_history() -> usize {
+    DEFAULT_RANDOM_HISTORY
+}
+
+fn default_sort() -> String {
+    "topo".to_string()
+}
+
+fn default_file_order() -> String {
+    "path".to_string()
+}
+
+fn default_file_tree_sort() -> String {
+    "path".to_string()
+}
+
+fn default_on_end() -> String {
+    "exit".to_string()
+}
+
 fn default_ignore_patterns() -> Vec<String> {
     Vec::new()
 }
@@ -49,29 +441,102 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             theme: default_theme(),
+            syntax_theme: None,
             speed: default_speed(),
+            speed_cps: None,
             background: default_background(),
             order: default_order(),
             loop_playback: default_loop(),
             ignore_patterns: default_ignore_patterns(),
             speed_rules: Vec::new(),
+            changes_only: false,
+            reverse_file: false,
+            syntax_highlighting: default_syntax_highlighting(),
+            highlight_cache_size: default_highlight_cache_size(),
+            static_view: false,
+            static_dwell_ms: default_static_dwell_ms(),
+            max_line_length: None,
+            max_files: None,
+            root_max_files: None,
+            hide_excluded: false,
+            max_blob_size: default_max_blob_size(),
+            max_change_lines: default_max_change_lines(),
+            scramble_frames: 0,
+            jitter: default_jitter(),
+            tiny_file_lines: default_tiny_file_lines(),
+            blank_line_pause_ms: 0,
+            stat_preroll_ms: default_stat_preroll_ms(),
+            excluded_card_ms: default_excluded_card_ms(),
+            respect_gitignore: false,
+            normalize_line_endings: default_normalize_line_endings(),
+            include_merges: false,
+            skip_root_commits: false,
+            resume: false,
+            random_history: default_random_history(),
+            rename_threshold: default_rename_threshold(),
+            set_terminal_title: false,
+            dim_typed_lines: false,
+            show_whitespace: false,
+            tab_width: default_tab_width(),
+            show_minimap: false,
+            match_brackets: default_match_brackets(),
+            show_line_numbers: default_show_line_numbers(),
+            show_sidebar: default_show_sidebar(),
+            date_display: default_date_display(),
+            wrap_file_jumps: default_wrap_file_jumps(),
+            color: default_color(),
+            author: None,
+            sort: default_sort(),
+            file_order: default_file_order(),
+            file_tree_sort: default_file_tree_sort(),
+            on_end: default_on_end(),
+            hold: None,
         }
     }
 }
 
 impl Config {
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
+        Self::load_files(&[Self::config_path()?])
+    }
+
+    /// Loads the global config with the repository's `.gitlogue.toml` merged over it,
+    /// so settings in the repository file win over the global ones
+    pub fn load_for_repo(repo_path: &Path) -> Result<Self> {
+        Self::load_files(&[Self::config_path()?, repo_path.join(REPO_CONFIG_FILE)])
+    }
 
-        if !config_path.exists() {
-            return Ok(Self::default());
+    // Merges the settings of the existing files, later files winning
+    fn load_files(paths: &[PathBuf]) -> Result<Self> {
+        let mut values = toml::Table::new();
+        for path in paths {
+            if let Some(file_values) = Self::read_table(path)? {
+                values.extend(file_values);
+            }
         }
+        Self::from_table(values)
+    }
 
-        let contents = fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+    // The settings in a config file, or `None` if there is no such file
+    fn read_table(path: &Path) -> Result<Option<toml::Table>> {
+        if !path.exists() {
+            return Ok(None);
+        }
 
-        toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        let mut table: toml::Table = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        // `loop_playback` is the old name of `loop`; a file setting both means `loop`
+        if let Some(value) = table.remove("loop_playback") {
+            table.entry("loop").or_insert(value);
+        }
+        Ok(Some(table))
+    }
+
+    fn from_table(values: toml::Table) -> Result<Self> {
+        Self::deserialize(values).context("Invalid config file")
     }
 
     pub fn save(&self) -> Result<()> {
@@ -175,6 +640,60 @@ impl Config {
             .with_context(|| format!("Failed to write config file: {}", config_path.display()))
     }
 
+    /// Writes every setting with its default value and a comment to the config
+    /// path, refusing to replace an existing file unless `force` is set
+    pub fn write_default(force: bool) -> Result<PathBuf> {
+        let config_path = Self::config_path()?;
+
+        if config_path.exists() && !force {
+            anyhow::bail!(
+                "Config file already exists: {} (use --force to overwrite)",
+                config_path.display()
+            );
+        }
+
+        fs::write(&config_path, Self::default_file_contents()?)
+            .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
+        Ok(config_path)
+    }
+
+    /// The default config as TOML, each setting preceded by its comment. Settings
+    /// without a default are written commented out with an example value.
+    pub fn default_file_contents() -> Result<String> {
+        let mut values =
+            toml::Table::try_from(Self::default()).context("Failed to serialize default config")?;
+
+        let mut contents = String::from(
+            "# gitlogue configuration file\n\
+             # All settings are optional and will use defaults if not specified\n",
+        );
+
+        for (key, comment) in SETTING_COMMENTS {
+            contents.push('\n');
+            for line in comment.lines() {
+                contents.push_str(&format!("# {}\n", line));
+            }
+
+            match values.remove(*key) {
+                Some(value) => contents.push_str(&format!("{} = {}\n", key, value)),
+                None => {
+                    let example = UNSET_EXAMPLES
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .map_or("", |(_, example)| example);
+                    contents.push_str(&format!("# {} = {}\n", key, example));
+                }
+            }
+        }
+
+        // Settings added without a comment still belong in the file
+        for (key, value) in values {
+            contents.push_str(&format!("\n{} = {}\n", key, value));
+        }
+
+        Ok(contents)
+    }
+
     pub fn config_path() -> Result<PathBuf> {
         let config_dir = dirs::home_dir()
             .context("Failed to determine home directory")?
@@ -191,13 +710,8 @@ impl Config {
         Ok(config_dir.join("config.toml"))
     }
 
-    #[allow(dead_code)]
     pub fn themes_dir() -> Result<PathBuf> {
-        let config_dir = dirs::home_dir()
-            .context("Failed to determine home directory")?
-            .join(".config")
-            .join("gitlogue")
-            .join("themes");
+        let config_dir = Theme::user_themes_dir().context("Failed to determine home directory")?;
 
         fs::create_dir_all(&config_dir).with_context(|| {
             format!(
@@ -209,3 +723,66 @@ impl Config {
         Ok(config_dir)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_file_contents_documents_every_setting() {
+        let contents = Config::default_file_contents().unwrap();
+
+        let values = toml::Table::try_from(Config::default()).unwrap();
+        for key in values.keys() {
+            assert!(
+                SETTING_COMMENTS.iter().any(|(name, _)| name == key),
+                "no comment for {}",
+                key
+            );
+        }
+
+        assert!(contents.contains("\n# Loop the animation continuously\nloop = false\n"));
+        assert!(contents.contains("\n# max_files = 20\n"));
+
+        let parsed: Config = toml::from_str(&contents).unwrap();
+        let default = Config::default();
+        assert_eq!(parsed.theme, default.theme);
+        assert_eq!(parsed.speed, default.speed);
+        assert_eq!(parsed.ignore_patterns, default.ignore_patterns);
+        assert_eq!(parsed.max_files, None);
+
+        let looped: Config = toml::from_str("loop = true").unwrap();
+        assert!(looped.loop_playback);
+    }
+
+    #[test]
+    fn test_repo_config_overrides_global_config() {
+        let dir = std::env::temp_dir().join(format!("gitlogue_config_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let global = dir.join("config.toml");
+        let repo = dir.join(REPO_CONFIG_FILE);
+        fs::write(
+            &global,
+            "theme = \"nord\"\nspeed = 50\nloop = true\nloop_playback = false\n",
+        )
+        .unwrap();
+        fs::write(
+            &repo,
+            "speed = 10\nignore_patterns = [\"*.lock\"]\nloop_playback = false\n",
+        )
+        .unwrap();
+
+        let config = Config::load_files(&[global.clone(), repo]).unwrap();
+        let missing = Config::load_files(&[global, dir.join("missing.toml")]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.theme, "nord");
+        assert_eq!(config.speed, 10);
+        assert_eq!(config.ignore_patterns, vec!["*.lock"]);
+        assert_eq!(config.order, default_order());
+        // Both spellings of `loop` can be mixed, with `loop` winning within a file
+        assert!(!config.loop_playback);
+        assert_eq!(missing.speed, 50);
+        assert!(missing.loop_playback);
+    }
+}
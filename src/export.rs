@@ -0,0 +1,459 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_8X13, MonoTextStyle},
+    pixelcolor::{Rgb888, RgbColor},
+    prelude::{DrawTarget, OriginDimensions, Point, Size},
+    text::{Baseline, Text},
+    Drawable, Pixel,
+};
+use gif::{Encoder, Frame, Repeat};
+use ratatui::{
+    backend::{CrosstermBackend, TestBackend},
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+use crate::ui::UI;
+
+const CELL_WIDTH: u32 = 8;
+const CELL_HEIGHT: u32 = 13;
+
+// GIF delays are in centiseconds and most viewers clamp anything faster than 20ms,
+// so frames closer together than this are merged into one
+const MIN_FRAME_INTERVAL: Duration = Duration::from_millis(40);
+
+// How long the last frame stays up before the GIF loops
+const FINAL_FRAME_HOLD: Duration = Duration::from_secs(2);
+
+// Colors used for cells that keep the terminal's default foreground/background
+const DEFAULT_FG: [u8; 3] = [204, 204, 204];
+const DEFAULT_BG: [u8; 3] = [0, 0, 0];
+
+/// Plays the commit loaded into `ui` off-screen and writes it to `path` as an animated GIF.
+pub fn export_gif(ui: &mut UI, path: &Path, cols: u16, rows: u16) -> Result<()> {
+    let width = cols as u32 * CELL_WIDTH;
+    let height = rows as u32 * CELL_HEIGHT;
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = Encoder::new(BufWriter::new(file), width as u16, height as u16, &[])
+        .context("Failed to start GIF encoder")?;
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    let mut canvas = Canvas::new(width, height);
+    // The latest frame is held back until the next one arrives, since its delay is only
+    // known once we see when the screen changes again
+    let mut pending: Option<(Buffer, Duration)> = None;
+
+    ui.run_headless(
+        TestBackend::new(cols, rows),
+        cols,
+        rows,
+        |backend, elapsed| {
+            let buffer = backend.buffer();
+            match pending.take() {
+                Some((previous, shown_at)) if &previous == buffer => {
+                    pending = Some((previous, shown_at));
+                }
+                Some((_, shown_at)) if elapsed.saturating_sub(shown_at) < MIN_FRAME_INTERVAL => {
+                    pending = Some((buffer.clone(), shown_at));
+                }
+                Some((previous, shown_at)) => {
+                    write_frame(&mut encoder, &mut canvas, &previous, elapsed - shown_at)?;
+                    pending = Some((buffer.clone(), elapsed));
+                }
+                None => pending = Some((buffer.clone(), elapsed)),
+            }
+            Ok(())
+        },
+    )?;
+
+    if let Some((buffer, _)) = pending {
+        write_frame(&mut encoder, &mut canvas, &buffer, FINAL_FRAME_HOLD)?;
+    }
+
+    Ok(())
+}
+
+/// Plays the commit loaded into `ui` off-screen and records the terminal output to `path`
+/// as an asciinema v2 cast.
+pub fn export_asciicast(ui: &mut UI, path: &Path, cols: u16, rows: u16) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let header = serde_json::json!({
+        "version": 2,
+        "width": cols,
+        "height": rows,
+        "timestamp": timestamp,
+        "title": "gitlogue",
+        "env": { "TERM": "xterm-256color" },
+    });
+    writeln!(out, "{}", header)?;
+    // Start from a blank screen, since ratatui only writes the cells that change
+    write_cast_event(&mut out, Duration::ZERO, "\x1b[2J\x1b[H\x1b[?25l")?;
+
+    let output = SharedOutput::default();
+    let mut last = Duration::ZERO;
+    ui.run_headless(
+        CrosstermBackend::new(output.clone()),
+        cols,
+        rows,
+        |_, elapsed| {
+            let output = output.0.take();
+            if !output.is_empty() {
+                write_cast_event(&mut out, elapsed, &String::from_utf8_lossy(&output))?;
+                last = elapsed;
+            }
+            Ok(())
+        },
+    )?;
+
+    // Hold the last frame before the player stops, then restore the cursor
+    write_cast_event(&mut out, last + FINAL_FRAME_HOLD, "\x1b[0m\x1b[?25h")?;
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Collects what the crossterm backend writes so it can be drained after each frame.
+#[derive(Clone, Default)]
+struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn write_cast_event(out: &mut impl Write, at: Duration, data: &str) -> Result<()> {
+    let event = serde_json::json!([at.as_secs_f64(), "o", data]);
+    writeln!(out, "{}", event)?;
+    Ok(())
+}
+
+fn write_frame<W: Write>(
+    encoder: &mut Encoder<W>,
+    canvas: &mut Canvas,
+    buffer: &Buffer,
+    delay: Duration,
+) -> Result<()> {
+    canvas.draw_buffer(buffer);
+
+    let mut frame = canvas.to_frame();
+    frame.delay = (delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+    encoder
+        .write_frame(&frame)
+        .context("Failed to write GIF frame")
+}
+
+/// RGB pixel grid the terminal cells are rasterized into.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![DEFAULT_BG; (width * height) as usize],
+        }
+    }
+
+    fn draw_buffer(&mut self, buffer: &Buffer) {
+        let area = buffer.area;
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let cell = &buffer[(area.x + x, area.y + y)];
+                let mut fg = to_rgb(cell.fg, DEFAULT_FG);
+                let mut bg = to_rgb(cell.bg, DEFAULT_BG);
+                if cell.modifier.contains(Modifier::REVERSED) {
+                    std::mem::swap(&mut fg, &mut bg);
+                }
+                if cell.modifier.contains(Modifier::DIM) {
+                    fg = blend(fg, bg);
+                }
+
+                let origin = Point::new(
+                    (x as u32 * CELL_WIDTH) as i32,
+                    (y as u32 * CELL_HEIGHT) as i32,
+                );
+                self.fill_cell(origin, bg);
+                self.draw_symbol(cell.symbol(), origin, fg);
+            }
+        }
+    }
+
+    fn fill_cell(&mut self, origin: Point, color: [u8; 3]) {
+        for dy in 0..CELL_HEIGHT as i32 {
+            for dx in 0..CELL_WIDTH as i32 {
+                self.set(origin.x + dx, origin.y + dy, color);
+            }
+        }
+    }
+
+    fn draw_symbol(&mut self, symbol: &str, origin: Point, color: [u8; 3]) {
+        let Some(ch) = symbol.chars().next() else {
+            return;
+        };
+        if ch == ' ' {
+            return;
+        }
+        if self.draw_box_char(ch, origin, color) {
+            return;
+        }
+        // The bitmap font only covers Latin-1; wider glyphs such as emoji are left blank
+        if (ch as u32) > 0xff {
+            return;
+        }
+
+        let style = MonoTextStyle::new(&FONT_8X13, Rgb888::new(color[0], color[1], color[2]));
+        let mut utf8 = [0; 4];
+        let _ =
+            Text::with_baseline(ch.encode_utf8(&mut utf8), origin, style, Baseline::Top).draw(self);
+    }
+
+    /// Draws the box-drawing characters ratatui uses for borders, which the font lacks.
+    fn draw_box_char(&mut self, ch: char, origin: Point, color: [u8; 3]) -> bool {
+        // Which arms extend from the cell center: (left, right, up, down)
+        let (left, right, up, down) = match ch {
+            '─' => (true, true, false, false),
+            '│' => (false, false, true, true),
+            '┌' | '╭' => (false, true, false, true),
+            '┐' | '╮' => (true, false, false, true),
+            '└' | '╰' => (false, true, true, false),
+            '┘' | '╯' => (true, false, true, false),
+            '├' => (false, true, true, true),
+            '┤' => (true, false, true, true),
+            '┬' => (true, true, false, true),
+            '┴' => (true, true, true, false),
+            '┼' => (true, true, true, true),
+            '█' => {
+                self.fill_cell(origin, color);
+                return true;
+            }
+            _ => return false,
+        };
+
+        let cx = origin.x + CELL_WIDTH as i32 / 2;
+        let cy = origin.y + CELL_HEIGHT as i32 / 2;
+        let left_x = if left { origin.x } else { cx };
+        let right_x = if right {
+            origin.x + CELL_WIDTH as i32 - 1
+        } else {
+            cx
+        };
+        let top_y = if up { origin.y } else { cy };
+        let bottom_y = if down {
+            origin.y + CELL_HEIGHT as i32 - 1
+        } else {
+            cy
+        };
+        for x in left_x..=right_x {
+            self.set(x, cy, color);
+        }
+        for y in top_y..=bottom_y {
+            self.set(cx, y, color);
+        }
+        true
+    }
+
+    fn set(&mut self, x: i32, y: i32, color: [u8; 3]) {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return;
+        }
+        self.pixels[(y as u32 * self.width + x as u32) as usize] = color;
+    }
+
+    /// Builds a GIF frame, using an exact palette when the frame has few enough colors.
+    fn to_frame(&self) -> Frame<'static> {
+        let mut palette: Vec<[u8; 3]> = Vec::new();
+        let mut lookup: HashMap<[u8; 3], u8> = HashMap::new();
+        let mut indices = Vec::with_capacity(self.pixels.len());
+        for pixel in &self.pixels {
+            let index = match lookup.get(pixel) {
+                Some(&index) => index,
+                None if palette.len() < 256 => {
+                    let index = palette.len() as u8;
+                    palette.push(*pixel);
+                    lookup.insert(*pixel, index);
+                    index
+                }
+                None => {
+                    let rgb: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+                    return Frame::from_rgb_speed(self.width as u16, self.height as u16, &rgb, 10);
+                }
+            };
+            indices.push(index);
+        }
+
+        let palette: Vec<u8> = palette.into_iter().flatten().collect();
+        Frame::from_palette_pixels(
+            self.width as u16,
+            self.height as u16,
+            indices,
+            palette,
+            None,
+        )
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl DrawTarget for Canvas {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set(point.x, point.y, [color.r(), color.g(), color.b()]);
+        }
+        Ok(())
+    }
+}
+
+fn blend(fg: [u8; 3], bg: [u8; 3]) -> [u8; 3] {
+    [0, 1, 2].map(|i| ((fg[i] as u16 + bg[i] as u16) / 2) as u8)
+}
+
+/// Resolves a ratatui color to RGB, using the xterm palette for named and indexed colors.
+fn to_rgb(color: Color, default: [u8; 3]) -> [u8; 3] {
+    match color {
+        Color::Reset => default,
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Black => ANSI_COLORS[0],
+        Color::Red => ANSI_COLORS[1],
+        Color::Green => ANSI_COLORS[2],
+        Color::Yellow => ANSI_COLORS[3],
+        Color::Blue => ANSI_COLORS[4],
+        Color::Magenta => ANSI_COLORS[5],
+        Color::Cyan => ANSI_COLORS[6],
+        Color::Gray => ANSI_COLORS[7],
+        Color::DarkGray => ANSI_COLORS[8],
+        Color::LightRed => ANSI_COLORS[9],
+        Color::LightGreen => ANSI_COLORS[10],
+        Color::LightYellow => ANSI_COLORS[11],
+        Color::LightBlue => ANSI_COLORS[12],
+        Color::LightMagenta => ANSI_COLORS[13],
+        Color::LightCyan => ANSI_COLORS[14],
+        Color::White => ANSI_COLORS[15],
+        Color::Indexed(index) => indexed_to_rgb(index),
+    }
+}
+
+const ANSI_COLORS: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+fn indexed_to_rgb(index: u8) -> [u8; 3] {
+    match index {
+        0..=15 => ANSI_COLORS[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            [level(i / 36), level((i / 6) % 6), level(i % 6)]
+        }
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            [v, v, v]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_indexed_colors_follow_xterm_palette() {
+        assert_eq!(indexed_to_rgb(1), ANSI_COLORS[1]);
+        assert_eq!(indexed_to_rgb(16), [0, 0, 0]);
+        assert_eq!(indexed_to_rgb(231), [255, 255, 255]);
+        assert_eq!(indexed_to_rgb(232), [8, 8, 8]);
+        assert_eq!(indexed_to_rgb(255), [238, 238, 238]);
+    }
+
+    #[test]
+    fn test_cast_events_escape_terminal_output() {
+        let mut out = Vec::new();
+        write_cast_event(
+            &mut out,
+            Duration::from_millis(1500),
+            "\x1b[1;1Hhi \"there\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[1.5,\"o\",\"\\u001b[1;1Hhi \\\"there\\\"\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_draw_buffer_paints_cell_colors() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(
+            0,
+            0,
+            "─",
+            Style::default()
+                .fg(Color::Rgb(255, 0, 0))
+                .bg(Color::Rgb(0, 0, 255)),
+        );
+
+        let mut canvas = Canvas::new(2 * CELL_WIDTH, CELL_HEIGHT);
+        canvas.draw_buffer(&buffer);
+
+        let pixel = |x: u32, y: u32| canvas.pixels[(y * canvas.width + x) as usize];
+        // Corner of the first cell is background, its center row is the line
+        assert_eq!(pixel(0, 0), [0, 0, 255]);
+        assert_eq!(pixel(0, CELL_HEIGHT / 2), [255, 0, 0]);
+        // The untouched second cell keeps the default background
+        assert_eq!(pixel(CELL_WIDTH, 0), DEFAULT_BG);
+    }
+}
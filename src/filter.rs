@@ -0,0 +1,407 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use git2::Commit as Git2Commit;
+
+use crate::git::parse_date;
+
+/// A boolean expression over commit fields, given with `--filter`.
+///
+/// Comparisons have the form `field op value`, combined with `and`/`&&`, `or`/`||`,
+/// `not`/`!` and parentheses. Values are quoted strings or bare words.
+///
+/// | field     | operators                  | compares                              |
+/// |-----------|----------------------------|---------------------------------------|
+/// | `author`  | `~` `=` `!=`               | author name or email                  |
+/// | `message` | `~` `=` `!=`               | full commit message                   |
+/// | `hash`    | `~` `=` `!=`               | commit hash (`=` accepts a prefix)    |
+/// | `files`   | `=` `!=` `<` `<=` `>` `>=` | number of changed files               |
+/// | `date`    | `<` `<=` `>` `>=`          | author date, in any `--after` format  |
+/// | `merge`   | used on its own            | whether the commit has several parents |
+///
+/// `~` is a case-insensitive substring match and `=` an exact, case-insensitive match.
+#[derive(Debug, Clone)]
+pub struct CommitFilter {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Merge,
+    Text(TextField, TextOp, String),
+    Files(NumberOp, usize),
+    Date(NumberOp, DateTime<Utc>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TextField {
+    Author,
+    Message,
+    Hash,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TextOp {
+    Contains,
+    Equals,
+    NotEquals,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NumberOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl NumberOp {
+    fn compare<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            NumberOp::Eq => left == right,
+            NumberOp::Ne => left != right,
+            NumberOp::Lt => left < right,
+            NumberOp::Le => left <= right,
+            NumberOp::Gt => left > right,
+            NumberOp::Ge => left >= right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+}
+
+impl CommitFilter {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            anyhow::bail!(
+                "unexpected {} after the end of the expression",
+                describe(token)
+            );
+        }
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    /// The expression as the user wrote it
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the expression refers to `merge`, which needs merge commits to be played
+    pub fn uses_merge(&self) -> bool {
+        uses_merge(&self.expr)
+    }
+
+    /// Evaluates the filter against a commit. `file_count` is only called when the
+    /// expression refers to `files`, since counting needs a diff.
+    pub fn matches(&self, commit: &Git2Commit, file_count: &mut dyn FnMut() -> usize) -> bool {
+        let mut cached_count = None;
+        let mut count = || *cached_count.get_or_insert_with(&mut *file_count);
+        eval(&self.expr, commit, &mut count)
+    }
+}
+
+fn uses_merge(expr: &Expr) -> bool {
+    match expr {
+        Expr::And(left, right) | Expr::Or(left, right) => uses_merge(left) || uses_merge(right),
+        Expr::Not(inner) => uses_merge(inner),
+        Expr::Merge => true,
+        Expr::Text(..) | Expr::Files(..) | Expr::Date(..) => false,
+    }
+}
+
+fn eval(expr: &Expr, commit: &Git2Commit, file_count: &mut dyn FnMut() -> usize) -> bool {
+    match expr {
+        Expr::And(left, right) => eval(left, commit, file_count) && eval(right, commit, file_count),
+        Expr::Or(left, right) => eval(left, commit, file_count) || eval(right, commit, file_count),
+        Expr::Not(inner) => !eval(inner, commit, file_count),
+        Expr::Merge => commit.parent_count() > 1,
+        Expr::Text(field, op, value) => {
+            let candidates = match field {
+                TextField::Author => {
+                    let author = commit.author();
+                    vec![
+                        author.name().unwrap_or("").to_lowercase(),
+                        author.email().unwrap_or("").to_lowercase(),
+                    ]
+                }
+                TextField::Message => {
+                    vec![String::from_utf8_lossy(commit.message_bytes())
+                        .trim()
+                        .to_lowercase()]
+                }
+                TextField::Hash => vec![commit.id().to_string()],
+            };
+            let matched = candidates.iter().any(|candidate| match (field, op) {
+                (_, TextOp::Contains) => candidate.contains(value.as_str()),
+                (TextField::Hash, _) => candidate.starts_with(value.as_str()),
+                _ => candidate == value,
+            });
+            match op {
+                TextOp::NotEquals => !matched,
+                _ => matched,
+            }
+        }
+        Expr::Files(op, value) => op.compare(file_count(), *value),
+        Expr::Date(op, value) => op.compare(commit.author().when().seconds(), value.timestamp()),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(c) if c == ch => break,
+                        Some('\\') => value.extend(chars.next()),
+                        Some(c) => value.push(c),
+                        None => anyhow::bail!("unterminated string starting with {}{}", ch, value),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(ch) {
+                    anyhow::bail!("expected '{0}{0}'", ch);
+                }
+                tokens.push(if ch == '&' { Token::And } else { Token::Or });
+            }
+            '!' | '=' | '<' | '>' | '~' => {
+                chars.next();
+                let followed_by_eq = chars.peek() == Some(&'=');
+                if followed_by_eq {
+                    chars.next();
+                }
+                tokens.push(match (ch, followed_by_eq) {
+                    ('!', false) => Token::Not,
+                    ('!', true) => Token::Op("!="),
+                    ('=', _) => Token::Op("="),
+                    ('<', false) => Token::Op("<"),
+                    ('<', true) => Token::Op("<="),
+                    ('>', false) => Token::Op(">"),
+                    ('>', true) => Token::Op(">="),
+                    _ => Token::Op("~"),
+                });
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "()\"'&|!=<>~".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("'{}'", word),
+        Token::Str(value) => format!("\"{}\"", value),
+        Token::Op(op) => format!("'{}'", op),
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::Not => "'not'".to_string(),
+        Token::And => "'and'".to_string(),
+        Token::Or => "'or'".to_string(),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    Some(token) => anyhow::bail!("expected ')' but found {}", describe(&token)),
+                    None => anyhow::bail!("missing ')' at the end of the expression"),
+                }
+            }
+            Some(Token::Word(field)) => self.parse_comparison(&field),
+            Some(token) => anyhow::bail!("expected a field name but found {}", describe(&token)),
+            None => anyhow::bail!("expected a field name at the end of the expression"),
+        }
+    }
+
+    fn parse_comparison(&mut self, field: &str) -> Result<Expr> {
+        let field_name = field.to_lowercase();
+        if field_name == "merge" {
+            return Ok(Expr::Merge);
+        }
+
+        let text_field = match field_name.as_str() {
+            "author" => Some(TextField::Author),
+            "message" => Some(TextField::Message),
+            "hash" => Some(TextField::Hash),
+            "files" | "date" => None,
+            _ => anyhow::bail!(
+                "unknown field '{}' (expected author, message, hash, files, date or merge)",
+                field
+            ),
+        };
+
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            Some(token) => anyhow::bail!(
+                "expected an operator after '{}' but found {}",
+                field,
+                describe(&token)
+            ),
+            None => anyhow::bail!("expected an operator after '{}'", field),
+        };
+        let value = match self.next() {
+            Some(Token::Word(value)) | Some(Token::Str(value)) => value,
+            Some(token) => anyhow::bail!(
+                "expected a value after '{} {}' but found {}",
+                field,
+                op,
+                describe(&token)
+            ),
+            None => anyhow::bail!("expected a value after '{} {}'", field, op),
+        };
+
+        if let Some(text_field) = text_field {
+            let text_op = match op {
+                "~" => TextOp::Contains,
+                "=" => TextOp::Equals,
+                "!=" => TextOp::NotEquals,
+                _ => anyhow::bail!("'{}' can't be used with '{}' (use ~, = or !=)", op, field),
+            };
+            return Ok(Expr::Text(text_field, text_op, value.to_lowercase()));
+        }
+
+        let number_op = match op {
+            "=" => NumberOp::Eq,
+            "!=" => NumberOp::Ne,
+            "<" => NumberOp::Lt,
+            "<=" => NumberOp::Le,
+            ">" => NumberOp::Gt,
+            ">=" => NumberOp::Ge,
+            _ => anyhow::bail!("'{}' can't be used with '{}'", op, field),
+        };
+
+        if field_name == "files" {
+            let count = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("'files' needs a number, got '{}'", value))?;
+            return Ok(Expr::Files(number_op, count));
+        }
+
+        if matches!(number_op, NumberOp::Eq | NumberOp::Ne) {
+            anyhow::bail!("'date' only supports <, <=, > and >=");
+        }
+        let date = parse_date(&value)?;
+        Ok(Expr::Date(number_op, date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_precedence_and_errors() {
+        let filter = CommitFilter::parse(r#"author~"alice" or files>3 and !merge"#).unwrap();
+        // `and` binds tighter than `or`
+        assert!(matches!(
+            filter.expr,
+            Expr::Or(_, ref right) if matches!(**right, Expr::And(_, _))
+        ));
+        assert!(filter.uses_merge());
+        assert!(!CommitFilter::parse("files > 3").unwrap().uses_merge());
+
+        assert!(CommitFilter::parse("(date >= 2024-01-01 && message ~ fix)").is_ok());
+
+        let error = |source: &str| CommitFilter::parse(source).unwrap_err().to_string();
+        assert!(error("colour = red").contains("unknown field 'colour'"));
+        assert!(error("files > many").contains("needs a number"));
+        assert!(error("author > bob").contains("can't be used with 'author'"));
+        assert!(error("(merge").contains("missing ')'"));
+        assert!(error("author ~ \"bob").contains("unterminated string"));
+        assert!(error("merge merge").contains("unexpected 'merge'"));
+    }
+}
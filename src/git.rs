@@ -1,29 +1,176 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local, Utc};
 use chrono_english::{parse_date_string, Dialect};
-use git2::{Commit as Git2Commit, Delta, DiffOptions, Oid, Repository};
+use git2::{
+    AttrCheckFlags, AttrValue, Commit as Git2Commit, Delta, DiffOptions, ObjectType, Oid,
+    Repository, Sort, Tree, TreeWalkMode, TreeWalkResult,
+};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use rand::Rng;
-use std::cell::RefCell;
+use regex::Regex;
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::Range;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
 use std::sync::OnceLock;
 
+use crate::filter::CommitFilter;
+use crate::resume::{ResumeFile, SavedPosition};
+
 // Thread-safe global pattern matcher for user-defined ignore patterns
 static USER_PATTERNS: OnceLock<GlobSet> = OnceLock::new();
 
-// Maximum blob size to read (500KB)
-const MAX_BLOB_SIZE: usize = 500 * 1024;
+// Whether files matching the repository's .gitignore rules are excluded
+static RESPECT_GITIGNORE: AtomicBool = AtomicBool::new(false);
+
+/// Default maximum blob size to read (500KB)
+pub const DEFAULT_MAX_BLOB_SIZE: usize = 500 * 1024;
+
+/// Default maximum number of changed lines per file to animate
+pub const DEFAULT_MAX_CHANGE_LINES: usize = 2000;
+
+// Larger blobs aren't loaded, so their content isn't animated
+static MAX_BLOB_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_BLOB_SIZE);
+
+// Files with more changed lines are skipped to prevent performance issues
+static MAX_CHANGE_LINES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_CHANGE_LINES);
+
+// Whether CRLF line endings are turned into LF when file content is loaded
+static NORMALIZE_LINE_ENDINGS: AtomicBool = AtomicBool::new(true);
+
+// How files are sorted within the file tree, as a FileTreeSort discriminant
+static FILE_TREE_SORT: AtomicU8 = AtomicU8::new(FileTreeSort::Path as u8);
+
+// Changed character ranges of the old and the new side of a line pair
+type IntralineRanges = (Vec<Range<usize>>, Vec<Range<usize>>);
 
-// Maximum number of changed lines per file to animate
-// Files with more changes will be skipped to prevent performance issues
-const MAX_CHANGE_LINES: usize = 2000;
+// Largest token grid (old tokens × new tokens) diffed word by word; longer line pairs
+// are left without intra-line ranges
+const MAX_INTRALINE_CELLS: usize = 40_000;
 
 /// Specifies which working tree changes to show in diff mode
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum DiffMode {
     #[default]
     Staged, // Only staged changes (index vs HEAD)
-    Unstaged, // Only unstaged changes (workdir vs index)
+    Unstaged,    // Only unstaged changes (workdir vs index)
+    WorkingTree, // All uncommitted changes, including untracked files (workdir vs HEAD)
+}
+
+/// Similarity (in percent) above which a removed and an added file count as a rename.
+/// Detection is off by default; git itself uses 50.
+pub const DEFAULT_RENAME_THRESHOLD: u8 = 0;
+
+/// Default number of recently played commits random playback avoids repeating
+pub const DEFAULT_RANDOM_HISTORY: usize = 20;
+
+// Maximum number of ancestor commits searched when ordering files by recency
+const RECENCY_HISTORY_LIMIT: usize = 1000;
+
+/// Order in which a commit's files are animated
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FileOrder {
+    #[default]
+    Path, // Directory, then filename (matches the file tree)
+    Recency, // Most recently modified before the commit first
+}
+
+/// How the file tree sorts a commit's files. Directories stay grouped, so ranks only order
+/// files within a directory: directories are ordered by their first file, and `path`
+/// playback order follows the tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FileTreeSort {
+    #[default]
+    Path, // Directory, then filename
+    Magnitude, // Most changed lines first
+    Status,    // Added, then modified, renamed and deleted files
+}
+
+impl FileTreeSort {
+    /// Where a changed file ranks under this sort, lower first; ties are sorted by name
+    pub fn rank(self, change: &FileChange) -> usize {
+        match self {
+            FileTreeSort::Path => 0,
+            FileTreeSort::Magnitude => {
+                let (additions, deletions) = change.line_counts();
+                usize::MAX - (additions + deletions)
+            }
+            FileTreeSort::Status => match change.status {
+                FileStatus::Added => 0,
+                FileStatus::Modified | FileStatus::Submodule => 1,
+                FileStatus::Renamed | FileStatus::Copied => 2,
+                FileStatus::Deleted => 3,
+                FileStatus::Unmodified => 4,
+            },
+        }
+    }
+
+    /// Rank of a growth-mode file the commit didn't touch: among the changed files when
+    /// sorting by path, after them otherwise
+    pub fn unchanged_rank(self) -> usize {
+        match self {
+            FileTreeSort::Path => 0,
+            _ => usize::MAX,
+        }
+    }
+}
+
+/// Splits a path into its directory ("" at the root) and file name, as the file tree groups it
+pub fn split_tree_path(path: &str) -> (&str, &str) {
+    path.rsplit_once('/').unwrap_or(("", path))
+}
+
+/// Orders `(path, rank)` entries the way the file tree lists them: grouped by directory,
+/// directories by their best rank and then path, files by rank and then name. Returns the
+/// entry indices in that order. The file tree and path-order playback both go through this,
+/// so the highlighted file always matches the one being animated.
+pub fn tree_order(entries: &[(&str, usize)]) -> Vec<usize> {
+    // A directory ranks as its best file, keeping its files together
+    let mut dir_ranks: HashMap<&str, usize> = HashMap::new();
+    for &(path, rank) in entries {
+        let dir_rank = dir_ranks
+            .entry(split_tree_path(path).0)
+            .or_insert(usize::MAX);
+        *dir_rank = (*dir_rank).min(rank);
+    }
+
+    let mut indices: Vec<usize> = (0..entries.len()).collect();
+    indices.sort_by_key(|&index| {
+        let (path, rank) = entries[index];
+        let (dir, filename) = split_tree_path(path);
+        (dir_ranks[dir], dir, rank, filename)
+    });
+    indices
+}
+
+/// Whether `path` contains the file filter typed with `/`, ignoring case. An empty filter
+/// matches every path.
+pub fn path_matches_filter(path: &str, filter: &str) -> bool {
+    path.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// Set how the file tree, and playback in path order, sort a commit's files (call once at startup)
+pub fn set_file_tree_sort(sort: FileTreeSort) {
+    FILE_TREE_SORT.store(sort as u8, Ordering::Relaxed);
+}
+
+/// How the file tree sorts a commit's files, see [`set_file_tree_sort`]
+pub fn file_tree_sort() -> FileTreeSort {
+    match FILE_TREE_SORT.load(Ordering::Relaxed) {
+        1 => FileTreeSort::Magnitude,
+        2 => FileTreeSort::Status,
+        _ => FileTreeSort::Path,
+    }
+}
+
+/// Order of the commit candidate list that asc/desc playback walks through
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CommitSort {
+    #[default]
+    Topo, // Revision walk order, parents always after their children
+    Date, // Strictly by author date
 }
 
 // Files to exclude from diff animation (lock files and generated files)
@@ -130,6 +277,313 @@ pub fn should_exclude_file(path: &str) -> bool {
     false
 }
 
+/// Exclude tracked files that match the repository's ignore rules (call once at startup)
+pub fn set_respect_gitignore(enabled: bool) {
+    RESPECT_GITIGNORE.store(enabled, Ordering::Relaxed);
+}
+
+/// Set the largest file, in bytes, whose content is loaded and animated (call once at startup)
+pub fn set_max_blob_size(bytes: usize) {
+    MAX_BLOB_SIZE.store(bytes, Ordering::Relaxed);
+}
+
+/// Set the most changed lines a file may have before it is skipped (call once at startup)
+pub fn set_max_change_lines(lines: usize) {
+    MAX_CHANGE_LINES.store(lines, Ordering::Relaxed);
+}
+
+/// Turn CRLF line endings into LF in loaded content, or keep them as stored (call once at startup)
+pub fn set_normalize_line_endings(enabled: bool) {
+    NORMALIZE_LINE_ENDINGS.store(enabled, Ordering::Relaxed);
+}
+
+fn max_blob_size() -> usize {
+    MAX_BLOB_SIZE.load(Ordering::Relaxed)
+}
+
+// Decodes file content or a diff line as UTF-8. CRLF becomes LF unless normalization is
+// off, so blobs, diff lines and working tree files agree whatever core.autocrlf did.
+fn decode_text(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if NORMALIZE_LINE_ENDINGS.load(Ordering::Relaxed) && text.contains('\r') {
+        text.replace("\r\n", "\n")
+    } else {
+        text.into_owned()
+    }
+}
+
+// Why a changed file is skipped during animation, None if it should be animated
+fn exclusion_reason(repo: &Repository, path: &str, total_changed_lines: usize) -> Option<String> {
+    if should_exclude_file(path) {
+        Some("lock/generated file".to_string())
+    } else if let Some(reason) = gitattributes_reason(repo, path) {
+        Some(reason.to_string())
+    } else if RESPECT_GITIGNORE.load(Ordering::Relaxed)
+        && repo.is_path_ignored(path).unwrap_or(false)
+    {
+        Some("matches .gitignore".to_string())
+    } else if total_changed_lines > MAX_CHANGE_LINES.load(Ordering::Relaxed) {
+        Some(format!("too many changes ({} lines)", total_changed_lines))
+    } else {
+        None
+    }
+}
+
+// Why the repository's .gitattributes rules a file out: `binary` and `-diff` mark it as
+// binary, `linguist-generated` as generated. Attributes are read from the working tree
+// (falling back to the index), so the repository's current conventions apply to all commits.
+fn gitattributes_reason(repo: &Repository, path: &str) -> Option<&'static str> {
+    let attr = |name: &str| {
+        repo.get_attr(Path::new(path), name, AttrCheckFlags::default())
+            .map(AttrValue::from_string)
+            .unwrap_or(AttrValue::Unspecified)
+    };
+
+    if matches!(attr("diff"), AttrValue::False) {
+        return Some("binary in .gitattributes");
+    }
+    match attr("linguist-generated") {
+        AttrValue::True => Some("generated in .gitattributes"),
+        AttrValue::String(value) if value.eq_ignore_ascii_case("true") => {
+            Some("generated in .gitattributes")
+        }
+        _ => None,
+    }
+}
+
+// Byte size of one side of a diff, None if the file doesn't exist on that side
+fn diff_file_size(repo: &Repository, file: &git2::DiffFile) -> Option<usize> {
+    if file.size() > 0 {
+        return Some(file.size() as usize);
+    }
+    if file.id().is_zero() {
+        return None;
+    }
+    repo.find_blob(file.id()).ok().map(|blob| blob.size())
+}
+
+// Gitlink entries (mode 160000) record a submodule commit rather than a blob
+fn submodule_change(delta: &git2::DiffDelta) -> Option<SubmoduleChange> {
+    let commit_of = |file: git2::DiffFile| {
+        (file.mode() == git2::FileMode::Commit && !file.id().is_zero())
+            .then(|| file.id().to_string())
+    };
+    if delta.old_file().mode() != git2::FileMode::Commit
+        && delta.new_file().mode() != git2::FileMode::Commit
+    {
+        return None;
+    }
+    Some(SubmoduleChange {
+        old_commit: commit_of(delta.old_file()),
+        new_commit: commit_of(delta.new_file()),
+    })
+}
+
+// Pairs up removed and added (or copied) files that are at least `threshold` percent
+// similar into renames and copies; a threshold of 0 leaves them apart
+fn detect_renames(diff: &mut git2::Diff, threshold: u8) -> Result<()> {
+    if threshold == 0 {
+        return Ok(());
+    }
+    let threshold = u16::from(threshold.min(100));
+    let mut find_opts = git2::DiffFindOptions::new();
+    find_opts
+        .renames(true)
+        .copies(true)
+        .for_untracked(true)
+        .rename_threshold(threshold)
+        .copy_threshold(threshold);
+    diff.find_similar(Some(&mut find_opts))
+        .context("Failed to detect renames")
+}
+
+// A submodule has no blob to read, so the change is a one-line pseudo-diff that adds
+// `Submodule <path>: <old sha> → <new sha>`
+fn submodule_file_change(
+    path: String,
+    old_path: Option<String>,
+    submodule: SubmoduleChange,
+) -> FileChange {
+    let short = |commit: &Option<String>| {
+        commit
+            .as_deref()
+            .map(|hash| hash[..7.min(hash.len())].to_string())
+            .unwrap_or_else(|| "(none)".to_string())
+    };
+    let summary = format!(
+        "Submodule {}: {} → {}\n",
+        path,
+        short(&submodule.old_commit),
+        short(&submodule.new_commit)
+    );
+    let hunks = vec![DiffHunk {
+        old_start: 0,
+        old_lines: 0,
+        new_start: 1,
+        new_lines: 1,
+        lines: vec![LineChange {
+            change_type: LineChangeType::Addition,
+            content: summary.clone(),
+            old_line_no: None,
+            new_line_no: Some(1),
+            intraline: None,
+        }],
+    }];
+
+    FileChange {
+        path,
+        old_path,
+        status: FileStatus::Submodule,
+        is_binary: false,
+        is_excluded: false,
+        exclusion_reason: None,
+        old_content: None,
+        new_content: Some(summary),
+        old_size: None,
+        new_size: None,
+        hunks,
+        diff: String::new(),
+        submodule: Some(submodule),
+    }
+}
+
+/// Pairs each run of deleted lines with the added lines right after it and records
+/// which words changed between the two sides of every pair.
+pub fn annotate_intraline(lines: &mut [LineChange]) {
+    let mut idx = 0;
+    while idx < lines.len() {
+        if lines[idx].change_type != LineChangeType::Deletion {
+            idx += 1;
+            continue;
+        }
+
+        let deletions_start = idx;
+        while idx < lines.len() && lines[idx].change_type == LineChangeType::Deletion {
+            idx += 1;
+        }
+        let additions_start = idx;
+        while idx < lines.len() && lines[idx].change_type == LineChangeType::Addition {
+            idx += 1;
+        }
+
+        let pairs = (additions_start - deletions_start).min(idx - additions_start);
+        for offset in 0..pairs {
+            let old = lines[deletions_start + offset]
+                .content
+                .trim_end_matches(['\n', '\r']);
+            let new = lines[additions_start + offset]
+                .content
+                .trim_end_matches(['\n', '\r']);
+            if let Some((old_ranges, new_ranges)) = word_diff(old, new) {
+                lines[deletions_start + offset].intraline = Some(old_ranges);
+                lines[additions_start + offset].intraline = Some(new_ranges);
+            }
+        }
+    }
+}
+
+// Splits a line into words, whitespace runs and single punctuation characters,
+// returned as character ranges
+fn tokenize_words(line: &str) -> Vec<(Range<usize>, &str)> {
+    let class = |ch: char| {
+        if ch.is_alphanumeric() || ch == '_' {
+            0
+        } else if ch.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+
+    let mut tokens: Vec<(Range<usize>, &str)> = Vec::new();
+    let mut start: Option<(usize, usize)> = None; // (char index, byte index)
+    let mut prev_class = None;
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    for (char_idx, &(byte_idx, ch)) in chars.iter().enumerate() {
+        let current = class(ch);
+        // Punctuation never merges with its neighbours
+        if prev_class != Some(current) || current == 2 {
+            if let Some((start_char, start_byte)) = start {
+                tokens.push((start_char..char_idx, &line[start_byte..byte_idx]));
+            }
+            start = Some((char_idx, byte_idx));
+        }
+        prev_class = Some(current);
+    }
+    if let Some((start_char, start_byte)) = start {
+        tokens.push((start_char..chars.len(), &line[start_byte..]));
+    }
+    tokens
+}
+
+/// Word-level diff of two versions of a line. Returns the changed character ranges of
+/// the old and the new line, or None when the lines share no words or are too long.
+fn word_diff(old: &str, new: &str) -> Option<IntralineRanges> {
+    let old_tokens = tokenize_words(old);
+    let new_tokens = tokenize_words(new);
+    if old_tokens.is_empty()
+        || new_tokens.is_empty()
+        || old_tokens.len() * new_tokens.len() > MAX_INTRALINE_CELLS
+    {
+        return None;
+    }
+
+    // Longest common subsequence of the tokens, filled from the end
+    let width = new_tokens.len() + 1;
+    let mut lcs = vec![0u16; (old_tokens.len() + 1) * width];
+    for i in (0..old_tokens.len()).rev() {
+        for j in (0..new_tokens.len()).rev() {
+            lcs[i * width + j] = if old_tokens[i].1 == new_tokens[j].1 {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; old_tokens.len()];
+    let mut new_common = vec![false; new_tokens.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old_tokens.len() && j < new_tokens.len() {
+        if old_tokens[i].1 == new_tokens[j].1 {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    // Sharing only whitespace means the line was rewritten, not edited
+    let shares_words = old_tokens
+        .iter()
+        .zip(&old_common)
+        .any(|((_, text), &common)| common && !text.trim().is_empty());
+    if !shares_words {
+        return None;
+    }
+
+    let changed_ranges = |tokens: &[(Range<usize>, &str)], common: &[bool]| {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for ((range, _), _) in tokens.iter().zip(common).filter(|(_, &common)| !common) {
+            match ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => ranges.push(range.clone()),
+            }
+        }
+        ranges
+    };
+    let old_ranges = changed_ranges(&old_tokens, &old_common);
+    let new_ranges = changed_ranges(&new_tokens, &new_common);
+    if old_ranges.is_empty() && new_ranges.is_empty() {
+        return None;
+    }
+    Some((old_ranges, new_ranges))
+}
+
 // Check if a commit matches the author filter pattern (case-insensitive partial match)
 fn matches_author(commit: &Git2Commit, pattern: &str) -> bool {
     let author = commit.author();
@@ -140,15 +594,47 @@ fn matches_author(commit: &Git2Commit, pattern: &str) -> bool {
     name.to_lowercase().contains(&pattern_lower) || email.to_lowercase().contains(&pattern_lower)
 }
 
+// Check if a commit adds, deletes or modifies the file at `path`, compared with its first parent
+fn touches_path(commit: &Git2Commit, path: &Path) -> bool {
+    let entry_id = |tree: Option<Tree>| tree?.get_path(path).ok().map(|entry| entry.id());
+    let parent_tree = commit.parent(0).and_then(|parent| parent.tree()).ok();
+    entry_id(commit.tree().ok()) != entry_id(parent_tree)
+}
+
 // Parse a date string using chrono-english (supports Git-like formats)
 pub fn parse_date(input: &str) -> Result<DateTime<Utc>> {
     let now = Local::now();
+    let normalized = normalize_relative_date(input);
 
-    parse_date_string(input, now, Dialect::Us)
+    parse_date_string(&normalized, now, Dialect::Us)
         .map(|dt| dt.with_timezone(&Utc))
         .with_context(|| format!("Invalid date format: '{}'. Use formats like '2024-01-01', '1 week ago', 'yesterday'", input))
 }
 
+// Rewrite Git-style shorthands into phrases chrono-english understands:
+// "2.weeks.ago" -> "2 weeks ago", "30d" -> "30 days ago"
+fn normalize_relative_date(input: &str) -> String {
+    let input = input.trim();
+
+    if input.ends_with(".ago") {
+        return input.replace('.', " ");
+    }
+
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+    let (amount, unit) = input.split_at(split);
+    let unit = match unit {
+        "h" => Some("hours"),
+        "d" => Some("days"),
+        "w" => Some("weeks"),
+        "y" => Some("years"),
+        _ => None,
+    };
+    match unit {
+        Some(unit) if !amount.is_empty() => format!("{} {} ago", amount, unit),
+        _ => input.to_string(),
+    }
+}
+
 // Check if a commit date is within the specified date range
 fn matches_date_filter(
     commit: &Git2Commit,
@@ -176,6 +662,10 @@ fn matches_date_filter(
 pub struct GitRepository {
     repo: Repository,
     commit_cache: RefCell<Option<Vec<Oid>>>,
+    // Branch, tag or commit history is walked from, None for HEAD
+    start_ref: Option<String>,
+    // Start commit when the commit cache was filled, to notice new commits
+    cached_head: Cell<Option<Oid>>,
     // Shared index for both cache-based playback (asc/desc) and range playback.
     // These modes are mutually exclusive based on CLI arguments.
     commit_index: RefCell<usize>,
@@ -183,9 +673,40 @@ pub struct GitRepository {
     author_filter: Option<String>,
     before_filter: Option<DateTime<Utc>>,
     after_filter: Option<DateTime<Utc>>,
+    message_filter: Option<Regex>,
+    commit_filter: Option<CommitFilter>,
+    // Only commits changing this path are played
+    follow_path: Option<String>,
+    commit_sort: CommitSort,
+    // Merge commits are skipped unless enabled; they're diffed against their first parent
+    include_merges: bool,
+    // 0 disables rename detection, so renames show as a deletion and an addition
+    rename_threshold: u8,
+    // Files animated per commit, None for no limit
+    max_files: Option<usize>,
+    root_max_files: Option<usize>,
+    hide_excluded: bool,
+    skip_root_commits: bool,
+    // Parentless commits seen while collecting history, for skip_root_commits
+    root_commits: RefCell<HashSet<Oid>>,
+    // Commits walked back from the start commit when collecting history, None for all
+    depth: Option<usize>,
+    file_order: FileOrder,
+    // Recency lookups walk history, so the resulting order is kept per commit
+    file_order_cache: RefCell<HashMap<Oid, Vec<usize>>>,
+    // Growth mode lists every file of each commit's tree, walked once per commit
+    tree_paths_cache: RefCell<HashMap<Oid, BTreeSet<String>>>,
+    tag_names: RefCell<HashMap<Oid, Vec<String>>>,
+    // Last commits picked in random playback, which aren't picked again while listed
+    recent_commits: RefCell<VecDeque<Oid>>,
+    random_history: usize,
+    // Saves asc/desc progress after each commit so the next run can carry on from it
+    resume_file: Option<ResumeFile>,
+    // Whether the saved position still has to be applied to the first commit loaded
+    resume_pending: Cell<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum FileStatus {
     Added,
     Deleted,
@@ -193,6 +714,8 @@ pub enum FileStatus {
     Renamed,
     Copied,
     Unmodified,
+    /// A submodule whose recorded commit changed
+    Submodule,
 }
 
 impl FileStatus {
@@ -204,6 +727,7 @@ impl FileStatus {
             FileStatus::Renamed => "R",
             FileStatus::Copied => "C",
             FileStatus::Unmodified => "U",
+            FileStatus::Submodule => "S",
         }
     }
 }
@@ -212,6 +736,8 @@ impl From<Delta> for FileStatus {
     fn from(delta: Delta) -> Self {
         match delta {
             Delta::Added => FileStatus::Added,
+            // Untracked files only show up in working tree diffs, as new files
+            Delta::Untracked => FileStatus::Added,
             Delta::Deleted => FileStatus::Deleted,
             Delta::Modified => FileStatus::Modified,
             Delta::Renamed => FileStatus::Renamed,
@@ -222,24 +748,25 @@ impl From<Delta> for FileStatus {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum LineChangeType {
     Addition,
     Deletion,
     Context,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LineChange {
     pub change_type: LineChangeType,
     pub content: String,
-    #[allow(dead_code)]
     pub old_line_no: Option<usize>,
-    #[allow(dead_code)]
     pub new_line_no: Option<usize>,
+    /// Character ranges of the words that differ from the paired line on the other side,
+    /// set when a deletion is directly followed by an addition
+    pub intraline: Option<Vec<Range<usize>>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DiffHunk {
     pub old_start: usize,
     #[allow(dead_code)]
@@ -251,52 +778,371 @@ pub struct DiffHunk {
     pub lines: Vec<LineChange>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FileChange {
     pub path: String,
     #[allow(dead_code)]
     pub old_path: Option<String>,
     pub status: FileStatus,
-    #[allow(dead_code)]
     pub is_binary: bool,
     pub is_excluded: bool,
     pub exclusion_reason: Option<String>,
+    // Full file contents are left out of JSON output; the hunks carry the diff
+    #[serde(skip)]
     pub old_content: Option<String>,
     #[allow(dead_code)]
+    #[serde(skip)]
     pub new_content: Option<String>,
+    /// Byte size of the file before the change, if it existed
+    pub old_size: Option<usize>,
+    /// Byte size of the file after the change, if it still exists
+    pub new_size: Option<usize>,
     pub hunks: Vec<DiffHunk>,
     #[allow(dead_code)]
+    #[serde(skip)]
     pub diff: String,
+    /// Set when the entry is a submodule (gitlink) whose recorded commit changed
+    pub submodule: Option<SubmoduleChange>,
+}
+
+/// The commits a submodule pointed to before and after a change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubmoduleChange {
+    pub old_commit: Option<String>,
+    pub new_commit: Option<String>,
+}
+
+impl FileChange {
+    /// Returns a compact copy containing only the changed lines of each hunk.
+    ///
+    /// The old content becomes the deleted lines and the new content the added lines,
+    /// so the animation only types and removes actual changes. Hunk starts are rebased
+    /// onto the compact content while each `LineChange` keeps its original line numbers.
+    pub fn changes_only(&self) -> FileChange {
+        let mut old_lines = Vec::new();
+        let mut new_lines = Vec::new();
+        let mut hunks = Vec::new();
+
+        for hunk in &self.hunks {
+            let lines: Vec<LineChange> = hunk
+                .lines
+                .iter()
+                .filter(|line| !matches!(line.change_type, LineChangeType::Context))
+                .cloned()
+                .collect();
+            if lines.is_empty() {
+                continue;
+            }
+
+            let old_start = old_lines.len() + 1;
+            let new_start = new_lines.len() + 1;
+            for line in &lines {
+                let content = line.content.trim_end_matches(['\n', '\r']).to_string();
+                match line.change_type {
+                    LineChangeType::Deletion => old_lines.push(content),
+                    LineChangeType::Addition => new_lines.push(content),
+                    LineChangeType::Context => {}
+                }
+            }
+
+            hunks.push(DiffHunk {
+                old_start,
+                old_lines: old_lines.len() + 1 - old_start,
+                new_start,
+                new_lines: new_lines.len() + 1 - new_start,
+                lines,
+            });
+        }
+
+        let join = |lines: &[String]| {
+            if lines.is_empty() {
+                String::new()
+            } else {
+                format!("{}\n", lines.join("\n"))
+            }
+        };
+
+        FileChange {
+            old_content: Some(join(&old_lines)),
+            new_content: Some(join(&new_lines)),
+            hunks,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy that only animates the hunks overlapping new-file lines `start..=end`.
+    ///
+    /// Hunks outside the range are applied to the old content up front, so the editor
+    /// still shows the whole file while only the focused hunks are typed. Returns `None`
+    /// if no hunk overlaps the range.
+    pub fn focus_lines(&self, start: usize, end: usize) -> Option<FileChange> {
+        let old_content = self.old_content.as_deref().unwrap_or("");
+        let old_lines: Vec<&str> = old_content.lines().collect();
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut hunks = Vec::new();
+        let mut old_idx = 0;
+        let mut offset = 0i64;
+
+        for hunk in &self.hunks {
+            // A pure insertion (no old lines) is anchored after `old_start` instead of at it
+            let start_idx = if hunk.old_lines == 0 {
+                hunk.old_start
+            } else {
+                hunk.old_start.saturating_sub(1)
+            }
+            .min(old_lines.len());
+            let copy_until = start_idx.max(old_idx);
+            lines.extend(old_lines[old_idx..copy_until].iter().map(|l| l.to_string()));
+
+            let hunk_end = hunk.new_start + hunk.new_lines.max(1) - 1;
+            let is_focused = hunk.new_start <= end && start <= hunk_end;
+
+            // Focused hunks keep their old side to be animated, the rest are pre-applied
+            let kept = hunk.lines.iter().filter(|line| match line.change_type {
+                LineChangeType::Context => true,
+                LineChangeType::Deletion => is_focused,
+                LineChangeType::Addition => !is_focused,
+            });
+            lines.extend(kept.map(|line| line.content.trim_end_matches(['\n', '\r']).to_string()));
+
+            if is_focused {
+                let rebase = |n: usize| (n as i64 + offset).max(0) as usize;
+                hunks.push(DiffHunk {
+                    old_start: rebase(hunk.old_start),
+                    lines: hunk
+                        .lines
+                        .iter()
+                        .map(|line| LineChange {
+                            old_line_no: line.old_line_no.map(rebase),
+                            ..line.clone()
+                        })
+                        .collect(),
+                    ..hunk.clone()
+                });
+            } else {
+                offset += hunk.new_lines as i64 - hunk.old_lines as i64;
+            }
+
+            old_idx = (start_idx + hunk.old_lines).min(old_lines.len());
+        }
+
+        if hunks.is_empty() {
+            return None;
+        }
+
+        lines.extend(old_lines[old_idx..].iter().map(|l| l.to_string()));
+        let old_content = if lines.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", lines.join("\n"))
+        };
+
+        Some(FileChange {
+            old_content: Some(old_content),
+            hunks,
+            ..self.clone()
+        })
+    }
+
+    /// Change in byte size between the old and new file, if either size is known
+    pub fn size_delta(&self) -> Option<i64> {
+        if self.old_size.is_none() && self.new_size.is_none() {
+            return None;
+        }
+        Some(self.new_size.unwrap_or(0) as i64 - self.old_size.unwrap_or(0) as i64)
+    }
+
+    /// Number of added and deleted lines across all hunks
+    pub fn line_counts(&self) -> (usize, usize) {
+        self.hunks.iter().flat_map(|hunk| &hunk.lines).fold(
+            (0, 0),
+            |(additions, deletions), line| match line.change_type {
+                LineChangeType::Addition => (additions + 1, deletions),
+                LineChangeType::Deletion => (additions, deletions + 1),
+                _ => (additions, deletions),
+            },
+        )
+    }
+
+    /// Original line numbers of the deleted lines, in the order used by `changes_only`
+    pub fn deleted_line_numbers(&self) -> Vec<usize> {
+        self.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .filter(|line| matches!(line.change_type, LineChangeType::Deletion))
+            .map(|line| line.old_line_no.unwrap_or(0))
+            .collect()
+    }
+
+    /// Returns the inverse change, going from the new content back to the old one.
+    ///
+    /// Additions become deletions and vice versa, and each run of changed lines is
+    /// reordered so the lines to remove still come before the lines to restore.
+    pub fn reversed(&self) -> FileChange {
+        let hunks = self
+            .hunks
+            .iter()
+            .map(|hunk| {
+                let mut lines: Vec<LineChange> = Vec::with_capacity(hunk.lines.len());
+                let mut restored: Vec<LineChange> = Vec::new();
+                for line in &hunk.lines {
+                    let flipped = LineChange {
+                        change_type: match line.change_type {
+                            LineChangeType::Addition => LineChangeType::Deletion,
+                            LineChangeType::Deletion => LineChangeType::Addition,
+                            LineChangeType::Context => LineChangeType::Context,
+                        },
+                        content: line.content.clone(),
+                        old_line_no: line.new_line_no,
+                        new_line_no: line.old_line_no,
+                        intraline: None,
+                    };
+                    match flipped.change_type {
+                        LineChangeType::Addition => restored.push(flipped),
+                        LineChangeType::Deletion => lines.push(flipped),
+                        LineChangeType::Context => {
+                            lines.append(&mut restored);
+                            lines.push(flipped);
+                        }
+                    }
+                }
+                lines.append(&mut restored);
+                annotate_intraline(&mut lines);
+
+                DiffHunk {
+                    old_start: hunk.new_start,
+                    old_lines: hunk.new_lines,
+                    new_start: hunk.old_start,
+                    new_lines: hunk.old_lines,
+                    lines,
+                }
+            })
+            .collect();
+
+        FileChange {
+            status: match self.status {
+                FileStatus::Added => FileStatus::Deleted,
+                FileStatus::Deleted => FileStatus::Added,
+                ref status => status.clone(),
+            },
+            submodule: self.submodule.as_ref().map(|submodule| SubmoduleChange {
+                old_commit: submodule.new_commit.clone(),
+                new_commit: submodule.old_commit.clone(),
+            }),
+            old_content: self.new_content.clone(),
+            new_content: self.old_content.clone(),
+            old_size: self.new_size,
+            new_size: self.old_size,
+            hunks,
+            ..self.clone()
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CommitMetadata {
     pub hash: String,
     pub author: String,
     pub date: DateTime<Utc>,
     pub message: String,
     pub changes: Vec<FileChange>,
+    /// Animation order of `changes` when it differs from the file tree order
+    #[serde(skip)]
+    pub file_order: Option<Vec<usize>>,
+    /// Tags pointing at this commit, filled in when playing the tag timeline
+    pub tags: Vec<String>,
+    /// Whether the commit carries a GPG or SSH signature (not verified)
+    pub signed: bool,
+    /// Files left out of `changes` by `limit_files`
+    pub hidden_files: usize,
 }
 
 impl CommitMetadata {
-    /// Returns indices sorted in FileTree display order (directory -> filename)
+    /// Returns indices sorted in FileTree display order (directory -> filename, or by the
+    /// configured [`FileTreeSort`] within the directory grouping)
     pub fn sorted_file_indices(&self) -> Vec<usize> {
-        let mut indices: Vec<usize> = (0..self.changes.len()).collect();
-        indices.sort_by_key(|&index| {
-            let path = &self.changes[index].path;
-            let parts: Vec<&str> = path.split('/').collect();
-
-            if parts.len() == 1 {
-                // Root level file: ("", filename)
-                (String::new(), path.clone())
-            } else {
-                // File in directory: (directory, filename)
-                let dir = parts[..parts.len() - 1].join("/");
-                let filename = parts[parts.len() - 1].to_string();
-                (dir, filename)
-            }
-        });
-        indices
+        self.sorted_file_indices_by(file_tree_sort())
+    }
+
+    fn sorted_file_indices_by(&self, sort: FileTreeSort) -> Vec<usize> {
+        let entries: Vec<(&str, usize)> = self
+            .changes
+            .iter()
+            .map(|change| (change.path.as_str(), sort.rank(change)))
+            .collect();
+        tree_order(&entries)
+    }
+
+    /// Whether every changed file is excluded, leaving nothing to animate
+    pub fn only_excluded_changes(&self) -> bool {
+        !self.changes.is_empty() && self.changes.iter().all(|change| change.is_excluded)
+    }
+
+    /// Returns indices in the order files should be animated
+    pub fn playback_file_indices(&self) -> Vec<usize> {
+        self.file_order
+            .clone()
+            .unwrap_or_else(|| self.sorted_file_indices())
+    }
+
+    /// Drops every file after the first `max_files` animated ones, in playback order.
+    /// Excluded files before the cut are kept, since they're skipped over quickly.
+    pub fn limit_files(&mut self, max_files: usize) {
+        let order = self.playback_file_indices();
+        let mut animated = 0;
+        let cut = order
+            .iter()
+            .position(|&index| {
+                if !self.changes[index].is_excluded {
+                    animated += 1;
+                }
+                animated > max_files
+            })
+            .unwrap_or(order.len());
+        if cut == order.len() {
+            return;
+        }
+
+        // Only files that would have been animated count as not shown
+        let hidden = order[cut..]
+            .iter()
+            .filter(|&&index| !self.changes[index].is_excluded)
+            .count();
+        let mut kept = order[..cut].to_vec();
+        kept.sort_unstable();
+        self.retain_files(&kept);
+        self.hidden_files += hidden;
+    }
+
+    /// Removes excluded and binary files entirely, instead of listing them and showing a
+    /// placeholder. Unlike [`Self::limit_files`], they aren't counted as not shown.
+    pub fn remove_excluded_files(&mut self) {
+        let kept: Vec<usize> = (0..self.changes.len())
+            .filter(|&index| !self.changes[index].is_excluded && !self.changes[index].is_binary)
+            .collect();
+        if kept.len() < self.changes.len() {
+            self.retain_files(&kept);
+        }
+    }
+
+    // Keeps only the files at the sorted indices in `kept`
+    fn retain_files(&mut self, kept: &[usize]) {
+        // Tree order follows from the paths, but an explicit order needs new indices
+        if let Some(file_order) = &self.file_order {
+            self.file_order = Some(
+                file_order
+                    .iter()
+                    .filter_map(|index| kept.binary_search(index).ok())
+                    .collect(),
+            );
+        }
+        self.changes = std::mem::take(&mut self.changes)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| kept.binary_search(index).is_ok())
+            .map(|(_, change)| change)
+            .collect();
     }
 }
 
@@ -306,23 +1152,46 @@ impl GitRepository {
         Ok(Self {
             repo,
             commit_cache: RefCell::new(None),
+            start_ref: None,
+            cached_head: Cell::new(None),
             commit_index: RefCell::new(0),
             commit_range: RefCell::new(None),
             author_filter: None,
             before_filter: None,
             after_filter: None,
+            message_filter: None,
+            follow_path: None,
+            commit_filter: None,
+            commit_sort: CommitSort::default(),
+            include_merges: false,
+            rename_threshold: DEFAULT_RENAME_THRESHOLD,
+            max_files: None,
+            root_max_files: None,
+            hide_excluded: false,
+            skip_root_commits: false,
+            root_commits: RefCell::new(HashSet::new()),
+            depth: None,
+            file_order: FileOrder::default(),
+            file_order_cache: RefCell::new(HashMap::new()),
+            tree_paths_cache: RefCell::new(HashMap::new()),
+            tag_names: RefCell::new(HashMap::new()),
+            recent_commits: RefCell::new(VecDeque::new()),
+            random_history: DEFAULT_RANDOM_HISTORY,
+            resume_file: None,
+            resume_pending: Cell::new(false),
         })
     }
 
     pub fn get_commit(&self, hash: &str) -> Result<CommitMetadata> {
+        // Any revision works: full or short hashes, HEAD~3, branches and tags
         let obj = self
             .repo
             .revparse_single(hash)
-            .context("Invalid commit hash or commit not found")?;
+            .with_context(|| format!("Invalid commit '{}' or commit not found", hash))?;
 
         let commit = obj.peel_to_commit().context("Object is not a commit")?;
 
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.load_metadata(&commit)
     }
 
     pub fn random_commit(&self) -> Result<CommitMetadata> {
@@ -331,12 +1200,49 @@ impl GitRepository {
         let cache = self.commit_cache.borrow();
         let candidates = cache.as_ref().unwrap();
 
-        let selected_oid = candidates
-            .get(rand::rng().random_range(0..candidates.len()))
-            .context("Failed to select random commit")?;
+        let selected_oid = self.pick_random(candidates)?;
 
-        let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        let commit = self.repo.find_commit(selected_oid)?;
+        self.load_metadata(&commit)
+    }
+
+    // Picks a random commit, rerolling while the pick was one of the last `random_history`
+    // ones. With no more candidates than that, any commit can come up again, since
+    // there'd be nothing left to pick once they had all been played.
+    fn pick_random(&self, candidates: &[Oid]) -> Result<Oid> {
+        let mut rng = rand::rng();
+        let mut recent = self.recent_commits.borrow_mut();
+        let roots = self.root_commits.borrow();
+        // Roots seen in other walks may not be candidates, so only count the ones that are.
+        // They're only skipped while other candidates exist, so a pick remains.
+        let candidate_roots = candidates.iter().filter(|oid| roots.contains(oid)).count();
+        let skip_roots = self.skip_root_commits && candidates.len() > candidate_roots;
+        let pickable = if skip_roots {
+            candidates.len() - candidate_roots
+        } else {
+            candidates.len()
+        };
+        let avoid_recent = pickable > self.random_history;
+
+        let selected_oid = loop {
+            let oid = *candidates
+                .get(rng.random_range(0..candidates.len()))
+                .context("Failed to select random commit")?;
+            if skip_roots && roots.contains(&oid) {
+                continue;
+            }
+            if !avoid_recent || !recent.contains(&oid) {
+                break oid;
+            }
+        };
+
+        if self.random_history > 0 {
+            if recent.len() >= self.random_history {
+                recent.pop_front();
+            }
+            recent.push_back(selected_oid);
+        }
+        Ok(selected_oid)
     }
 
     pub fn next_asc_commit(&self) -> Result<CommitMetadata> {
@@ -350,6 +1256,10 @@ impl GitRepository {
             anyhow::bail!("No non-merge commits found in repository");
         }
 
+        if let Some(resumed) = self.resume_index("asc", candidates, true) {
+            *index = resumed;
+        }
+
         if *index >= candidates.len() {
             anyhow::bail!("All commits have been played");
         }
@@ -361,9 +1271,10 @@ impl GitRepository {
             .context("Failed to select commit")?;
 
         *index += 1;
+        self.save_position("asc", *index, selected_oid);
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.load_metadata(&commit)
     }
 
     pub fn next_desc_commit(&self) -> Result<CommitMetadata> {
@@ -377,6 +1288,10 @@ impl GitRepository {
             anyhow::bail!("No non-merge commits found in repository");
         }
 
+        if let Some(resumed) = self.resume_index("desc", candidates, false) {
+            *index = resumed;
+        }
+
         if *index >= candidates.len() {
             anyhow::bail!("All commits have been played");
         }
@@ -385,39 +1300,339 @@ impl GitRepository {
         let selected_oid = candidates.get(*index).context("Failed to select commit")?;
 
         *index += 1;
+        self.save_position("desc", *index, selected_oid);
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.load_metadata(&commit)
     }
 
-    pub fn reset_index(&self) {
-        *self.commit_index.borrow_mut() = 0;
+    /// Number of candidate commits available for playback (after filters)
+    pub fn commit_count(&self) -> Result<usize> {
+        self.populate_cache()?;
+        Ok(self.commit_cache.borrow().as_ref().map_or(0, |c| c.len()))
     }
 
-    pub fn set_author_filter(&mut self, author: Option<String>) {
-        self.author_filter = author;
+    /// Where sequential (asc/desc or range) playback stands, as the 1-based number of the
+    /// commit last played and the number of commits to play. None before the first one.
+    pub fn playback_position(&self) -> Option<(usize, usize)> {
+        let played = *self.commit_index.borrow();
+        let total = match self.commit_range.borrow().as_ref() {
+            Some(range) => range.len(),
+            None => self.commit_cache.borrow().as_ref()?.len(),
+        };
+        (played > 0).then_some((played, total))
     }
 
-    pub fn set_before_filter(&mut self, before: Option<DateTime<Utc>>) {
-        self.before_filter = before;
-    }
+    /// Collects the commits again if HEAD (or the start ref) moved since they were
+    /// collected, returning how many new commits are on top of the ones seen before.
+    /// After a fast-forward, asc playback carries on where it was, reaching the new
+    /// commits last. When history was rewritten instead (a reset, rebase or amend), it
+    /// continues with just the new commits, since the old position no longer applies.
+    pub fn refresh_commits(&self) -> Result<usize> {
+        let head = self.start_commit()?;
+        let previous_head = self.cached_head.get();
+        if previous_head == Some(head) {
+            return Ok(0);
+        }
+        let fast_forward = match previous_head {
+            Some(previous_head) => self.repo.graph_descendant_of(head, previous_head)?,
+            None => true,
+        };
 
-    pub fn set_after_filter(&mut self, after: Option<DateTime<Utc>>) {
-        self.after_filter = after;
+        let previous = self.commit_cache.borrow_mut().take();
+        if let Err(e) = self.populate_cache() {
+            *self.commit_cache.borrow_mut() = previous;
+            self.cached_head.set(previous_head);
+            return Err(e);
+        }
+
+        let cache = self.commit_cache.borrow();
+        let commits = cache.as_deref().unwrap_or_default();
+        let known: HashSet<&Oid> = previous.iter().flatten().collect();
+        let added = commits
+            .iter()
+            .take_while(|oid| !known.contains(oid))
+            .count();
+        if !fast_forward {
+            *self.commit_index.borrow_mut() = commits.len() - added;
+        }
+        Ok(added)
     }
 
-    pub fn set_commit_range(&self, range: &str) -> Result<()> {
-        let commits = self.parse_commit_range(range)?;
-        *self.commit_range.borrow_mut() = Some(commits);
+    pub fn reset_index(&self) {
         *self.commit_index.borrow_mut() = 0;
-        Ok(())
     }
 
-    pub fn next_range_commit_asc(&self) -> Result<CommitMetadata> {
-        let range = self.commit_range.borrow();
-        let commits = range.as_ref().context("Commit range not set")?;
-        let mut index = self.commit_index.borrow_mut();
-
+    /// Remember asc/desc playback progress in the repository's state file under the
+    /// user cache directory, and start from the saved position on the first commit
+    pub fn set_resume(&mut self, resume: bool) {
+        let file = if resume {
+            ResumeFile::for_repository(self.repo.path())
+        } else {
+            None
+        };
+        self.set_resume_file(file);
+    }
+
+    pub fn set_resume_file(&mut self, file: Option<ResumeFile>) {
+        self.resume_pending.set(file.is_some());
+        self.resume_file = file;
+    }
+
+    // Index of the next commit to play according to the state file, on the first call only.
+    // The last played commit is looked up by hash so that commits made since then don't
+    // shift the position, and a finished run starts over.
+    fn resume_index(&self, order: &str, candidates: &[Oid], ascending: bool) -> Option<usize> {
+        if !self.resume_pending.replace(false) {
+            return None;
+        }
+        let saved = self.resume_file.as_ref()?.load(order)?;
+        let index = match candidates
+            .iter()
+            .position(|oid| oid.to_string() == saved.commit)
+        {
+            Some(position) if ascending => candidates.len() - position,
+            Some(position) => position + 1,
+            None => saved.commit_index,
+        };
+        Some(if index < candidates.len() { index } else { 0 })
+    }
+
+    fn save_position(&self, order: &str, commit_index: usize, oid: &Oid) {
+        if let Some(file) = &self.resume_file {
+            // Losing the position only means starting over next time, so playback goes on
+            let _ = file.save(
+                order,
+                SavedPosition {
+                    commit_index,
+                    commit: oid.to_string(),
+                },
+            );
+        }
+    }
+
+    /// Walks history from a branch, tag or commit instead of HEAD. Fails right away if
+    /// the ref doesn't name a commit.
+    pub fn set_start_ref(&mut self, name: Option<String>) -> Result<()> {
+        if let Some(name) = &name {
+            self.resolve_commit(name)?;
+        }
+        self.start_ref = name;
+        Ok(())
+    }
+
+    // The commit history is walked from: the start ref if set, otherwise HEAD
+    fn start_commit(&self) -> Result<Oid> {
+        match &self.start_ref {
+            Some(name) => self.resolve_commit(name),
+            None if self.is_empty() => anyhow::bail!("This repository has no commits yet"),
+            None => Ok(self.repo.head()?.peel_to_commit()?.id()),
+        }
+    }
+
+    /// Returns true for a repository without any commits, such as one just created with
+    /// `git init`, whose HEAD points at a branch that doesn't exist yet
+    pub fn is_empty(&self) -> bool {
+        self.repo.is_empty().unwrap_or(false)
+    }
+
+    fn resolve_commit(&self, name: &str) -> Result<Oid> {
+        self.repo
+            .revparse_single(name)
+            .and_then(|object| object.peel_to_commit())
+            .map(|commit| commit.id())
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Ref '{}' not found: expected a branch, tag or commit in this repository",
+                    name
+                )
+            })
+    }
+
+    pub fn set_author_filter(&mut self, author: Option<String>) {
+        self.author_filter = author;
+    }
+
+    pub fn set_before_filter(&mut self, before: Option<DateTime<Utc>>) {
+        self.before_filter = before;
+    }
+
+    pub fn set_after_filter(&mut self, after: Option<DateTime<Utc>>) {
+        self.after_filter = after;
+    }
+
+    pub fn set_message_filter(&mut self, pattern: Option<Regex>) {
+        self.message_filter = pattern;
+    }
+
+    pub fn set_commit_filter(&mut self, filter: Option<CommitFilter>) {
+        self.commit_filter = filter;
+    }
+
+    /// Only plays commits that change the file at `path`, relative to the repository root
+    pub fn set_follow_path(&mut self, path: Option<String>) {
+        self.follow_path = path;
+    }
+
+    /// Keeps merge commits among the commits to play, shown as their diff against the
+    /// first parent
+    pub fn set_include_merges(&mut self, include_merges: bool) {
+        self.include_merges = include_merges;
+    }
+
+    /// Sets how similar (0-100%) a removed and an added file must be to be shown as a
+    /// rename or copy; 0 turns rename detection off
+    pub fn set_rename_threshold(&mut self, threshold: u8) {
+        self.rename_threshold = threshold.min(100);
+    }
+
+    /// Caps how many files of each commit are animated; the rest are left out
+    pub fn set_max_files(&mut self, max_files: Option<usize>) {
+        self.max_files = max_files;
+    }
+
+    /// A stricter cap on animated files for root commits, whose initial import would
+    /// otherwise add every file of the project
+    pub fn set_root_max_files(&mut self, root_max_files: Option<usize>) {
+        self.root_max_files = root_max_files;
+    }
+
+    /// Drops excluded and binary files from every commit, so they're neither listed in the
+    /// file tree nor given a placeholder during playback
+    pub fn set_hide_excluded(&mut self, hide_excluded: bool) {
+        self.hide_excluded = hide_excluded;
+    }
+
+    /// Leaves root commits out of random picks, unless there is nothing else to play
+    pub fn set_skip_root_commits(&mut self, skip: bool) {
+        self.skip_root_commits = skip;
+    }
+
+    /// Only walks the `depth` newest commits when collecting history to play, so large
+    /// repositories start quickly. Filters then apply to those commits; ranges are not limited.
+    pub fn set_depth(&mut self, depth: Option<usize>) {
+        self.depth = depth;
+    }
+
+    /// Sets how many recently picked commits random playback avoids (0 allows repeats)
+    pub fn set_random_history(&mut self, random_history: usize) {
+        self.random_history = random_history;
+    }
+
+    pub fn set_commit_sort(&mut self, commit_sort: CommitSort) {
+        self.commit_sort = commit_sort;
+    }
+
+    pub fn set_file_order(&mut self, file_order: FileOrder) {
+        self.file_order = file_order;
+    }
+
+    /// Paths of every file in the tree a commit was made on top of (empty for root commits)
+    pub fn parent_tree_paths(&self, hash: &str) -> Result<BTreeSet<String>> {
+        let commit = self
+            .repo
+            .revparse_single(hash)
+            .and_then(|obj| obj.peel_to_commit())
+            .context("Invalid commit hash or commit not found")?;
+        match commit.parent(0) {
+            Ok(parent) => self.commit_tree_paths(&parent),
+            Err(_) => Ok(BTreeSet::new()),
+        }
+    }
+
+    /// Paths of every file in a commit's tree, as growth mode lists the codebase
+    pub fn tree_paths(&self, hash: &str) -> Result<BTreeSet<String>> {
+        let commit = self
+            .repo
+            .revparse_single(hash)
+            .and_then(|obj| obj.peel_to_commit())
+            .context("Invalid commit hash or commit not found")?;
+        self.commit_tree_paths(&commit)
+    }
+
+    fn commit_tree_paths(&self, commit: &Git2Commit) -> Result<BTreeSet<String>> {
+        if let Some(paths) = self.tree_paths_cache.borrow().get(&commit.id()) {
+            return Ok(paths.clone());
+        }
+
+        let mut paths = BTreeSet::new();
+        commit.tree()?.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(ObjectType::Blob) {
+                if let Some(name) = entry.name() {
+                    paths.insert(format!("{}{}", dir, name));
+                }
+            }
+            TreeWalkResult::Ok
+        })?;
+        self.tree_paths_cache
+            .borrow_mut()
+            .insert(commit.id(), paths.clone());
+        Ok(paths)
+    }
+
+    pub fn set_commit_range(&self, range: &str) -> Result<()> {
+        let commits = self.parse_commit_range(range)?;
+        *self.commit_range.borrow_mut() = Some(commits);
+        *self.commit_index.borrow_mut() = 0;
+        Ok(())
+    }
+
+    /// Plays the commits tags point at, oldest first, in place of a commit range.
+    /// Tagged commits are filtered like any other. Returns the number of tagged commits.
+    pub fn set_tag_timeline(&self) -> Result<usize> {
+        let mut tag_names: HashMap<Oid, Vec<String>> = HashMap::new();
+        for name in self.repo.tag_names(None)?.iter().flatten() {
+            let Ok(reference) = self.repo.find_reference(&format!("refs/tags/{}", name)) else {
+                continue;
+            };
+            // Tags on trees or blobs have no commit to replay
+            if let Ok(commit) = reference.peel_to_commit() {
+                tag_names
+                    .entry(commit.id())
+                    .or_default()
+                    .push(name.to_string());
+            }
+        }
+
+        // Walk back from the tagged commits so releases play in history order
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        for oid in tag_names.keys() {
+            revwalk.push(*oid)?;
+        }
+
+        let mut commits = Vec::new();
+        for oid in revwalk.filter_map(|oid| oid.ok()) {
+            if !tag_names.contains_key(&oid) {
+                continue;
+            }
+            let commit = self.repo.find_commit(oid)?;
+            if self.matches_filters(&commit)? {
+                commits.push((oid, commit.author().when().seconds()));
+            }
+        }
+
+        // Newest first, like the revision walk; ties keep their walk order
+        if self.commit_sort == CommitSort::Date {
+            commits.sort_by_key(|&(_, time)| std::cmp::Reverse(time));
+        }
+        commits.reverse();
+        for names in tag_names.values_mut() {
+            names.sort();
+        }
+
+        let count = commits.len();
+        *self.commit_range.borrow_mut() = Some(commits.into_iter().map(|(oid, _)| oid).collect());
+        *self.commit_index.borrow_mut() = 0;
+        *self.tag_names.borrow_mut() = tag_names;
+        Ok(count)
+    }
+
+    pub fn next_range_commit_asc(&self) -> Result<CommitMetadata> {
+        let range = self.commit_range.borrow();
+        let commits = range.as_ref().context("Commit range not set")?;
+        let mut index = self.commit_index.borrow_mut();
+
         if commits.is_empty() {
             anyhow::bail!("No commits in range");
         }
@@ -430,7 +1645,7 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.load_metadata(&commit)
     }
 
     pub fn next_range_commit_desc(&self) -> Result<CommitMetadata> {
@@ -452,7 +1667,7 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.load_metadata(&commit)
     }
 
     pub fn random_range_commit(&self) -> Result<CommitMetadata> {
@@ -463,47 +1678,130 @@ impl GitRepository {
             anyhow::bail!("No commits in range");
         }
 
-        let selected_oid = commits
-            .get(rand::rng().random_range(0..commits.len()))
-            .context("Failed to select random commit")?;
+        let selected_oid = self.pick_random(commits)?;
 
-        let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        let commit = self.repo.find_commit(selected_oid)?;
+        self.load_metadata(&commit)
+    }
+
+    // Number of files a commit changes relative to its first parent
+    fn changed_file_count(&self, commit: &Git2Commit) -> usize {
+        let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+        commit
+            .tree()
+            .and_then(|tree| {
+                self.repo
+                    .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            })
+            .map(|diff| diff.deltas().len())
+            .unwrap_or(0)
+    }
+
+    /// Whether a commit passes the author, message, date, expression and path filters
+    fn matches_filters(&self, commit: &Git2Commit) -> Result<bool> {
+        if let Some(ref pattern) = self.author_filter {
+            if !matches_author(commit, pattern) {
+                return Ok(false);
+            }
+        }
+        if let Some(ref pattern) = self.message_filter {
+            if !pattern.is_match(&String::from_utf8_lossy(commit.message_bytes())) {
+                return Ok(false);
+            }
+        }
+        if !matches_date_filter(
+            commit,
+            self.before_filter.as_ref(),
+            self.after_filter.as_ref(),
+        )? {
+            return Ok(false);
+        }
+        if let Some(ref filter) = self.commit_filter {
+            if !filter.matches(commit, &mut || self.changed_file_count(commit)) {
+                return Ok(false);
+            }
+        }
+        if let Some(ref path) = self.follow_path {
+            if !touches_path(commit, Path::new(path)) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
     }
 
-    // Collect non-merge commits from a revwalk, applying author and date filters if set
+    // Collect commits from a revwalk, skipping merges unless included and applying
+    // author, message, date and expression filters if set
     fn collect_commits_from_revwalk(
         &self,
         revwalk: git2::Revwalk,
+        limit: Option<usize>,
         context: &str,
     ) -> Result<Vec<Oid>> {
         let mut commits = Vec::new();
-        for oid in revwalk.filter_map(|oid| oid.ok()) {
-            if let Ok(commit) = self.repo.find_commit(oid) {
-                if commit.parent_count() <= 1 {
-                    if let Some(ref pattern) = self.author_filter {
-                        if !matches_author(&commit, pattern) {
-                            continue;
-                        }
-                    }
-                    if !matches_date_filter(
-                        &commit,
-                        self.before_filter.as_ref(),
-                        self.after_filter.as_ref(),
-                    )? {
-                        continue;
-                    }
-                    commits.push(oid);
-                }
+        for oid in revwalk.take(limit.unwrap_or(usize::MAX)) {
+            let oid = oid.context("Failed to walk the commit history")?;
+            let commit = self.repo.find_commit(oid)?;
+            if commit.parent_count() == 0 {
+                self.root_commits.borrow_mut().insert(oid);
             }
+            if (commit.parent_count() <= 1 || self.include_merges)
+                && self.matches_filters(&commit)?
+            {
+                commits.push((oid, commit.author().when().seconds()));
+            }
+        }
+
+        // Newest first, like the revision walk; ties keep their walk order
+        if self.commit_sort == CommitSort::Date {
+            commits.sort_by_key(|&(_, time)| std::cmp::Reverse(time));
         }
+        let commits: Vec<Oid> = commits.into_iter().map(|(oid, _)| oid).collect();
 
         if commits.is_empty() {
+            if let (Some(pattern), None, None, None, None, None) = (
+                &self.author_filter,
+                &self.before_filter,
+                &self.after_filter,
+                &self.message_filter,
+                &self.commit_filter,
+                &self.follow_path,
+            ) {
+                anyhow::bail!("No commits found for author '{}' {}", pattern, context);
+            }
             if self.author_filter.is_some()
                 || self.before_filter.is_some()
                 || self.after_filter.is_some()
+                || self.message_filter.is_some()
+                || self.commit_filter.is_some()
+                || self.follow_path.is_some()
             {
-                anyhow::bail!("No commits found matching the filters {}", context);
+                let mut filters = Vec::new();
+                if let Some(pattern) = &self.author_filter {
+                    filters.push(format!("author '{}'", pattern));
+                }
+                if let Some(pattern) = &self.message_filter {
+                    filters.push(format!("message matching '{}'", pattern));
+                }
+                if let Some(after) = &self.after_filter {
+                    filters.push(format!("since {}", after.format("%Y-%m-%d %H:%M")));
+                }
+                if let Some(before) = &self.before_filter {
+                    filters.push(format!("until {}", before.format("%Y-%m-%d %H:%M")));
+                }
+                if let Some(filter) = &self.commit_filter {
+                    filters.push(format!("filter '{}'", filter.source()));
+                }
+                if let Some(path) = &self.follow_path {
+                    filters.push(format!("changing '{}'", path));
+                }
+                anyhow::bail!(
+                    "No commits found matching the filters ({}) {}",
+                    filters.join(", "),
+                    context
+                );
+            }
+            if self.include_merges {
+                anyhow::bail!("No commits found {}", context);
             }
             anyhow::bail!("No non-merge commits found {}", context);
         }
@@ -538,7 +1836,7 @@ impl GitRepository {
         };
 
         let end = if parts[1].is_empty() {
-            self.repo.head()?.peel_to_commit()?.id()
+            self.start_commit()?
         } else {
             self.repo.revparse_single(parts[1])?.id()
         };
@@ -550,7 +1848,7 @@ impl GitRepository {
             revwalk.hide(start_oid)?;
         }
 
-        let mut commits = self.collect_commits_from_revwalk(revwalk, "in range")?;
+        let mut commits = self.collect_commits_from_revwalk(revwalk, None, "in range")?;
         commits.reverse();
         Ok(commits)
     }
@@ -558,18 +1856,105 @@ impl GitRepository {
     fn populate_cache(&self) -> Result<()> {
         let mut cache = self.commit_cache.borrow_mut();
         if cache.is_none() {
+            let start = self.start_commit()?;
             let mut revwalk = self.repo.revwalk()?;
-            revwalk.push_head()?;
+            revwalk.push(start)?;
+            self.cached_head.set(Some(start));
 
-            let candidates = self.collect_commits_from_revwalk(revwalk, "in repository")?;
+            let candidates =
+                self.collect_commits_from_revwalk(revwalk, self.depth, "in repository")?;
             *cache = Some(candidates);
         }
         Ok(())
     }
 
+    fn load_metadata(&self, commit: &Git2Commit) -> Result<CommitMetadata> {
+        let mut metadata =
+            Self::extract_metadata_with_changes(&self.repo, commit, self.rename_threshold)?;
+        if self.file_order == FileOrder::Recency {
+            metadata.file_order = Some(self.recency_file_order(commit, &metadata)?);
+        }
+        if let Some(tags) = self.tag_names.borrow().get(&commit.id()) {
+            metadata.tags = tags.clone();
+        }
+        if self.hide_excluded {
+            metadata.remove_excluded_files();
+        }
+        let max_files = if commit.parent_count() == 0 {
+            self.max_files.into_iter().chain(self.root_max_files).min()
+        } else {
+            self.max_files
+        };
+        if let Some(max_files) = max_files {
+            metadata.limit_files(max_files);
+        }
+        Ok(metadata)
+    }
+
+    // Order files by how recently each was modified before this commit.
+    // Files with no earlier history (e.g. newly added) keep tree order at the end.
+    fn recency_file_order(
+        &self,
+        commit: &Git2Commit,
+        metadata: &CommitMetadata,
+    ) -> Result<Vec<usize>> {
+        if let Some(order) = self.file_order_cache.borrow().get(&commit.id()) {
+            return Ok(order.clone());
+        }
+
+        let changes = &metadata.changes;
+        let mut last_touched: Vec<Option<usize>> = vec![None; changes.len()];
+
+        if commit.parent_count() > 0 {
+            let mut revwalk = self.repo.revwalk()?;
+            revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+            revwalk.push(commit.parent_id(0)?)?;
+
+            let ancestors = revwalk
+                .filter_map(|oid| oid.ok())
+                .take(RECENCY_HISTORY_LIMIT)
+                .enumerate();
+            for (age, oid) in ancestors {
+                let Ok(ancestor) = self.repo.find_commit(oid) else {
+                    continue;
+                };
+                let tree = ancestor.tree()?;
+                let parent_tree = ancestor.parent(0).and_then(|parent| parent.tree()).ok();
+                let entry_id = |tree: &Tree, path: &str| {
+                    tree.get_path(Path::new(path)).ok().map(|entry| entry.id())
+                };
+
+                for (index, change) in changes.iter().enumerate() {
+                    if last_touched[index].is_some() {
+                        continue;
+                    }
+                    // A renamed file's history lives under its old path
+                    let path = change.old_path.as_deref().unwrap_or(&change.path);
+                    let before = parent_tree.as_ref().and_then(|tree| entry_id(tree, path));
+                    if entry_id(&tree, path) != before {
+                        last_touched[index] = Some(age);
+                    }
+                }
+
+                if last_touched.iter().all(Option::is_some) {
+                    break;
+                }
+            }
+        }
+
+        let mut order = metadata.sorted_file_indices();
+        order.sort_by_key(|&index| last_touched[index].unwrap_or(usize::MAX));
+
+        self.file_order_cache
+            .borrow_mut()
+            .insert(commit.id(), order.clone());
+        Ok(order)
+    }
+
     fn extract_metadata_with_changes(
         repo: &Repository,
         commit: &Git2Commit,
+        rename_threshold: u8,
     ) -> Result<CommitMetadata> {
         let hash = commit.id().to_string();
         let author = commit.author();
@@ -577,8 +1962,10 @@ impl GitRepository {
         let timestamp = author.when().seconds();
         let date = DateTime::from_timestamp(timestamp, 0).unwrap_or_else(Utc::now);
         let message = commit.message().unwrap_or("").trim().to_string();
+        // Both GPG and SSH signatures are stored in the gpgsig header
+        let signed = commit.header_field_bytes("gpgsig").is_ok();
 
-        let changes = Self::extract_changes(repo, commit)?;
+        let changes = Self::extract_changes(repo, commit, rename_threshold)?;
 
         Ok(CommitMetadata {
             hash,
@@ -586,10 +1973,18 @@ impl GitRepository {
             date,
             message,
             changes,
+            file_order: None,
+            tags: Vec::new(),
+            signed,
+            hidden_files: 0,
         })
     }
 
-    fn extract_changes(repo: &Repository, commit: &Git2Commit) -> Result<Vec<FileChange>> {
+    fn extract_changes(
+        repo: &Repository,
+        commit: &Git2Commit,
+        rename_threshold: u8,
+    ) -> Result<Vec<FileChange>> {
         let commit_tree = commit.tree().context("Failed to get commit tree")?;
         let parent_tree = if commit.parent_count() > 0 {
             match commit.parent(0).and_then(|p| p.tree()) {
@@ -603,7 +1998,7 @@ impl GitRepository {
         let mut diff_opts = DiffOptions::new();
         diff_opts.context_lines(3);
 
-        let diff = match repo.diff_tree_to_tree(
+        let mut diff = match repo.diff_tree_to_tree(
             parent_tree.as_ref(),
             Some(&commit_tree),
             Some(&mut diff_opts),
@@ -611,6 +2006,7 @@ impl GitRepository {
             Ok(d) => d,
             Err(_) => return Ok(Vec::new()), // Skip if diff fails
         };
+        detect_renames(&mut diff, rename_threshold)?;
 
         let mut changes = Vec::new();
 
@@ -628,7 +2024,7 @@ impl GitRepository {
                 .unwrap_or("unknown")
                 .to_string();
 
-            let old_path = if delta.status() == Delta::Renamed {
+            let old_path = if matches!(delta.status(), Delta::Renamed | Delta::Copied) {
                 delta
                     .old_file()
                     .path()
@@ -638,7 +2034,20 @@ impl GitRepository {
                 None
             };
 
-            let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
+            if let Some(submodule) = submodule_change(&delta) {
+                changes.push(submodule_file_change(path, old_path, submodule));
+                continue;
+            }
+
+            // Binary detection needs the blob contents, which libgit2 loads when building the patch
+            let patch = git2::Patch::from_diff(&diff, i).ok().flatten();
+            let is_binary = patch
+                .as_ref()
+                .is_some_and(|patch| patch.delta().flags().is_binary())
+                || delta.new_file().is_binary()
+                || delta.old_file().is_binary();
+            let old_size = diff_file_size(repo, &delta.old_file());
+            let new_size = diff_file_size(repo, &delta.new_file());
 
             let old_content = if let Some(parent_tree) = parent_tree.as_ref() {
                 if let Some(old_file_path) = delta.old_file().path() {
@@ -647,8 +2056,8 @@ impl GitRepository {
                         .ok()
                         .and_then(|entry| repo.find_blob(entry.id()).ok())
                         .and_then(|blob| {
-                            if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-                                Some(String::from_utf8_lossy(blob.content()).to_string())
+                            if !blob.is_binary() && blob.size() <= max_blob_size() {
+                                Some(decode_text(blob.content()))
                             } else {
                                 None
                             }
@@ -666,8 +2075,8 @@ impl GitRepository {
                     .ok()
                     .and_then(|entry| repo.find_blob(entry.id()).ok())
                     .and_then(|blob| {
-                        if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-                            Some(String::from_utf8_lossy(blob.content()).to_string())
+                        if !blob.is_binary() && blob.size() <= max_blob_size() {
+                            Some(decode_text(blob.content()))
                         } else {
                             None
                         }
@@ -679,7 +2088,7 @@ impl GitRepository {
             let mut hunks = Vec::new();
             let mut diff_text = String::new();
 
-            if let Ok(Some(mut patch)) = git2::Patch::from_diff(&diff, i) {
+            if let Some(mut patch) = patch {
                 if let Ok(patch_str) = patch.to_buf() {
                     diff_text = String::from_utf8_lossy(patch_str.as_ref()).to_string();
                 }
@@ -695,8 +2104,7 @@ impl GitRepository {
 
                             for line_idx in 0..num_lines {
                                 if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
-                                    let content =
-                                        String::from_utf8_lossy(line.content()).to_string();
+                                    let content = decode_text(line.content());
                                     let origin = line.origin();
 
                                     let (change_type, old_no, new_no) = match origin {
@@ -724,9 +2132,11 @@ impl GitRepository {
                                         content,
                                         old_line_no: old_no,
                                         new_line_no: new_no,
+                                        intraline: None,
                                     });
                                 }
                             }
+                            annotate_intraline(&mut lines);
 
                             hunks.push(DiffHunk {
                                 old_start: hunk.old_start() as usize,
@@ -747,17 +2157,8 @@ impl GitRepository {
                 .filter(|line| !matches!(line.change_type, LineChangeType::Context))
                 .count();
 
-            // Determine exclusion reason
-            let (is_excluded, exclusion_reason) = if should_exclude_file(&path) {
-                (true, Some("lock/generated file".to_string()))
-            } else if total_changed_lines > MAX_CHANGE_LINES {
-                (
-                    true,
-                    Some(format!("too many changes ({} lines)", total_changed_lines)),
-                )
-            } else {
-                (false, None)
-            };
+            let exclusion_reason = exclusion_reason(repo, &path, total_changed_lines);
+            let is_excluded = exclusion_reason.is_some();
 
             changes.push(FileChange {
                 path,
@@ -768,8 +2169,11 @@ impl GitRepository {
                 exclusion_reason,
                 old_content,
                 new_content,
+                old_size,
+                new_size,
                 hunks,
                 diff: diff_text,
+                submodule: None,
             });
         }
 
@@ -780,24 +2184,38 @@ impl GitRepository {
     ///
     /// DiffMode::Staged - Only staged changes (index vs HEAD)
     /// DiffMode::Unstaged - Only unstaged changes (workdir vs index)
+    /// DiffMode::WorkingTree - Everything not yet committed (workdir vs HEAD)
     pub fn get_working_tree_diff(&self, mode: DiffMode) -> Result<CommitMetadata> {
         let changes = match mode {
             DiffMode::Staged => self.extract_staged_changes()?,
             DiffMode::Unstaged => self.extract_unstaged_changes()?,
+            DiffMode::WorkingTree => self.extract_uncommitted_changes()?,
         };
 
         let message = match mode {
             DiffMode::Staged => "Staged changes",
             DiffMode::Unstaged => "Unstaged changes",
+            DiffMode::WorkingTree => "Uncommitted changes",
         };
 
-        Ok(CommitMetadata {
+        let mut metadata = CommitMetadata {
             hash: "working-tree".to_string(),
             author: "Working Tree".to_string(),
             date: Utc::now(),
             message: message.to_string(),
             changes,
-        })
+            file_order: None,
+            tags: Vec::new(),
+            signed: false,
+            hidden_files: 0,
+        };
+        if self.hide_excluded {
+            metadata.remove_excluded_files();
+        }
+        if let Some(max_files) = self.max_files {
+            metadata.limit_files(max_files);
+        }
+        Ok(metadata)
     }
 
     /// Extract staged changes (index vs HEAD)
@@ -816,10 +2234,11 @@ impl GitRepository {
         let mut diff_opts = DiffOptions::new();
         diff_opts.context_lines(3);
 
-        let diff = self
+        let mut diff = self
             .repo
             .diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut diff_opts))
             .context("Failed to diff tree to index")?;
+        detect_renames(&mut diff, self.rename_threshold)?;
 
         self.extract_changes_from_diff(&diff, head_tree.as_ref(), None)
     }
@@ -844,6 +2263,36 @@ impl GitRepository {
         self.extract_changes_from_diff_workdir(&diff, &index)
     }
 
+    /// Extract all uncommitted changes (workdir vs HEAD), with untracked files as additions
+    fn extract_uncommitted_changes(&self) -> Result<Vec<FileChange>> {
+        let head_tree = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_tree().ok());
+
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.context_lines(3);
+        diff_opts.include_untracked(true);
+        diff_opts.recurse_untracked_dirs(true);
+        diff_opts.show_untracked_content(true);
+
+        // Going through the index keeps staged renames and respects its stat cache
+        let mut diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut diff_opts))
+            .context("Failed to diff HEAD to workdir")?;
+        detect_renames(&mut diff, self.rename_threshold)?;
+
+        self.extract_changes_from_diff_with_content(&diff, |delta| {
+            let old_content = head_tree
+                .as_ref()
+                .and_then(|tree| self.get_blob_content_from_tree(tree, delta.old_file().path()));
+            let new_content = self.get_workdir_content(delta.new_file().path());
+            (old_content, new_content)
+        })
+    }
+
     /// Extract FileChange data from a git2::Diff (for staged changes)
     fn extract_changes_from_diff(
         &self,
@@ -902,7 +2351,7 @@ impl GitRepository {
                 .unwrap_or("unknown")
                 .to_string();
 
-            let old_path = if delta.status() == Delta::Renamed {
+            let old_path = if matches!(delta.status(), Delta::Renamed | Delta::Copied) {
                 delta
                     .old_file()
                     .path()
@@ -912,9 +2361,15 @@ impl GitRepository {
                 None
             };
 
-            let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
+            if let Some(submodule) = submodule_change(&delta) {
+                changes.push(submodule_file_change(path, old_path, submodule));
+                continue;
+            }
+
+            let old_size = diff_file_size(&self.repo, &delta.old_file());
+            let new_size = diff_file_size(&self.repo, &delta.new_file());
             let (old_content, new_content) = get_content(&delta);
-            let (hunks, diff_text) = self.extract_hunks_from_diff(diff, i, is_binary)?;
+            let (hunks, diff_text, is_binary) = self.extract_hunks_from_diff(diff, i)?;
 
             // Calculate total changed lines
             let total_changed_lines: usize = hunks
@@ -923,16 +2378,8 @@ impl GitRepository {
                 .filter(|line| !matches!(line.change_type, LineChangeType::Context))
                 .count();
 
-            let (is_excluded, exclusion_reason) = if should_exclude_file(&path) {
-                (true, Some("lock/generated file".to_string()))
-            } else if total_changed_lines > MAX_CHANGE_LINES {
-                (
-                    true,
-                    Some(format!("too many changes ({} lines)", total_changed_lines)),
-                )
-            } else {
-                (false, None)
-            };
+            let exclusion_reason = exclusion_reason(&self.repo, &path, total_changed_lines);
+            let is_excluded = exclusion_reason.is_some();
 
             changes.push(FileChange {
                 path,
@@ -943,8 +2390,11 @@ impl GitRepository {
                 exclusion_reason,
                 old_content,
                 new_content,
+                old_size,
+                new_size,
                 hunks,
                 diff: diff_text,
+                submodule: None,
             });
         }
 
@@ -960,8 +2410,8 @@ impl GitRepository {
         let path = path?;
         let entry = tree.get_path(path).ok()?;
         let blob = self.repo.find_blob(entry.id()).ok()?;
-        if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-            Some(String::from_utf8_lossy(blob.content()).to_string())
+        if !blob.is_binary() && blob.size() <= max_blob_size() {
+            Some(decode_text(blob.content()))
         } else {
             None
         }
@@ -972,12 +2422,18 @@ impl GitRepository {
         &self,
         diff: &git2::Diff,
         delta_idx: usize,
-        is_binary: bool,
-    ) -> Result<(Vec<DiffHunk>, String)> {
+    ) -> Result<(Vec<DiffHunk>, String, bool)> {
         let mut hunks = Vec::new();
         let mut diff_text = String::new();
+        let mut is_binary = false;
 
         if let Ok(Some(mut patch)) = git2::Patch::from_diff(diff, delta_idx) {
+            // Binary detection needs the blob contents, which libgit2 loads when building the patch
+            let delta = patch.delta();
+            is_binary = delta.flags().is_binary()
+                || delta.new_file().is_binary()
+                || delta.old_file().is_binary();
+
             if let Ok(patch_str) = patch.to_buf() {
                 diff_text = String::from_utf8_lossy(patch_str.as_ref()).to_string();
             }
@@ -993,7 +2449,7 @@ impl GitRepository {
 
                         for line_idx in 0..num_lines {
                             if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
-                                let content = String::from_utf8_lossy(line.content()).to_string();
+                                let content = decode_text(line.content());
                                 let origin = line.origin();
 
                                 let (change_type, old_no, new_no) = match origin {
@@ -1021,9 +2477,11 @@ impl GitRepository {
                                     content,
                                     old_line_no: old_no,
                                     new_line_no: new_no,
+                                    intraline: None,
                                 });
                             }
                         }
+                        annotate_intraline(&mut lines);
 
                         hunks.push(DiffHunk {
                             old_start: hunk.old_start() as usize,
@@ -1037,7 +2495,7 @@ impl GitRepository {
             }
         }
 
-        Ok((hunks, diff_text))
+        Ok((hunks, diff_text, is_binary))
     }
 
     /// Get file content from the current index
@@ -1057,8 +2515,8 @@ impl GitRepository {
         let entry = index.get_path(path, 0)?;
         let blob = self.repo.find_blob(entry.id).ok()?;
 
-        if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-            Some(String::from_utf8_lossy(blob.content()).to_string())
+        if !blob.is_binary() && blob.size() <= max_blob_size() {
+            Some(decode_text(blob.content()))
         } else {
             None
         }
@@ -1070,19 +2528,141 @@ impl GitRepository {
     /// - Path is not provided
     /// - Repository is bare (no working directory)
     /// - File cannot be read (missing, permissions, binary/non-UTF8)
-    /// - File size exceeds MAX_BLOB_SIZE (500KB)
+    /// - File size exceeds the blob size limit (500KB by default)
     fn get_workdir_content(&self, path: Option<&std::path::Path>) -> Option<String> {
         let path = path?;
         let workdir = self.repo.workdir()?;
         let full_path = workdir.join(path);
 
         match std::fs::read_to_string(&full_path) {
-            Ok(content) if content.len() <= MAX_BLOB_SIZE => Some(content),
+            Ok(content) if content.len() <= max_blob_size() => {
+                Some(decode_text(content.as_bytes()))
+            }
             _ => None,
         }
     }
 }
 
+// Fixtures shared by tests across modules. The binary's test build leaves out the library
+// modules using some of them.
+#[cfg(test)]
+impl FileChange {
+    /// A text file changed from `a` to `b`, with no hunks
+    pub(crate) fn test_modified(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            old_path: None,
+            status: FileStatus::Modified,
+            is_binary: false,
+            is_excluded: false,
+            exclusion_reason: None,
+            old_content: Some("a\n".to_string()),
+            new_content: Some("b\n".to_string()),
+            old_size: Some(2),
+            new_size: Some(2),
+            hunks: Vec::new(),
+            diff: String::new(),
+            submodule: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl CommitMetadata {
+    /// A commit made now with the given changes
+    pub(crate) fn test_with(changes: Vec<FileChange>) -> Self {
+        Self {
+            hash: "0123456789abcdef".to_string(),
+            author: "Test User".to_string(),
+            date: Utc::now(),
+            message: "Test commit".to_string(),
+            changes,
+            file_order: None,
+            tags: Vec::new(),
+            signed: false,
+            hidden_files: 0,
+        }
+    }
+}
+
+/// RAII guard for temporary git repository - auto-cleans on drop
+#[cfg(test)]
+pub(crate) struct TestRepo {
+    pub(crate) path: std::path::PathBuf,
+    pub(crate) repo: git2::Repository,
+}
+
+#[cfg(test)]
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+impl TestRepo {
+    pub(crate) fn new() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::time::{SystemTime, UNIX_EPOCH};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let unique_id = format!(
+            "{}_{}_{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = std::env::temp_dir().join(format!("gitlogue_test_{}", unique_id));
+        if path.exists() {
+            std::fs::remove_dir_all(&path).unwrap();
+        }
+        std::fs::create_dir_all(&path).unwrap();
+
+        let repo = git2::Repository::init(&path).unwrap();
+
+        // Configure user for commits
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        Self { path, repo }
+    }
+
+    /// Write a file, stage it and commit on top of HEAD
+    pub(crate) fn commit_file(&self, name: &str, content: &str, message: &str) -> Oid {
+        self.commit_files(&[(name, content)], message)
+    }
+
+    /// Write several files, stage them and commit on top of HEAD
+    pub(crate) fn commit_files(&self, files: &[(&str, &str)], message: &str) -> Oid {
+        let mut index = self.repo.index().unwrap();
+        for (name, content) in files {
+            let file_path = self.path.join(name);
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            std::fs::write(&file_path, content).unwrap();
+            index.add_path(std::path::Path::new(name)).unwrap();
+        }
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = self.repo.find_tree(tree_id).unwrap();
+        let sig = self.repo.signature().unwrap();
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        self.repo
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1157,6 +2737,7 @@ mod tests {
         assert!(!should_exclude_file("styles.css"));
         assert!(!should_exclude_file("lock.txt"));
         assert!(!should_exclude_file("minify.rs"));
+        assert!(!should_exclude_file("migrations/20240101_create_users.sql"));
     }
 
     #[test]
@@ -1206,6 +2787,228 @@ mod tests {
         assert!(init_ignore_patterns(&patterns).is_err());
     }
 
+    fn line(
+        change_type: LineChangeType,
+        content: &str,
+        old: Option<usize>,
+        new: Option<usize>,
+    ) -> LineChange {
+        LineChange {
+            change_type,
+            content: format!("{}\n", content),
+            old_line_no: old,
+            new_line_no: new,
+            intraline: None,
+        }
+    }
+
+    #[test]
+    fn test_word_diff_marks_changed_words() {
+        let (old, new) =
+            word_diff("    let total = count + 1;", "    let total = count * 2;").unwrap();
+        assert_eq!(old, vec![22..23, 24..25]);
+        assert_eq!(new, vec![22..23, 24..25]);
+
+        // Inserted words only show up on the new side
+        let (old, new) = word_diff("call(a)", "call(a, b)").unwrap();
+        assert!(old.is_empty());
+        assert_eq!((new[0].start, new[0].end, new.len()), (6, 9, 1));
+
+        // Lines sharing no words are a rewrite, not an edit
+        assert!(word_diff("foo bar", "baz qux").is_none());
+        assert!(word_diff("same", "same").is_none());
+    }
+
+    #[test]
+    fn test_intraline_ranges_pair_deletions_with_additions() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("lib.rs", "let a = 1;\nlet b = 2;\nkeep();\n", "Initial");
+        test_repo.commit_file(
+            "lib.rs",
+            "let a = 10;\nlet b = 2;\nkeep();\nadded();\n",
+            "Edit",
+        );
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit("HEAD").unwrap();
+        let intraline: Vec<_> = metadata.changes[0].hunks[0]
+            .lines
+            .iter()
+            .map(|line| {
+                let ranges = line.intraline.as_ref().map(|ranges| {
+                    ranges
+                        .iter()
+                        .map(|range| (range.start, range.end))
+                        .collect::<Vec<_>>()
+                });
+                (line.change_type.clone(), ranges)
+            })
+            .collect();
+
+        assert_eq!(
+            intraline,
+            vec![
+                (LineChangeType::Deletion, Some(vec![(8, 9)])),
+                (LineChangeType::Addition, Some(vec![(8, 10)])),
+                (LineChangeType::Context, None),
+                (LineChangeType::Context, None),
+                (LineChangeType::Addition, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changes_only_drops_context_and_rebases_hunks() {
+        let change = FileChange {
+            old_content: None,
+            new_content: None,
+            old_size: None,
+            new_size: None,
+            hunks: vec![
+                DiffHunk {
+                    old_start: 10,
+                    old_lines: 3,
+                    new_start: 10,
+                    new_lines: 3,
+                    lines: vec![
+                        line(LineChangeType::Context, "a", Some(10), Some(10)),
+                        line(LineChangeType::Deletion, "b", Some(11), None),
+                        line(LineChangeType::Addition, "B", None, Some(11)),
+                        line(LineChangeType::Context, "c", Some(12), Some(12)),
+                    ],
+                },
+                DiffHunk {
+                    old_start: 40,
+                    old_lines: 2,
+                    new_start: 40,
+                    new_lines: 3,
+                    lines: vec![
+                        line(LineChangeType::Deletion, "x", Some(40), None),
+                        line(LineChangeType::Addition, "X", None, Some(40)),
+                        line(LineChangeType::Addition, "Y", None, Some(41)),
+                    ],
+                },
+            ],
+            ..FileChange::test_modified("src/lib.rs")
+        };
+
+        let compact = change.changes_only();
+
+        assert_eq!(compact.old_content.as_deref(), Some("b\nx\n"));
+        assert_eq!(compact.new_content.as_deref(), Some("B\nX\nY\n"));
+        assert_eq!(compact.hunks.len(), 2);
+        assert_eq!(compact.hunks[0].old_start, 1);
+        assert_eq!(compact.hunks[1].old_start, 2);
+        assert!(compact
+            .hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .all(|line| !matches!(line.change_type, LineChangeType::Context)));
+        assert_eq!(change.deleted_line_numbers(), vec![11, 40]);
+    }
+
+    #[test]
+    fn test_reversed_swaps_sides_and_keeps_removals_first() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("lib.rs", "let a = 1;\nkeep();\n", "Initial");
+        test_repo.commit_file("lib.rs", "let a = 10;\nkeep();\nadded();\n", "Edit");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let change = &repo.get_commit("HEAD").unwrap().changes[0];
+        let reversed = change.reversed();
+
+        assert_eq!(reversed.old_content, change.new_content);
+        assert_eq!(reversed.new_content, change.old_content);
+        assert_eq!(reversed.status, FileStatus::Modified);
+        assert_eq!(change.line_counts(), (2, 1));
+        assert_eq!(reversed.line_counts(), (1, 2));
+
+        let hunk = &reversed.hunks[0];
+        assert_eq!(
+            (
+                hunk.old_start,
+                hunk.old_lines,
+                hunk.new_start,
+                hunk.new_lines
+            ),
+            (1, 3, 1, 2)
+        );
+        let lines: Vec<_> = hunk
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.change_type.clone(),
+                    line.content.trim_end(),
+                    line.old_line_no,
+                    line.new_line_no,
+                    line.intraline.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (LineChangeType::Deletion, "let a = 10;", Some(1), None, true),
+                (LineChangeType::Addition, "let a = 1;", None, Some(1), true),
+                (LineChangeType::Context, "keep();", Some(2), Some(2), false),
+                (LineChangeType::Deletion, "added();", Some(3), None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_focus_lines_pre_applies_other_hunks() {
+        let test_repo = TestRepo::new();
+        let original: Vec<String> = (1..=30).map(|n| format!("line {}", n)).collect();
+        test_repo.commit_file("notes.txt", &(original.join("\n") + "\n"), "Initial");
+
+        let mut updated = original.clone();
+        updated[2] = "line three".to_string();
+        updated.insert(27, "inserted".to_string());
+        updated.remove(14);
+        test_repo.commit_file("notes.txt", &(updated.join("\n") + "\n"), "Edit");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let change = &repo.get_commit("HEAD").unwrap().changes[0];
+
+        // Only the third hunk (new lines 24-30) overlaps
+        let focused = change.focus_lines(27, 27).unwrap();
+        assert_eq!(focused.hunks.len(), 1);
+        // The deletion in the second hunk was pre-applied, shifting the hunk up by one
+        assert_eq!(focused.hunks[0].old_start, 24);
+
+        let mut expected_old = original.clone();
+        expected_old[2] = "line three".to_string();
+        expected_old.remove(14);
+        assert_eq!(
+            focused.old_content.as_deref(),
+            Some((expected_old.join("\n") + "\n").as_str())
+        );
+        assert_eq!(focused.new_content, change.new_content);
+
+        assert!(change.focus_lines(100, 120).is_none());
+    }
+
+    #[test]
+    fn test_parse_date_git_style_shorthands() {
+        let close = |a: DateTime<Utc>, b: DateTime<Utc>| (a - b).num_seconds().abs() < 5;
+
+        assert!(close(
+            parse_date("2.weeks.ago").unwrap(),
+            parse_date("2 weeks ago").unwrap()
+        ));
+        assert!(close(
+            parse_date("30d").unwrap(),
+            parse_date("30 days ago").unwrap()
+        ));
+        assert_eq!(
+            parse_date("2024-01-01").unwrap(),
+            parse_date(" 2024-01-01 ").unwrap()
+        );
+        assert!(parse_date("30x").is_err());
+    }
+
     // DiffMode tests
     #[test]
     fn test_diff_mode_default() {
@@ -1213,48 +3016,834 @@ mod tests {
         assert_eq!(mode, DiffMode::Staged);
     }
 
-    // RAII guard for temporary git repository - auto-cleans on drop
-    struct TestRepo {
-        path: std::path::PathBuf,
-        repo: git2::Repository,
+    #[test]
+    fn test_single_commit_repository() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("README.md", "hello\n", "Initial commit");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        assert_eq!(repo.commit_count().unwrap(), 1);
+
+        assert_eq!(repo.playback_position(), None);
+        let first = repo.next_asc_commit().unwrap();
+        assert_eq!(first.message, "Initial commit");
+        assert_eq!(repo.playback_position(), Some((1, 1)));
+        assert!(repo.next_asc_commit().is_err());
+        assert_eq!(repo.playback_position(), Some((1, 1)));
+
+        let random = repo.random_commit().unwrap();
+        assert_eq!(random.hash, first.hash);
+    }
+
+    #[test]
+    fn test_message_filter() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "a\n", "Add parser");
+        test_repo.commit_file("b.txt", "b\n", "Fix typo in README");
+        test_repo.commit_file("c.txt", "c\n", "parser: handle escapes");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_message_filter(Some(Regex::new("(?i)^parser|add parser").unwrap()));
+        assert_eq!(repo.commit_count().unwrap(), 2);
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Add parser");
+        assert_eq!(
+            repo.next_asc_commit().unwrap().message,
+            "parser: handle escapes"
+        );
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_message_filter(Some(Regex::new("release").unwrap()));
+        let err = repo.commit_count().unwrap_err().to_string();
+        assert!(err.contains("message matching 'release'"), "{}", err);
+    }
+
+    #[test]
+    fn test_commit_filter_expression() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_files(&[("a.txt", "a\n"), ("b.txt", "b\n")], "Add parser");
+        test_repo.commit_file("a.txt", "a2\n", "Fix parser bug");
+        test_repo.commit_files(&[("a.txt", "a3\n"), ("c.txt", "c\n")], "Docs");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        let filter = CommitFilter::parse(r#"message~"PARSER" and (files>1 or author=nobody)"#);
+        repo.set_commit_filter(Some(filter.unwrap()));
+        assert_eq!(repo.commit_count().unwrap(), 1);
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Add parser");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_commit_filter(Some(CommitFilter::parse("files > 5").unwrap()));
+        let err = repo.commit_count().unwrap_err().to_string();
+        assert!(err.contains("filter 'files > 5'"), "{}", err);
+    }
+
+    #[test]
+    fn test_rename_threshold() {
+        let test_repo = TestRepo::new();
+        let content: String = (1..=10).map(|i| format!("line {}\n", i)).collect();
+        test_repo.commit_file("old.rs", &content, "Add file");
+
+        // Move the file and edit one of its ten lines
+        std::fs::remove_file(test_repo.path.join("old.rs")).unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.remove_path(Path::new("old.rs")).unwrap();
+        index.write().unwrap();
+        let moved = test_repo.commit_file("new.rs", &content.replace("line 5", "five"), "Move");
+
+        // Detection is off by default
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit(&moved.to_string()).unwrap();
+        assert_eq!(metadata.changes.len(), 2);
+        assert!(metadata.changes.iter().all(|c| c.old_path.is_none()));
+
+        repo.set_rename_threshold(50);
+        let metadata = repo.get_commit(&moved.to_string()).unwrap();
+        assert_eq!(metadata.changes.len(), 1);
+        assert_eq!(metadata.changes[0].status, FileStatus::Renamed);
+        assert_eq!(metadata.changes[0].old_path.as_deref(), Some("old.rs"));
+        assert_eq!(metadata.changes[0].path, "new.rs");
+
+        // 90% similar falls short of a 95% threshold
+        repo.set_rename_threshold(95);
+        let metadata = repo.get_commit(&moved.to_string()).unwrap();
+        let statuses: Vec<_> = metadata.changes.iter().map(|c| c.status.clone()).collect();
+        assert_eq!(statuses, vec![FileStatus::Added, FileStatus::Deleted]);
+    }
+
+    #[test]
+    fn test_include_merges_diffs_against_first_parent() {
+        let test_repo = TestRepo::new();
+        let base = test_repo.commit_file("a.txt", "a\n", "Base");
+        let side = test_repo.commit_file("b.txt", "b\n", "Side");
+        test_repo.repo.set_head_detached(base).unwrap();
+        let main = test_repo.commit_file("c.txt", "c\n", "Main");
+
+        // Merge commit on top of main, with side as its second parent
+        let merge = {
+            std::fs::write(test_repo.path.join("merged.txt"), "m\n").unwrap();
+            let mut index = test_repo.repo.index().unwrap();
+            index.add_path(Path::new("merged.txt")).unwrap();
+            let tree = test_repo
+                .repo
+                .find_tree(index.write_tree().unwrap())
+                .unwrap();
+            let sig = test_repo.repo.signature().unwrap();
+            let parents = [
+                &test_repo.repo.find_commit(main).unwrap(),
+                &test_repo.repo.find_commit(side).unwrap(),
+            ];
+            test_repo
+                .repo
+                .commit(Some("HEAD"), &sig, &sig, "Merge side", &tree, &parents)
+                .unwrap()
+        };
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        assert_eq!(repo.commit_count().unwrap(), 3);
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_include_merges(true);
+        assert_eq!(repo.commit_count().unwrap(), 4);
+        let metadata = repo.next_desc_commit().unwrap();
+        assert_eq!(metadata.hash, merge.to_string());
+        let paths: Vec<&str> = metadata.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["merged.txt"]);
     }
 
-    impl Drop for TestRepo {
-        fn drop(&mut self) {
-            let _ = std::fs::remove_dir_all(&self.path);
+    #[test]
+    fn test_random_commit_avoids_recent_picks() {
+        let test_repo = TestRepo::new();
+        for n in 0..4 {
+            test_repo.commit_file("a.txt", &format!("{}\n", n), &format!("Commit {}", n));
+        }
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_random_history(3);
+        for _ in 0..10 {
+            let picks: std::collections::HashSet<String> =
+                (0..4).map(|_| repo.random_commit().unwrap().hash).collect();
+            assert_eq!(picks.len(), 4);
+        }
+
+        // No more commits than the history allows repeats instead of looping forever
+        repo.set_random_history(4);
+        for _ in 0..10 {
+            repo.random_commit().unwrap();
         }
     }
 
-    impl TestRepo {
-        fn new() -> Self {
-            use std::sync::atomic::{AtomicU64, Ordering};
-            use std::time::{SystemTime, UNIX_EPOCH};
-            static COUNTER: AtomicU64 = AtomicU64::new(0);
+    #[test]
+    fn test_resume_continues_after_last_played_commit() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "1\n", "First");
+        let second = test_repo.commit_file("a.txt", "2\n", "Second");
+        test_repo.commit_file("a.txt", "3\n", "Third");
+        let state_file = || Some(ResumeFile::new(test_repo.path.join(".git/resume.json")));
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_resume_file(state_file());
+        repo.next_asc_commit().unwrap();
+        assert_eq!(repo.next_asc_commit().unwrap().hash, second.to_string());
+
+        // A commit made in between doesn't shift the asc position
+        let fourth = test_repo.commit_file("a.txt", "4\n", "Fourth");
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_resume_file(state_file());
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Third");
+        assert_eq!(repo.next_asc_commit().unwrap().hash, fourth.to_string());
+
+        // Desc keeps its own position, and a finished run starts over
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_resume_file(state_file());
+        assert_eq!(repo.next_desc_commit().unwrap().hash, fourth.to_string());
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_resume_file(state_file());
+        assert_eq!(repo.next_asc_commit().unwrap().message, "First");
+    }
 
-            let unique_id = format!(
-                "{}_{}_{}",
-                std::process::id(),
-                SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_nanos(),
-                COUNTER.fetch_add(1, Ordering::SeqCst)
-            );
-            let path = std::env::temp_dir().join(format!("gitlogue_test_{}", unique_id));
-            if path.exists() {
-                std::fs::remove_dir_all(&path).unwrap();
-            }
-            std::fs::create_dir_all(&path).unwrap();
+    #[test]
+    fn test_refresh_commits_picks_up_new_head() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "1\n", "First");
+        test_repo.commit_file("a.txt", "2\n", "Second");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.next_asc_commit().unwrap();
+        repo.next_asc_commit().unwrap();
+        assert!(repo.next_asc_commit().is_err());
+        assert_eq!(repo.refresh_commits().unwrap(), 0);
+
+        let third = test_repo.commit_file("a.txt", "3\n", "Third");
+        assert_eq!(repo.refresh_commits().unwrap(), 1);
+        assert_eq!(repo.next_asc_commit().unwrap().hash, third.to_string());
+        assert!(repo.next_asc_commit().is_err());
+
+        // Amending rewrites history: only the replacement commit is new
+        let second = test_repo
+            .repo
+            .find_commit(third)
+            .unwrap()
+            .parent_id(0)
+            .unwrap();
+        test_repo.repo.set_head_detached(second).unwrap();
+        let amended = test_repo.commit_file("a.txt", "3!\n", "Third, amended");
+        assert_eq!(repo.refresh_commits().unwrap(), 1);
+        assert_eq!(repo.next_asc_commit().unwrap().hash, amended.to_string());
+        assert!(repo.next_asc_commit().is_err());
+
+        // Resetting to an older commit leaves nothing new to play
+        test_repo.repo.set_head_detached(second).unwrap();
+        assert_eq!(repo.refresh_commits().unwrap(), 0);
+        assert!(repo.next_asc_commit().is_err());
+    }
+
+    #[test]
+    fn test_start_ref_walks_branch_history() {
+        let test_repo = TestRepo::new();
+        let first = test_repo.commit_file("a.txt", "1\n", "First");
+        test_repo.commit_file("a.txt", "2\n", "Second");
+        test_repo
+            .repo
+            .branch("old", &test_repo.repo.find_commit(first).unwrap(), false)
+            .unwrap();
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_start_ref(Some("old".to_string())).unwrap();
+        assert_eq!(repo.commit_count().unwrap(), 1);
+        assert_eq!(repo.next_desc_commit().unwrap().hash, first.to_string());
+
+        let error = repo.set_start_ref(Some("missing".to_string())).unwrap_err();
+        assert!(error.to_string().contains("Ref 'missing' not found"));
+    }
+
+    #[test]
+    fn test_crlf_line_endings_are_normalized() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("win.txt", "one\r\ntwo\r\n", "Initial");
+        test_repo.commit_file("win.txt", "one\r\nthree\r\n", "Edit");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let change = &repo.get_commit("HEAD").unwrap().changes[0];
+
+        assert_eq!(change.old_content.as_deref(), Some("one\ntwo\n"));
+        assert_eq!(change.new_content.as_deref(), Some("one\nthree\n"));
+        let contents: Vec<_> = change.hunks[0]
+            .lines
+            .iter()
+            .map(|line| line.content.as_str())
+            .collect();
+        assert_eq!(contents, ["one\n", "two\n", "three\n"]);
+    }
+
+    #[test]
+    fn test_signed_commits_are_detected() {
+        let test_repo = TestRepo::new();
+        let unsigned = test_repo.commit_file("a.txt", "a\n", "Unsigned");
+
+        // Re-create HEAD with a signature header; its content doesn't have to verify
+        let repo = &test_repo.repo;
+        let parent = repo.find_commit(unsigned).unwrap();
+        let sig = repo.signature().unwrap();
+        let buffer = repo
+            .commit_create_buffer(&sig, &sig, "Signed", &parent.tree().unwrap(), &[&parent])
+            .unwrap();
+        let signature = "-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----";
+        let signed = repo
+            .commit_signed(buffer.as_str().unwrap(), signature, None)
+            .unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        assert!(!repo.get_commit(&unsigned.to_string()).unwrap().signed);
+        assert!(repo.get_commit(&signed.to_string()).unwrap().signed);
+    }
+
+    #[test]
+    fn test_commit_metadata_serializes_to_json() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "one\n", "Initial");
+        let head = test_repo.commit_file("a.txt", "two\n", "Change a");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit(&head.to_string()).unwrap();
+        let json = serde_json::to_value(&metadata).unwrap();
+
+        assert_eq!(json["hash"], head.to_string());
+        assert_eq!(json["message"], "Change a");
+        let change = &json["changes"][0];
+        assert_eq!(change["path"], "a.txt");
+        assert_eq!(change["status"], "Modified");
+        assert!(change.get("old_content").is_none());
+        let lines = &change["hunks"][0]["lines"];
+        assert_eq!(lines[0]["change_type"], "Deletion");
+        assert_eq!(lines[0]["content"], "one\n");
+        assert_eq!(lines[1]["change_type"], "Addition");
+        assert_eq!(lines[1]["new_line_no"], 1);
+    }
 
-            let repo = git2::Repository::init(&path).unwrap();
+    #[test]
+    fn test_recency_file_order() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_files(&[("a.txt", "a\n"), ("b.txt", "b\n")], "Initial");
+        test_repo.commit_file("a.txt", "a2\n", "Touch a");
+        test_repo.commit_file("b.txt", "b2\n", "Touch b");
+        let head = test_repo.commit_files(
+            &[("a.txt", "a3\n"), ("b.txt", "b3\n"), ("c.txt", "c\n")],
+            "Touch all",
+        );
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit(&head.to_string()).unwrap();
+        assert_eq!(metadata.file_order, None);
+
+        repo.set_file_order(FileOrder::Recency);
+        let metadata = repo.get_commit(&head.to_string()).unwrap();
+        let paths: Vec<&str> = metadata
+            .playback_file_indices()
+            .into_iter()
+            .map(|index| metadata.changes[index].path.as_str())
+            .collect();
+
+        // b.txt was touched most recently, c.txt has no earlier history
+        assert_eq!(paths, vec!["b.txt", "a.txt", "c.txt"]);
+    }
 
-            // Configure user for commits
-            let mut config = repo.config().unwrap();
-            config.set_str("user.name", "Test User").unwrap();
-            config.set_str("user.email", "test@example.com").unwrap();
+    #[test]
+    fn test_get_commit_accepts_any_revision() {
+        let test_repo = TestRepo::new();
+        let first = test_repo.commit_file("a.txt", "1\n", "First");
+        test_repo.commit_file("a.txt", "2\n", "Second");
+        let commit = test_repo.repo.find_commit(first).unwrap();
+        let sig = test_repo.repo.signature().unwrap();
+        test_repo
+            .repo
+            .tag("v1.0", commit.as_object(), &sig, "Release", false)
+            .unwrap();
 
-            Self { path, repo }
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let first = first.to_string();
+        for spec in [first.as_str(), &first[..7], "HEAD~1", "v1.0"] {
+            assert_eq!(repo.get_commit(spec).unwrap().hash, first, "{}", spec);
         }
+        let error = repo.get_commit("no-such-ref").unwrap_err();
+        assert!(error.to_string().contains("no-such-ref"));
+    }
+
+    #[test]
+    fn test_follow_path_keeps_commits_changing_the_file() {
+        let test_repo = TestRepo::new();
+        let added = test_repo.commit_file("src/lib.rs", "1\n", "Add lib");
+        test_repo.commit_file("README.md", "hi\n", "Add readme");
+        let changed = test_repo.commit_files(
+            &[("src/lib.rs", "2\n"), ("README.md", "hello\n")],
+            "Touch both",
+        );
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_follow_path(Some("src/lib.rs".to_string()));
+        assert_eq!(repo.commit_count().unwrap(), 2);
+        assert_eq!(repo.next_asc_commit().unwrap().hash, added.to_string());
+        assert_eq!(repo.next_asc_commit().unwrap().hash, changed.to_string());
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_follow_path(Some("missing.rs".to_string()));
+        let error = repo.commit_count().unwrap_err();
+        assert!(error.to_string().contains("changing 'missing.rs'"));
+    }
+
+    #[test]
+    fn test_empty_repository_has_no_commits() {
+        let test_repo = TestRepo::new();
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        assert!(repo.is_empty());
+        let error = repo.random_commit().unwrap_err();
+        assert_eq!(error.to_string(), "This repository has no commits yet");
+
+        test_repo.commit_file("a.txt", "a\n", "First");
+        assert!(!GitRepository::open(&test_repo.path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_depth_limits_the_history_walk() {
+        let test_repo = TestRepo::new();
+        let oldest = test_repo.commit_file("a.txt", "1\n", "First");
+        test_repo.commit_file("a.txt", "2\n", "Second");
+        let newest = test_repo.commit_file("a.txt", "3\n", "Third");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_depth(Some(2));
+        assert_eq!(repo.commit_count().unwrap(), 2);
+        for _ in 0..10 {
+            assert_ne!(repo.random_commit().unwrap().hash, oldest.to_string());
+        }
+        assert_eq!(repo.next_desc_commit().unwrap().hash, newest.to_string());
+
+        // A range is walked in full
+        repo.set_commit_range(&format!("{}..{}", oldest, newest))
+            .unwrap();
+        assert_eq!(repo.playback_position(), None);
+        repo.next_range_commit_asc().unwrap();
+        assert_eq!(repo.playback_position(), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_root_commits_can_be_capped_and_skipped() {
+        let test_repo = TestRepo::new();
+        let root = test_repo.commit_files(
+            &[("a.txt", "a\n"), ("b.txt", "b\n"), ("c.txt", "c\n")],
+            "Import",
+        );
+        test_repo.commit_files(&[("a.txt", "a2\n"), ("b.txt", "b2\n")], "Update");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_root_max_files(Some(1));
+        let root_metadata = repo.get_commit(&root.to_string()).unwrap();
+        assert_eq!(root_metadata.changes.len(), 1);
+        assert_eq!(root_metadata.hidden_files, 2);
+        // Other commits keep every file
+        assert_eq!(repo.get_commit("HEAD").unwrap().changes.len(), 2);
+
+        repo.set_skip_root_commits(true);
+        repo.set_random_history(0);
+        for _ in 0..20 {
+            assert_eq!(repo.random_commit().unwrap().message, "Update");
+        }
+
+        // Roots outside the candidates, e.g. from another walk, don't stop the skipping
+        let update = repo.resolve_commit("HEAD").unwrap();
+        repo.root_commits.borrow_mut().insert(Oid::zero());
+        for _ in 0..20 {
+            assert_eq!(repo.pick_random(&[root, update]).unwrap(), update);
+        }
+    }
+
+    #[test]
+    fn test_max_files_keeps_first_animated_files() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("README.md", "hi\n", "Initial");
+        let head = test_repo.commit_files(
+            &[
+                ("a.rs", "a\n"),
+                ("Cargo.lock", "lock\n"),
+                ("c.rs", "c\n"),
+                ("d.rs", "d\n"),
+                ("vendor/Cargo.lock", "lock\n"),
+            ],
+            "Touch several",
+        );
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_max_files(Some(2));
+        let metadata = repo.get_commit(&head.to_string()).unwrap();
+        let paths: Vec<&str> = metadata
+            .playback_file_indices()
+            .into_iter()
+            .map(|index| metadata.changes[index].path.as_str())
+            .collect();
+
+        // The lock files are excluded, so they count neither towards the limit nor as hidden
+        assert_eq!(paths, vec!["Cargo.lock", "a.rs", "c.rs"]);
+        assert_eq!(metadata.hidden_files, 1);
+
+        repo.set_max_files(Some(3));
+        assert_eq!(repo.get_commit(&head.to_string()).unwrap().hidden_files, 0);
+    }
+
+    #[test]
+    fn test_tree_paths_list_the_whole_codebase_of_a_commit() {
+        let test_repo = TestRepo::new();
+        let first = test_repo.commit_file("README.md", "hi\n", "Initial");
+        // Files from commits that were never played still show up later on
+        test_repo.commit_files(&[("src/a.rs", "a\n"), ("old.txt", "o\n")], "Add files");
+        let mut index = test_repo.repo.index().unwrap();
+        index.remove_path(Path::new("old.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::remove_file(test_repo.path.join("old.txt")).unwrap();
+        let head = test_repo.commit_file("src/b.rs", "b\n", "Add b");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let paths = repo.tree_paths(&head.to_string()).unwrap();
+        assert_eq!(
+            paths.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["README.md", "src/a.rs", "src/b.rs"]
+        );
+        assert_eq!(repo.parent_tree_paths(&first.to_string()).unwrap().len(), 0);
+        // Walked once, then served from the cache
+        assert_eq!(repo.tree_paths(&head.to_string()).unwrap(), paths);
+        assert_eq!(repo.tree_paths_cache.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_file_tree_sort_keeps_directories_together() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_files(
+            &[
+                ("src/old.rs", "1\n2\n3\n"),
+                ("b.txt", "b\n"),
+                ("z.txt", "z\n"),
+            ],
+            "Initial",
+        );
+        let mut index = test_repo.repo.index().unwrap();
+        for deleted in ["b.txt", "z.txt"] {
+            index.remove_path(Path::new(deleted)).unwrap();
+            std::fs::remove_file(test_repo.path.join(deleted)).unwrap();
+        }
+        index.write().unwrap();
+        let head = test_repo.commit_files(
+            &[
+                ("a.txt", "a\n"),
+                ("c.txt", "c\n"),
+                ("src/old.rs", "1\n"),
+                ("src/big.rs", "a\nb\nc\nd\ne\n"),
+            ],
+            "Mixed",
+        );
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit(&head.to_string()).unwrap();
+        let paths = |sort: FileTreeSort| -> Vec<&str> {
+            metadata
+                .sorted_file_indices_by(sort)
+                .into_iter()
+                .map(|index| metadata.changes[index].path.as_str())
+                .collect()
+        };
+
+        assert_eq!(
+            paths(FileTreeSort::Path),
+            vec![
+                "a.txt",
+                "b.txt",
+                "c.txt",
+                "z.txt",
+                "src/big.rs",
+                "src/old.rs"
+            ]
+        );
+        // src/big.rs is the biggest change, so its directory comes first
+        assert_eq!(
+            paths(FileTreeSort::Magnitude),
+            vec![
+                "src/big.rs",
+                "src/old.rs",
+                "a.txt",
+                "b.txt",
+                "c.txt",
+                "z.txt"
+            ]
+        );
+        // Added files come before deleted ones within a directory, but the added
+        // src/big.rs still stays below the deleted root files
+        assert_eq!(
+            paths(FileTreeSort::Status),
+            vec![
+                "a.txt",
+                "c.txt",
+                "b.txt",
+                "z.txt",
+                "src/big.rs",
+                "src/old.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hide_excluded_removes_excluded_and_binary_files() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("README.md", "hi\n", "Initial");
+        let head = test_repo.commit_files(
+            &[
+                ("a.rs", "a\n"),
+                ("Cargo.lock", "lock\n"),
+                ("logo.bin", "\0\x01\x02"),
+            ],
+            "Add assets",
+        );
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        assert_eq!(repo.get_commit(&head.to_string()).unwrap().changes.len(), 3);
+
+        repo.set_hide_excluded(true);
+        let metadata = repo.get_commit(&head.to_string()).unwrap();
+        let paths: Vec<&str> = metadata.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.rs"]);
+        // Hidden files aren't reported as cut by max_files
+        assert_eq!(metadata.hidden_files, 0);
+    }
+
+    #[test]
+    fn test_tag_timeline() {
+        let test_repo = TestRepo::new();
+        let first = test_repo.commit_file("a.txt", "a\n", "Initial");
+        test_repo.commit_file("a.txt", "a2\n", "Untagged");
+        let second = test_repo.commit_file("a.txt", "a3\n", "Release");
+
+        let signature = test_repo.repo.signature().unwrap();
+        let first_commit = test_repo.repo.find_object(first, None).unwrap();
+        let second_commit = test_repo.repo.find_object(second, None).unwrap();
+        test_repo
+            .repo
+            .tag_lightweight("v0.1.0", &first_commit, false)
+            .unwrap();
+        test_repo
+            .repo
+            .tag(
+                "v0.2.0",
+                &second_commit,
+                &signature,
+                "Second release",
+                false,
+            )
+            .unwrap();
+        test_repo
+            .repo
+            .tag_lightweight("latest", &second_commit, false)
+            .unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        assert_eq!(repo.set_tag_timeline().unwrap(), 2);
+
+        let metadata = repo.next_range_commit_asc().unwrap();
+        assert_eq!(metadata.hash, first.to_string());
+        assert_eq!(metadata.tags, vec!["v0.1.0"]);
+
+        let metadata = repo.next_range_commit_asc().unwrap();
+        assert_eq!(metadata.hash, second.to_string());
+        assert_eq!(metadata.tags, vec!["latest", "v0.2.0"]);
+
+        assert!(repo.next_range_commit_asc().is_err());
+
+        // Tagged commits go through the same filters as any other commit
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_message_filter(Some(Regex::new("Release").unwrap()));
+        assert_eq!(repo.set_tag_timeline().unwrap(), 1);
+        let metadata = repo.next_range_commit_asc().unwrap();
+        assert_eq!(metadata.hash, second.to_string());
+    }
+
+    #[test]
+    fn test_submodule_pointer_change() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("README.md", "hello\n", "Initial");
+
+        // Point a gitlink entry at a commit, the way `git submodule update` records it
+        let commit_gitlink = |target: &str, message: &str| {
+            let head = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+            let mut builder = test_repo
+                .repo
+                .treebuilder(Some(&head.tree().unwrap()))
+                .unwrap();
+            builder
+                .insert("vendor", Oid::from_str(target).unwrap(), 0o160000)
+                .unwrap();
+            let tree = test_repo.repo.find_tree(builder.write().unwrap()).unwrap();
+            let sig = test_repo.repo.signature().unwrap();
+            test_repo
+                .repo
+                .commit(Some("HEAD"), &sig, &sig, message, &tree, &[&head])
+                .unwrap();
+        };
+        let old = "1111111111111111111111111111111111111111";
+        let new = "2222222222222222222222222222222222222222";
+        commit_gitlink(old, "Add submodule");
+        commit_gitlink(new, "Bump submodule");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit("HEAD").unwrap();
+        assert_eq!(metadata.changes.len(), 1);
+        let change = &metadata.changes[0];
+        assert_eq!(change.path, "vendor");
+        assert_eq!(change.status, FileStatus::Submodule);
+        assert_eq!(change.line_counts(), (1, 0));
+        let summary = "Submodule vendor: 1111111 → 2222222\n";
+        assert_eq!(change.hunks[0].lines[0].content, summary);
+        assert_eq!(change.old_content, None);
+        assert_eq!(change.new_content.as_deref(), Some(summary));
+        assert_eq!(
+            change.submodule,
+            Some(SubmoduleChange {
+                old_commit: Some(old.to_string()),
+                new_commit: Some(new.to_string()),
+            })
+        );
+
+        let added = repo.get_commit("HEAD~1").unwrap();
+        assert_eq!(
+            added.changes[0].new_content.as_deref(),
+            Some("Submodule vendor: (none) → 1111111\n")
+        );
+        assert_eq!(
+            added.changes[0].submodule,
+            Some(SubmoduleChange {
+                old_commit: None,
+                new_commit: Some(old.to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sql_is_excluded_only_past_the_line_limit() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file(
+            "migrations/001_users.sql",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);\n",
+            "Add users table",
+        );
+        let dump: String = (0..=DEFAULT_MAX_CHANGE_LINES)
+            .map(|n| format!("INSERT INTO users VALUES ({});\n", n))
+            .collect();
+        test_repo.commit_file("backup/dump.sql", &dump, "Add dump");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let migration = repo.get_commit("HEAD~1").unwrap();
+        assert!(!migration.changes[0].is_excluded);
+
+        let dump = repo.get_commit("HEAD").unwrap();
+        assert!(dump.changes[0].is_excluded);
+        assert_eq!(
+            dump.changes[0].exclusion_reason.as_deref(),
+            Some("too many changes (2001 lines)")
+        );
+    }
+
+    #[test]
+    fn test_gitattributes_exclude_binary_and_generated_files() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file(
+            ".gitattributes",
+            "*.dat binary\nschema.txt -diff\ngen/** linguist-generated=true\ngen/keep.rs linguist-generated=false\n",
+            "Add attributes",
+        );
+        test_repo.commit_files(
+            &[
+                ("data.dat", "plain text\n"),
+                ("schema.txt", "id int\n"),
+                ("gen/api.rs", "pub fn api() {}\n"),
+                ("gen/keep.rs", "pub fn keep() {}\n"),
+                ("src/main.rs", "fn main() {}\n"),
+            ],
+            "Add files",
+        );
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit("HEAD").unwrap();
+        let reasons: Vec<(&str, Option<&str>)> = metadata
+            .changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.exclusion_reason.as_deref()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("data.dat", Some("binary in .gitattributes")),
+                ("gen/api.rs", Some("generated in .gitattributes")),
+                ("gen/keep.rs", None),
+                ("schema.txt", Some("binary in .gitattributes")),
+                ("src/main.rs", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_numbers_across_multiple_hunks() {
+        let test_repo = TestRepo::new();
+        let original: Vec<String> = (1..=30).map(|n| format!("line {}", n)).collect();
+        test_repo.commit_file("notes.txt", &(original.join("\n") + "\n"), "Initial");
+
+        // Modify line 3, delete line 15 and insert after line 27: three separate hunks
+        let mut updated = original.clone();
+        updated[2] = "line three".to_string();
+        updated.insert(27, "inserted".to_string());
+        updated.remove(14);
+        test_repo.commit_file("notes.txt", &(updated.join("\n") + "\n"), "Edit");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit("HEAD").unwrap();
+        let hunks = &metadata.changes[0].hunks;
+        assert_eq!(hunks.len(), 3);
+
+        let numbers = |hunk: &DiffHunk| -> Vec<(LineChangeType, Option<usize>, Option<usize>)> {
+            hunk.lines
+                .iter()
+                .map(|l| (l.change_type.clone(), l.old_line_no, l.new_line_no))
+                .collect()
+        };
+        use LineChangeType::{Addition as Add, Context as Ctx, Deletion as Del};
+
+        assert_eq!(
+            numbers(&hunks[0]),
+            vec![
+                (Ctx, Some(1), Some(1)),
+                (Ctx, Some(2), Some(2)),
+                (Del, Some(3), None),
+                (Add, None, Some(3)),
+                (Ctx, Some(4), Some(4)),
+                (Ctx, Some(5), Some(5)),
+                (Ctx, Some(6), Some(6)),
+            ]
+        );
+        assert_eq!(
+            numbers(&hunks[1]),
+            vec![
+                (Ctx, Some(12), Some(12)),
+                (Ctx, Some(13), Some(13)),
+                (Ctx, Some(14), Some(14)),
+                (Del, Some(15), None),
+                (Ctx, Some(16), Some(15)),
+                (Ctx, Some(17), Some(16)),
+                (Ctx, Some(18), Some(17)),
+            ]
+        );
+        assert_eq!(
+            numbers(&hunks[2]),
+            vec![
+                (Ctx, Some(25), Some(24)),
+                (Ctx, Some(26), Some(25)),
+                (Ctx, Some(27), Some(26)),
+                (Add, None, Some(27)),
+                (Ctx, Some(28), Some(28)),
+                (Ctx, Some(29), Some(29)),
+                (Ctx, Some(30), Some(30)),
+            ]
+        );
     }
 
     #[test]
@@ -1376,6 +3965,51 @@ mod tests {
         assert_eq!(unstaged.changes[0].path, "file2.txt");
     }
 
+    #[test]
+    fn test_working_tree_diff_all_uncommitted_changes() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("staged.txt", "committed\n", "Initial commit");
+        test_repo.commit_file("edited.txt", "committed\n", "Second commit");
+
+        std::fs::write(test_repo.path.join("staged.txt"), "staged\n").unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(test_repo.path.join("edited.txt"), "edited\n").unwrap();
+        std::fs::create_dir(test_repo.path.join("notes")).unwrap();
+        std::fs::write(test_repo.path.join("notes/todo.md"), "- ship it\n").unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_working_tree_diff(DiffMode::WorkingTree).unwrap();
+        assert_eq!(metadata.hash, "working-tree");
+        assert_eq!(metadata.message, "Uncommitted changes");
+
+        let change = |path: &str| {
+            metadata
+                .changes
+                .iter()
+                .find(|change| change.path == path)
+                .unwrap_or_else(|| panic!("missing change for {}", path))
+        };
+        assert_eq!(metadata.changes.len(), 3);
+        assert_eq!(
+            change("staged.txt").old_content.as_deref(),
+            Some("committed\n")
+        );
+        assert_eq!(
+            change("staged.txt").new_content.as_deref(),
+            Some("staged\n")
+        );
+        assert_eq!(
+            change("edited.txt").new_content.as_deref(),
+            Some("edited\n")
+        );
+        let untracked = change("notes/todo.md");
+        assert_eq!(untracked.status, FileStatus::Added);
+        assert_eq!(untracked.new_content.as_deref(), Some("- ship it\n"));
+        assert_eq!(untracked.hunks.len(), 1);
+    }
+
     #[test]
     fn test_working_tree_diff_new_file() {
         let test_repo = TestRepo::new();
@@ -1,3 +1,33 @@
+// The gitlogue binary is built on these modules. They aren't part of the library's API,
+// which is re-exported below, so they're left out of its documentation.
+#[doc(hidden)]
+pub mod animation;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod export;
+#[doc(hidden)]
+pub mod filter;
+#[doc(hidden)]
 pub mod git;
+#[doc(hidden)]
+pub mod panes;
+#[doc(hidden)]
+pub mod preview;
+#[doc(hidden)]
 pub mod syntax;
+#[doc(hidden)]
 pub mod theme;
+#[doc(hidden)]
+pub mod ui;
+
+mod player;
+mod resume;
+mod session;
+mod widgets;
+
+pub use git::{CommitMetadata, DiffHunk, FileChange, FileStatus, LineChange, LineChangeType};
+pub use player::Player;
+pub use session::{Order, Session};
+pub use syntax::Highlighter;
+pub use theme::Theme;
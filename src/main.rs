@@ -1,28 +1,28 @@
-mod animation;
-mod config;
-mod git;
-mod panes;
-mod syntax;
-mod theme;
-mod ui;
-mod widgets;
-
-use animation::SpeedRule;
+use gitlogue::{animation, config, export, filter, git, panes, preview, syntax, theme, ui};
+
+use animation::{FileFocus, SpeedRule};
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use config::Config;
-use git::{DiffMode, GitRepository};
+use filter::CommitFilter;
+use git::{CommitSort, DiffMode, FileOrder, FileTreeSort, GitRepository};
+use panes::DateDisplay;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use theme::Theme;
-use ui::UI;
+use theme::{ColorDepth, SyntaxTheme, Theme};
+use ui::{EndBehavior, PlaybackOrder, UI};
+
+// Typing speed of `theme preview`, quick enough that the sample finishes in a few seconds
+const PREVIEW_SPEED_MS: u64 = 15;
 
-/// Defines the order in which commits are played back during animation.
-#[derive(Debug, Clone, Copy, Default, ValueEnum)]
-pub enum PlaybackOrder {
+/// Defines how consecutive commits relate to each other during playback.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum PlaybackMode {
+    /// Each commit is shown on its own
     #[default]
-    Random,
-    Asc,
-    Desc,
+    Commits,
+    /// Commits build on each other in history order, showing the codebase grow
+    Growth,
 }
 
 #[derive(Parser, Debug)]
@@ -41,14 +41,54 @@ pub struct Args {
     )]
     pub path: Option<PathBuf>,
 
+    #[arg(
+        long,
+        value_name = "PATHS",
+        value_delimiter = ',',
+        conflicts_with_all = ["path", "commit"],
+        help = "Play commits from several Git repositories in turn (comma-separated paths)"
+    )]
+    pub repos: Vec<PathBuf>,
+
     #[arg(
         short,
         long,
-        value_name = "HASH_OR_RANGE",
-        help = "Replay a specific commit or commit range (e.g., HEAD~5..HEAD or abc123..)"
+        value_name = "REV_OR_RANGE",
+        help = "Replay a specific commit (any revision, e.g. abc123, HEAD~3 or v1.0) or commit range (e.g., HEAD~5..HEAD or abc123..)"
     )]
     pub commit: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "A..B",
+        conflicts_with_all = ["commit", "tags"],
+        value_parser = parse_range,
+        help = "Replay a commit range (e.g., HEAD~20..HEAD), honoring --order and --loop"
+    )]
+    pub range: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["commit", "repos"],
+        help = "Play only tagged commits, oldest release first"
+    )]
+    pub tags: bool,
+
+    #[arg(
+        long = "ref",
+        value_name = "NAME",
+        conflicts_with_all = ["tags", "working_tree"],
+        help = "Walk history from a branch, tag or commit instead of HEAD"
+    )]
+    pub start_ref: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["commit", "repos", "tags"],
+        help = "Play your uncommitted changes, including untracked files, as one commit"
+    )]
+    pub working_tree: bool,
+
     #[arg(
         short,
         long,
@@ -57,6 +97,16 @@ pub struct Args {
     )]
     pub speed: Option<u64>,
 
+    #[arg(
+        long,
+        visible_alias = "speed-cps",
+        value_name = "CPS",
+        conflicts_with = "speed",
+        value_parser = parse_cps,
+        help = "Typing speed in characters per second, an alternative to --speed (overrides config file)"
+    )]
+    pub cps: Option<f64>,
+
     #[arg(
         short,
         long,
@@ -65,6 +115,29 @@ pub struct Args {
     )]
     pub theme: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "theme",
+        help = "Load the theme from a TOML theme file (overrides --theme and config file)"
+    )]
+    pub theme_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Color code with the syntax colors of another theme, keeping the UI colors (overrides config file)"
+    )]
+    pub syntax_theme: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "syntax_theme",
+        help = "Load the syntax colors from the [syntax] table of a TOML file (overrides --syntax-theme and config file)"
+    )]
+    pub syntax_theme_file: Option<PathBuf>,
+
     #[arg(
         long,
         num_args = 0..=1,
@@ -74,6 +147,23 @@ pub struct Args {
     )]
     pub background: Option<bool>,
 
+    #[arg(
+        long,
+        global = true,
+        value_name = "MODE",
+        value_parser = ["auto", "truecolor", "256", "16"],
+        help = "Colors the terminal supports: auto detects them from COLORTERM and TERM, 256 and 16 map theme colors to the nearest palette entry (overrides config file)"
+    )]
+    pub color: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "STYLE",
+        value_parser = ["absolute", "relative", "both"],
+        help = "Show the commit date as a timestamp, as a time ago (e.g. 3 days ago), or both (overrides config file)"
+    )]
+    pub date_display: Option<String>,
+
     #[arg(
         long,
         value_enum,
@@ -82,6 +172,14 @@ pub struct Args {
     )]
     pub order: Option<PlaybackOrder>,
 
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        help = "Playback mode: 'growth' plays history oldest-first as one evolving codebase"
+    )]
+    pub mode: Option<PlaybackMode>,
+
     #[arg(
         long = "loop",
         num_args = 0..=1,
@@ -91,9 +189,262 @@ pub struct Args {
     )]
     pub loop_playback: Option<bool>,
 
+    #[arg(
+        long,
+        conflicts_with = "loop_playback",
+        help = "Play a single commit, picked according to --order, and exit when it finishes"
+    )]
+    pub once: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["loop_playback", "once", "commit", "repos", "tags"],
+        help = "After the last commit, wait for new commits on HEAD and play them as they land"
+    )]
+    pub watch: bool,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Continue asc/desc playback where the previous run stopped (overrides config file)"
+    )]
+    pub resume: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Number of recently played commits random playback won't pick again, 0 to allow repeats (overrides config file)"
+    )]
+    pub random_history: Option<usize>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Also play merge commits, shown as their diff against the first parent (overrides config file)"
+    )]
+    pub include_merges: Option<bool>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Leave root commits, which add the whole initial tree, out of random playback (overrides config file)"
+    )]
+    pub skip_root_commits: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        help = "Similarity (0-100) at which a removed and an added file show as a rename, 0 to turn detection off (overrides config file)"
+    )]
+    pub rename_threshold: Option<u8>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Show only changed lines in the editor, skipping unchanged file content (overrides config file)"
+    )]
+    pub changes_only: Option<bool>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Play each file backwards, removing added lines before restoring deleted ones (overrides config file)"
+    )]
+    pub reverse_file: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "CHARS",
+        help = "Clip editor lines longer than CHARS characters with a truncation marker instead of wrapping (overrides config file)"
+    )]
+    pub max_line_length: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Animate at most N files of each commit, leaving the rest out (0 for no limit, overrides config file)"
+    )]
+    pub max_files: Option<usize>,
+
+    #[arg(
+        long,
+        alias = "quiet-binary",
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Leave excluded and binary files out of the file tree and playback entirely (overrides config file)"
+    )]
+    pub hide_excluded: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Animate at most N files of a root commit, such as an initial import (0 for no limit, overrides config file)"
+    )]
+    pub root_max_files: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only consider the N newest commits, so huge repositories start quickly (0 for no limit)"
+    )]
+    pub depth: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "BYTES",
+        help = "Largest file, in bytes, whose content is animated (default: 512000) (overrides config file)"
+    )]
+    pub max_blob_size: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Skip files with more than N changed lines (default: 2000) (overrides config file)"
+    )]
+    pub max_change_lines: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "TERM",
+        help = "Highlight every occurrence of TERM in the editor as it is typed (case-insensitive)"
+    )]
+    pub highlight: Option<String>,
+
+    #[arg(
+        long,
+        requires = "highlight",
+        help = "Match the --highlight term case-sensitively"
+    )]
+    pub highlight_case_sensitive: bool,
+
+    #[arg(
+        long,
+        help = "Render file content as plain text without syntax highlighting (overrides config file)"
+    )]
+    pub no_syntax: bool,
+
+    #[arg(
+        long = "static",
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Show each file's final content at once instead of typing it, like a diff viewer (overrides config file)"
+    )]
+    pub static_view: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "MS",
+        help = "Milliseconds the static view shows each file, 0 to wait for a key (overrides config file)"
+    )]
+    pub dwell: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MS",
+        help = "Milliseconds a finished commit stays on screen before the next one starts; defaults to 100x the typing speed (overrides config file)"
+    )]
+    pub hold_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "FRAMES",
+        help = "Scramble each typed character through FRAMES random glyphs before it resolves, 0 disables (overrides config file)"
+    )]
+    pub scramble_frames: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "FRACTION",
+        value_parser = parse_jitter,
+        help = "Vary each character's typing delay randomly by up to this fraction (0.0-1.0) of the speed, 0 for constant timing (overrides config file)"
+    )]
+    pub jitter: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "commit",
+        help = "Only animate this file of the commit (path relative to the repository root)"
+    )]
+    pub focus_file: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "START-END",
+        requires = "focus_file",
+        value_parser = parse_line_range,
+        help = "Only animate hunks of --focus-file overlapping these lines (e.g. 40-60)"
+    )]
+    pub focus_lines: Option<(usize, usize)>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "focus_file",
+        help = "Replay the history of one file: only commits changing it, animating just that file (path relative to the repository root)"
+    )]
+    pub follow: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Start the TUI even when TERM=dumb or stdout is not a terminal"
+    )]
+    pub force_tui: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Render the replay of one commit to an animated GIF instead of the terminal"
+    )]
+    pub export_gif: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "export_gif",
+        help = "Record the replay of one commit to an asciinema v2 .cast file instead of the terminal"
+    )]
+    pub export_asciicast: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "COLSxROWS",
+        default_value = "120x40",
+        value_parser = parse_export_size,
+        help = "Terminal grid size used when exporting (e.g. 120x40)"
+    )]
+    pub export_size: (u16, u16),
+
+    #[arg(
+        long,
+        conflicts_with_all = ["export_gif", "export_asciicast"],
+        help = "Print the selected commit (or every commit of a range) as JSON lines instead of playing it"
+    )]
+    pub json: bool,
+
     #[arg(long, help = "Display third-party license information")]
     pub license: bool,
 
+    #[arg(
+        long,
+        help = "Print the ABI and version of each bundled tree-sitter grammar"
+    )]
+    pub grammar_versions: bool,
+
     #[arg(
         short = 'a',
         long,
@@ -107,17 +458,33 @@ pub struct Args {
     )]
     pub author: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "REGEX",
+        help = "Only play commits whose message matches this regular expression (use (?i) for case-insensitive)"
+    )]
+    pub grep: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "EXPR",
+        help = "Only play commits matching an expression, e.g. 'author~\"alice\" and files>3 and !merge'"
+    )]
+    pub filter: Option<String>,
+
     #[arg(
         long,
         value_name = "DATE",
-        help = "Show commits before this date (e.g., '2024-01-01', '1 week ago', 'yesterday')"
+        visible_alias = "until",
+        help = "Show commits before this date (e.g., '2024-01-01', '1 week ago', '2.weeks.ago', 'yesterday')"
     )]
     pub before: Option<String>,
 
     #[arg(
         long,
         value_name = "DATE",
-        help = "Show commits after this date (e.g., '2024-01-01', '1 week ago', 'yesterday')"
+        visible_alias = "since",
+        help = "Show commits after this date (e.g., '2024-01-01', '1 week ago', '30d', 'yesterday')"
     )]
     pub after: Option<String>,
 
@@ -156,11 +523,23 @@ pub enum Commands {
         #[command(subcommand)]
         command: ThemeCommands,
     },
+    /// Config file management commands
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
     /// Show staged working tree changes (use --unstaged for unstaged changes)
     Diff {
         #[arg(long, help = "Show unstaged changes instead of staged")]
         unstaged: bool,
 
+        #[arg(
+            long,
+            conflicts_with = "unstaged",
+            help = "Show all uncommitted changes against HEAD, including untracked files"
+        )]
+        working_tree: bool,
+
         #[arg(
             short,
             long,
@@ -172,6 +551,14 @@ pub enum Commands {
         #[arg(short, long, value_name = "NAME", help = "Theme to use")]
         theme: Option<String>,
 
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with = "theme",
+            help = "Load the theme from a TOML theme file"
+        )]
+        theme_file: Option<PathBuf>,
+
         #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL",
               help = "Show background colors (use --background=false for transparent)")]
         background: Option<bool>,
@@ -199,13 +586,45 @@ pub enum ThemeCommands {
         #[arg(value_name = "NAME", help = "Theme name to set as default")]
         name: String,
     },
+    /// Show the directory custom themes are loaded from
+    Dir,
+    /// Play a short sample commit in a theme, then wait for a key
+    Preview {
+        #[arg(value_name = "NAME", help = "Theme to preview")]
+        name: String,
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL",
+              help = "Show background colors (use --background=false for transparent)")]
+        background: Option<bool>,
+    },
+    /// Write a theme as a TOML theme file, to start a custom theme from
+    Export {
+        #[arg(value_name = "NAME", help = "Theme to export")]
+        name: String,
+        #[arg(
+            value_name = "PATH",
+            help = "File to write (prints to stdout if omitted)"
+        )]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Write a commented config file with every setting at its default
+    Init {
+        #[arg(long, help = "Overwrite an existing config file")]
+        force: bool,
+    },
 }
 
 impl Args {
     /// Validates the command-line arguments and returns the Git repository path.
     pub fn validate(&self) -> Result<PathBuf> {
         let start_path = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+        Self::resolve_repo_path(&start_path)
+    }
 
+    fn resolve_repo_path(start_path: &Path) -> Result<PathBuf> {
         if !start_path.exists() {
             anyhow::bail!("Path does not exist: {}", start_path.display());
         }
@@ -242,8 +661,251 @@ impl Args {
     }
 }
 
+/// Parses an inclusive, 1-indexed line range like `40-60`
+fn parse_line_range(s: &str) -> Result<(usize, usize), String> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or_else(|| format!("expected a line range like 40-60, got '{}'", s))?;
+    let start: usize = start
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid start line '{}'", start))?;
+    let end: usize = end
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid end line '{}'", end))?;
+
+    if start == 0 || end < start {
+        return Err(format!(
+            "invalid line range {}-{}: lines start at 1 and the end must not precede the start",
+            start, end
+        ));
+    }
+    Ok((start, end))
+}
+
+fn date_display(args: &Args, config: &Config) -> DateDisplay {
+    DateDisplay::parse(args.date_display.as_deref().unwrap_or(&config.date_display))
+        .unwrap_or_default()
+}
+
+/// Colors to draw with, from `--color` or the config file, detected from the terminal on `auto`
+fn color_depth(args: &Args, config: &Config) -> ColorDepth {
+    let mode = args.color.as_deref().unwrap_or(&config.color);
+    ColorDepth::parse(mode).unwrap_or_else(ColorDepth::detect)
+}
+
+/// Accepts a revision range for `--range`; a single commit belongs in `--commit`
+fn parse_range(s: &str) -> Result<String, String> {
+    if s.contains("..") {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "'{}' is not a range: expected A..B (use --commit for a single commit)",
+            s
+        ))
+    }
+}
+
+/// Parses a positive characters-per-second rate for `--cps`
+fn parse_cps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(cps) if cps.is_finite() && cps > 0.0 => Ok(cps),
+        _ => Err(format!(
+            "invalid rate '{}': expected a positive number of characters per second",
+            s
+        )),
+    }
+}
+
+/// Parses a fraction between 0 and 1 for `--jitter`
+fn parse_jitter(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(jitter) if (0.0..=1.0).contains(&jitter) => Ok(jitter),
+        _ => Err(format!(
+            "invalid jitter '{}': expected a number between 0.0 and 1.0",
+            s
+        )),
+    }
+}
+
+/// Parses a `COLSxROWS` grid size for `--export-size`
+fn parse_export_size(s: &str) -> Result<(u16, u16), String> {
+    let parsed = s
+        .split_once(['x', 'X'])
+        .and_then(|(cols, rows)| Some((cols.trim().parse().ok()?, rows.trim().parse().ok()?)));
+    match parsed {
+        Some((cols, rows)) if (40..=500).contains(&cols) && (10..=200).contains(&rows) => {
+            Ok((cols, rows))
+        }
+        _ => Err(format!(
+            "invalid size '{}': expected COLSxROWS with 40-500 columns and 10-200 rows",
+            s
+        )),
+    }
+}
+
+/// Converts characters per second to the milliseconds per character used internally,
+/// never going below 1ms
+fn cps_to_speed_ms(cps: f64) -> u64 {
+    ((1000.0 / cps).round() as u64).max(1)
+}
+
+/// Typing speed from the config file, where `speed_cps` takes the place of `speed`
+fn config_speed(config: &Config) -> Result<u64> {
+    match config.speed_cps {
+        Some(cps) if !cps.is_finite() || cps <= 0.0 => anyhow::bail!(
+            "Invalid speed_cps {} in config: expected a positive number of characters per second",
+            cps
+        ),
+        Some(cps) => Ok(cps_to_speed_ms(cps)),
+        None => Ok(config.speed),
+    }
+}
+
+// Process-wide settings for reading diffs, shared by commit playback and the diff subcommand
+fn set_git_options(args: &Args, config: &Config) {
+    git::set_respect_gitignore(config.respect_gitignore);
+    git::set_normalize_line_endings(config.normalize_line_endings);
+    git::set_file_tree_sort(file_tree_sort(config));
+    git::set_max_blob_size(args.max_blob_size.unwrap_or(config.max_blob_size));
+    git::set_max_change_lines(args.max_change_lines.unwrap_or(config.max_change_lines));
+}
+
+// Playback settings shared by commit playback and the diff subcommand
+fn configure_ui(ui: &mut UI, args: &Args, config: &Config, focus: Option<FileFocus>) {
+    ui.set_changes_only(args.changes_only.unwrap_or(config.changes_only));
+    ui.set_reverse_file(args.reverse_file.unwrap_or(config.reverse_file));
+    // A limit of 0 disables clipping, so the config value can be overridden from the CLI
+    ui.set_max_line_length(
+        args.max_line_length
+            .or(config.max_line_length)
+            .filter(|&max| max > 0),
+    );
+    ui.set_scramble_frames(args.scramble_frames.unwrap_or(config.scramble_frames));
+    ui.set_jitter(args.jitter.unwrap_or(config.jitter));
+    ui.set_tiny_file_lines(config.tiny_file_lines);
+    ui.set_blank_line_pause_ms(config.blank_line_pause_ms);
+    ui.set_stat_preroll_ms(config.stat_preroll_ms);
+    ui.set_excluded_card_ms(config.excluded_card_ms);
+    ui.set_terminal_title(config.set_terminal_title);
+    ui.set_dim_typed_lines(config.dim_typed_lines);
+    ui.set_show_whitespace(config.show_whitespace);
+    ui.set_match_brackets(config.match_brackets);
+    ui.set_tab_width(config.tab_width);
+    ui.set_show_minimap(config.show_minimap);
+    ui.set_show_line_numbers(config.show_line_numbers);
+    ui.set_show_sidebar(config.show_sidebar);
+    ui.set_syntax_highlighting(!args.no_syntax && config.syntax_highlighting);
+    ui.set_highlight_cache_size(config.highlight_cache_size);
+    ui.set_wrap_file_jumps(config.wrap_file_jumps);
+    ui.set_hold_ms(args.hold_ms.or(config.hold));
+    ui.set_date_display(date_display(args, config));
+    ui.set_static_view(args.static_view.unwrap_or(config.static_view));
+    ui.set_static_dwell_ms(args.dwell.unwrap_or(config.static_dwell_ms));
+    ui.set_highlight(args.highlight.as_deref(), args.highlight_case_sensitive);
+    ui.set_focus(focus);
+}
+
+// How the file tree sorts files, from the config file
+fn file_tree_sort(config: &Config) -> FileTreeSort {
+    match config.file_tree_sort.as_str() {
+        "magnitude" => FileTreeSort::Magnitude,
+        "status" => FileTreeSort::Status,
+        _ => FileTreeSort::Path,
+    }
+}
+
+// The syntax colors picked with --syntax-theme-file, --syntax-theme or the config file, if any
+fn load_syntax_theme(args: &Args, config: &Config) -> Result<Option<SyntaxTheme>> {
+    if let Some(path) = &args.syntax_theme_file {
+        return SyntaxTheme::load_from_file(path).map(Some);
+    }
+    args.syntax_theme
+        .as_ref()
+        .or(config.syntax_theme.as_ref())
+        .map(|name| SyntaxTheme::load(name))
+        .transpose()
+}
+
+/// Refuses to start the TUI where it would only print escape sequences
+fn ensure_capable_terminal(force_tui: bool) -> Result<()> {
+    if force_tui {
+        return Ok(());
+    }
+
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        anyhow::bail!(
+            "gitlogue needs a terminal with cursor and color support, but TERM is set to 'dumb'. \
+             Run it in a capable terminal, or pass --force-tui to start anyway"
+        );
+    }
+    if !std::io::stdout().is_terminal() {
+        anyhow::bail!(
+            "gitlogue needs to write to a terminal, but stdout is redirected. \
+             Run it without piping its output, or pass --force-tui to start anyway"
+        );
+    }
+    Ok(())
+}
+
+/// Writes commits to stdout as JSON, one object per line. A range or the tag timeline
+/// prints all of its commits in order, anything else just the selected commit.
+fn print_json(
+    repo: &GitRepository,
+    first: git::CommitMetadata,
+    is_range_mode: bool,
+    order: PlaybackOrder,
+) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    let mut metadata = first;
+    loop {
+        serde_json::to_writer(&mut out, &metadata)?;
+        writeln!(out)?;
+
+        let has_more = is_range_mode
+            && repo
+                .playback_position()
+                .is_some_and(|(played, total)| played < total);
+        if !has_more {
+            return Ok(());
+        }
+        metadata = match order {
+            PlaybackOrder::Desc => repo.next_range_commit_desc()?,
+            _ => repo.next_range_commit_asc()?,
+        };
+    }
+}
+
+/// Short name shown in the status bar when playing several repositories
+fn repo_display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+
+    // --range is the explicit spelling of --commit with a range
+    if let Some(range) = args.range.take() {
+        args.commit = Some(range);
+    }
+
+    // --working-tree is shorthand for `diff --working-tree` with the top-level options
+    if args.working_tree && args.command.is_none() {
+        args.command = Some(Commands::Diff {
+            unstaged: false,
+            working_tree: true,
+            speed: args.speed.or(args.cps.map(cps_to_speed_ms)),
+            theme: args.theme.clone(),
+            theme_file: args.theme_file.clone(),
+            background: args.background,
+            loop_playback: args.loop_playback,
+            ignore: args.ignore.clone(),
+            speed_rule: args.speed_rule.clone(),
+        });
+    }
 
     // Handle --license flag
     if args.license {
@@ -251,15 +913,49 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.grammar_versions {
+        println!("{:<12} {:>3}  VERSION", "LANGUAGE", "ABI");
+        for (name, language) in syntax::languages::all_languages() {
+            // Grammars generated by older tree-sitter CLIs don't embed version metadata
+            let version = language
+                .metadata()
+                .map(|m| {
+                    format!(
+                        "{}.{}.{}",
+                        m.major_version, m.minor_version, m.patch_version
+                    )
+                })
+                .unwrap_or_else(|| "-".to_string());
+            println!("{:<12} {:>3}  {}", name, language.abi_version(), version);
+        }
+        return Ok(());
+    }
+
     // Handle subcommands
     if let Some(ref command) = args.command {
         match command {
             Commands::Theme { command } => match command {
                 ThemeCommands::List => {
                     println!("Available themes:");
-                    for theme in Theme::available_themes() {
+                    for theme in Theme::builtin_themes() {
                         println!("  - {}", theme);
                     }
+
+                    let user_themes = Theme::user_themes();
+                    if !user_themes.is_empty() {
+                        println!();
+                        println!("Custom themes:");
+                        for user_theme in user_themes {
+                            match user_theme.theme {
+                                Err(e) => println!("  - {} (error: {:#})", user_theme.name, e),
+                                Ok(_) if Theme::is_builtin(&user_theme.name) => println!(
+                                    "  - {} (ignored: shadowed by built-in theme)",
+                                    user_theme.name
+                                ),
+                                Ok(_) => println!("  - {}", user_theme.name),
+                            }
+                        }
+                    }
                     return Ok(());
                 }
                 ThemeCommands::Set { name } => {
@@ -275,25 +971,90 @@ fn main() -> Result<()> {
                     println!("Theme set to '{}' in {}", name, config_path.display());
                     return Ok(());
                 }
+                ThemeCommands::Dir => {
+                    let themes_dir = Config::themes_dir()?;
+                    println!("{}", themes_dir.display());
+                    return Ok(());
+                }
+                ThemeCommands::Preview { name, background } => {
+                    let mut theme = Theme::load(name)?;
+                    let config = Config::load().unwrap_or_default();
+                    if !background.or(args.background).unwrap_or(config.background) {
+                        theme = theme.with_transparent_background();
+                    }
+                    ensure_capable_terminal(args.force_tui)?;
+
+                    let mut ui = UI::new(
+                        PREVIEW_SPEED_MS,
+                        None,
+                        theme,
+                        PlaybackOrder::Asc,
+                        false,
+                        None,
+                        false,
+                        Vec::new(),
+                    );
+                    ui.set_on_end(EndBehavior::Hold);
+                    ui.set_color_depth(color_depth(&args, &config));
+                    ui.load_commit(preview::sample_commit());
+                    ui.run()?;
+                    return Ok(());
+                }
+                ThemeCommands::Export { name, path } => {
+                    let toml = Theme::load(name)?.to_toml()?;
+                    match path {
+                        Some(path) => {
+                            std::fs::write(path, toml).with_context(|| {
+                                format!("Failed to write theme file: {}", path.display())
+                            })?;
+                            eprintln!("Exported theme '{}' to {}", name, path.display());
+                        }
+                        None => print!("{}", toml),
+                    }
+                    return Ok(());
+                }
+            },
+            Commands::Config { command } => match command {
+                ConfigCommands::Init { force } => {
+                    let config_path = Config::write_default(*force)?;
+                    println!("Wrote default config to {}", config_path.display());
+                    return Ok(());
+                }
             },
             Commands::Diff {
                 unstaged,
+                working_tree,
                 speed,
                 theme,
+                theme_file,
                 background,
                 loop_playback,
                 ignore,
                 speed_rule,
             } => {
+                ensure_capable_terminal(args.force_tui)?;
                 let repo_path = args.validate()?;
-                let repo = GitRepository::open(&repo_path)?;
+                let mut repo = GitRepository::open(&repo_path)?;
 
-                let mode = if *unstaged {
+                let mode = if *working_tree {
+                    DiffMode::WorkingTree
+                } else if *unstaged {
                     DiffMode::Unstaged
                 } else {
                     DiffMode::Staged
                 };
 
+                let config = Config::load_for_repo(&repo_path)?;
+
+                // Exclusions are decided while extracting the diff, so set them up first
+                let mut patterns = config.ignore_patterns.clone();
+                patterns.extend(ignore.clone());
+                git::init_ignore_patterns(&patterns).ok();
+                set_git_options(&args, &config);
+                repo.set_rename_threshold(args.rename_threshold.unwrap_or(config.rename_threshold));
+                repo.set_max_files(args.max_files.or(config.max_files).filter(|&max| max > 0));
+                repo.set_hide_excluded(args.hide_excluded.unwrap_or(config.hide_excluded));
+
                 let metadata = repo.get_working_tree_diff(mode)?;
 
                 if metadata.changes.is_empty() {
@@ -301,18 +1062,21 @@ fn main() -> Result<()> {
                     return Ok(());
                 }
 
-                let config = Config::load()?;
-
-                let mut patterns = config.ignore_patterns.clone();
-                patterns.extend(ignore.clone());
-                git::init_ignore_patterns(&patterns).ok();
-
                 let theme_name = theme.as_deref().unwrap_or(&config.theme);
-                let speed = speed.unwrap_or(config.speed);
+                let speed = match speed {
+                    Some(speed) => *speed,
+                    None => config_speed(&config)?,
+                };
                 let background = background.unwrap_or(config.background);
                 let loop_playback = loop_playback.unwrap_or(false);
 
-                let mut theme = Theme::load(theme_name)?;
+                let mut theme = match theme_file {
+                    Some(path) => Theme::load_from_file(path)?,
+                    None => Theme::load(theme_name)?,
+                };
+                if let Some(syntax) = load_syntax_theme(&args, &config)? {
+                    theme = theme.with_syntax(syntax);
+                }
                 if !background {
                     theme = theme.with_transparent_background();
                 }
@@ -341,6 +1105,12 @@ fn main() -> Result<()> {
                     speed_rules,
                 );
                 ui.set_diff_mode(Some(mode));
+                let focus = args.focus_file.clone().map(|path| FileFocus {
+                    path,
+                    lines: args.focus_lines,
+                });
+                configure_ui(&mut ui, &args, &config, focus);
+                ui.set_color_depth(color_depth(&args, &config));
                 ui.load_commit(metadata);
                 ui.run()?;
 
@@ -349,34 +1119,165 @@ fn main() -> Result<()> {
         }
     }
 
-    let repo_path = args.validate()?;
-    let mut repo = GitRepository::open(&repo_path)?;
-
-    // Set author filter if specified
-    if args.author.is_some() {
-        repo.set_author_filter(args.author.clone());
+    let is_exporting = args.export_gif.is_some() || args.export_asciicast.is_some();
+    if !is_exporting && !args.json {
+        ensure_capable_terminal(args.force_tui)?;
     }
 
-    // Set date filters if specified
-    if let Some(ref before_str) = args.before {
-        let before_date = git::parse_date(before_str)?;
-        repo.set_before_filter(Some(before_date));
+    let mut repo_paths = if args.repos.is_empty() {
+        vec![args.validate()?]
+    } else {
+        args.repos
+            .iter()
+            .map(|path| Args::resolve_repo_path(path))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    // Load config: CLI arguments > .gitlogue.toml > config file > defaults.
+    // With several repositories, the first one's .gitlogue.toml applies.
+    let config = Config::load_for_repo(&repo_paths[0])?;
+
+    let file_order = match config.file_order.as_str() {
+        "recency" => FileOrder::Recency,
+        _ => FileOrder::Path,
+    };
+
+    // Author filter: CLI argument > config file
+    let author = args.author.clone().or_else(|| {
+        config
+            .author
+            .clone()
+            .filter(|pattern| !pattern.trim().is_empty())
+    });
+
+    let include_merges = args.include_merges.unwrap_or(config.include_merges);
+    let resume = args.resume.unwrap_or(config.resume);
+    let random_history = args.random_history.unwrap_or(config.random_history);
+    let rename_threshold = args.rename_threshold.unwrap_or(config.rename_threshold);
+    // Like max_line_length, 0 lifts a limit set in the config file
+    let max_files = args.max_files.or(config.max_files).filter(|&max| max > 0);
+    let root_max_files = args
+        .root_max_files
+        .or(config.root_max_files)
+        .filter(|&max| max > 0);
+    let skip_root_commits = args.skip_root_commits.unwrap_or(config.skip_root_commits);
+    let hide_excluded = args.hide_excluded.unwrap_or(config.hide_excluded);
+    let commit_sort = match config.sort.as_str() {
+        "date" => CommitSort::Date,
+        _ => CommitSort::Topo,
+    };
+
+    let message_filter = args
+        .grep
+        .as_deref()
+        .map(|pattern| {
+            regex::Regex::new(pattern)
+                .with_context(|| format!("Invalid --grep pattern '{}'", pattern))
+        })
+        .transpose()?;
+    let commit_filter = args
+        .filter
+        .as_deref()
+        .map(|source| {
+            let filter = CommitFilter::parse(source)
+                .with_context(|| format!("Invalid --filter expression '{}'", source))?;
+            // Merge commits are skipped before the filter runs
+            if filter.uses_merge() && !include_merges {
+                anyhow::bail!("--filter can only match 'merge' with --include-merges");
+            }
+            Ok(filter)
+        })
+        .transpose()?;
+
+    let follow = args
+        .follow
+        .as_deref()
+        .map(|path| path.trim_start_matches("./").to_string());
+
+    let mut repos = Vec::with_capacity(repo_paths.len());
+    let mut loaded_paths = Vec::with_capacity(repo_paths.len());
+    for repo_path in &repo_paths {
+        let mut repo = GitRepository::open(repo_path)?;
+
+        // Set author filter if specified
+        if author.is_some() {
+            repo.set_author_filter(author.clone());
+        }
+
+        // Set date filters if specified
+        if let Some(ref before_str) = args.before {
+            let before_date = git::parse_date(before_str)?;
+            repo.set_before_filter(Some(before_date));
+        }
+        if let Some(ref after_str) = args.after {
+            let after_date = git::parse_date(after_str)?;
+            repo.set_after_filter(Some(after_date));
+        }
+
+        repo.set_message_filter(message_filter.clone());
+        repo.set_commit_filter(commit_filter.clone());
+        repo.set_follow_path(follow.clone());
+        repo.set_commit_sort(commit_sort);
+        repo.set_include_merges(include_merges);
+        repo.set_rename_threshold(rename_threshold);
+        repo.set_max_files(max_files);
+        repo.set_root_max_files(root_max_files);
+        repo.set_hide_excluded(hide_excluded);
+        repo.set_skip_root_commits(skip_root_commits);
+        repo.set_depth(args.depth.filter(|&depth| depth > 0));
+        repo.set_resume(resume);
+        repo.set_random_history(random_history);
+        repo.set_file_order(file_order);
+        // With several repositories, those that don't have the ref are left out
+        if let Err(err) = repo.set_start_ref(args.start_ref.clone()) {
+            if repo_paths.len() == 1 {
+                return Err(err);
+            }
+            eprintln!(
+                "Warning: {} has no ref '{}', skipping",
+                repo_path.display(),
+                args.start_ref.as_deref().unwrap_or_default()
+            );
+            continue;
+        }
+
+        loaded_paths.push(repo_path.clone());
+        repos.push(repo);
+    }
+    if let (true, Some(name)) = (repos.is_empty(), &args.start_ref) {
+        anyhow::bail!("Ref '{}' not found in any of the repositories", name);
     }
-    if let Some(ref after_str) = args.after {
-        let after_date = git::parse_date(after_str)?;
-        repo.set_after_filter(Some(after_date));
+    repo_paths = loaded_paths;
+    // Repositories without commits yet are left out, unless there's nothing else to play
+    if repos.len() > 1 && repos.iter().any(|repo| !repo.is_empty()) {
+        (repo_paths, repos) = repo_paths
+            .into_iter()
+            .zip(repos)
+            .filter(|(path, repo)| {
+                if repo.is_empty() {
+                    eprintln!("Warning: {} has no commits yet, skipping", path.display());
+                }
+                !repo.is_empty()
+            })
+            .unzip();
     }
+    let repo = &repos[0];
+    let is_multi_repo = repos.len() > 1;
 
     let is_commit_specified = args.commit.is_some();
-    let is_range_mode = args
-        .commit
-        .as_ref()
-        .map(|c| c.contains(".."))
-        .unwrap_or(false);
-    let is_filtered = args.author.is_some() || args.before.is_some() || args.after.is_some();
-
-    // Load config: CLI arguments > config file > defaults
-    let config = Config::load()?;
+    // The tag timeline plays a curated list of commits, just like a range
+    let is_range_mode = args.tags
+        || args
+            .commit
+            .as_ref()
+            .map(|c| c.contains(".."))
+            .unwrap_or(false);
+    let is_filtered = author.is_some()
+        || args.grep.is_some()
+        || args.filter.is_some()
+        || follow.is_some()
+        || args.before.is_some()
+        || args.after.is_some();
 
     // Initialize ignore patterns: CLI flags > ignore-file > config
     let mut patterns = config.ignore_patterns.clone();
@@ -392,8 +1293,12 @@ fn main() -> Result<()> {
     }
     patterns.extend(args.ignore.clone());
     git::init_ignore_patterns(&patterns).ok();
+    set_git_options(&args, &config);
     let theme_name = args.theme.as_deref().unwrap_or(&config.theme);
-    let speed = args.speed.unwrap_or(config.speed);
+    let speed = match args.speed.or(args.cps.map(cps_to_speed_ms)) {
+        Some(speed) => speed,
+        None => config_speed(&config)?,
+    };
     let background = args.background.unwrap_or(config.background);
     let mut order = args.order.unwrap_or(match config.order.as_str() {
         "asc" => PlaybackOrder::Asc,
@@ -406,16 +1311,93 @@ fn main() -> Result<()> {
         order = PlaybackOrder::Asc;
     }
 
-    let loop_playback = args.loop_playback.unwrap_or(config.loop_playback);
-    let mut theme = Theme::load(theme_name)?;
+    // JSON output is meant to be repeatable, so random picks start from the newest commit
+    if args.json && matches!(order, PlaybackOrder::Random) {
+        order = PlaybackOrder::Desc;
+    }
+
+    // Growth mode replays history forward, carrying the codebase from one commit to the next
+    let is_growth_mode = args.mode == Some(PlaybackMode::Growth);
+    if is_growth_mode {
+        if matches!(
+            args.order,
+            Some(PlaybackOrder::Random | PlaybackOrder::Desc)
+        ) {
+            anyhow::bail!("--mode growth plays commits oldest-first and only supports --order asc");
+        }
+        if is_multi_repo {
+            anyhow::bail!("--mode growth cannot be combined with --repos");
+        }
+        if args.tags {
+            anyhow::bail!("--mode growth cannot be combined with --tags");
+        }
+        if is_commit_specified && !is_range_mode {
+            anyhow::bail!(
+                "--mode growth needs a history to replay; use a range like --commit HEAD~20..HEAD"
+            );
+        }
+        order = PlaybackOrder::Asc;
+    }
+
+    // Watching follows history forward from the oldest commit as new ones land
+    if args.watch {
+        if matches!(
+            args.order,
+            Some(PlaybackOrder::Random | PlaybackOrder::Desc)
+        ) {
+            anyhow::bail!("--watch plays commits oldest-first and only supports --order asc");
+        }
+        order = PlaybackOrder::Asc;
+    }
+
+    // --once overrides looping and holding set in the config file, and --watch never ends
+    let loop_playback =
+        !args.once && !args.watch && args.loop_playback.unwrap_or(config.loop_playback);
+    let on_end = match config.on_end.as_str() {
+        _ if args.once => EndBehavior::Exit,
+        "loop" => EndBehavior::Loop,
+        "hold" => EndBehavior::Hold,
+        _ => EndBehavior::Exit,
+    };
+    let static_view = args.static_view.unwrap_or(config.static_view);
+    let static_dwell_ms = args.dwell.unwrap_or(config.static_dwell_ms);
+    if static_view && static_dwell_ms == 0 && is_exporting {
+        anyhow::bail!(
+            "A static view that waits for keys can't be exported; set a dwell time with --dwell"
+        );
+    }
+    let mut theme = match &args.theme_file {
+        Some(path) => Theme::load_from_file(path)?,
+        None => Theme::load(theme_name)?,
+    };
+    if let Some(syntax) = load_syntax_theme(&args, &config)? {
+        theme = theme.with_syntax(syntax);
+    }
 
     // Apply transparent background if requested
     if !background {
         theme = theme.with_transparent_background();
     }
 
+    // A fresh `git init` has nothing to play, so say so instead of failing on the unborn HEAD
+    if !args.tags && !is_commit_specified && repo.is_empty() {
+        if args.json || is_exporting {
+            anyhow::bail!("This repository has no commits yet");
+        }
+        ui::show_notice(
+            &theme,
+            "This repository has no commits yet",
+            "Make a first commit, then run gitlogue again.",
+        )?;
+        return Ok(());
+    }
+
     // Setup commit range if specified
-    if is_range_mode {
+    if args.tags {
+        if repo.set_tag_timeline()? == 0 {
+            anyhow::bail!("No tagged commits found to play");
+        }
+    } else if is_range_mode {
         repo.set_commit_range(args.commit.as_ref().unwrap())?;
     }
 
@@ -436,6 +1418,53 @@ fn main() -> Result<()> {
         }
     };
 
+    // Fail early if the focused file or lines aren't part of the requested commit
+    if let (Some(focus_path), false) = (&args.focus_file, is_range_mode) {
+        let change = metadata
+            .changes
+            .iter()
+            .find(|change| &change.path == focus_path)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "File '{}' is not changed in commit {}",
+                    focus_path,
+                    &metadata.hash[..7]
+                )
+            })?;
+
+        if let Some((start, end)) = args.focus_lines {
+            if change.focus_lines(start, end).is_none() {
+                let changed_ranges: Vec<String> = change
+                    .hunks
+                    .iter()
+                    .map(|hunk| {
+                        format!(
+                            "{}-{}",
+                            hunk.new_start,
+                            hunk.new_start + hunk.new_lines.max(1) - 1
+                        )
+                    })
+                    .collect();
+                anyhow::bail!(
+                    "No changes in '{}' overlap lines {}-{} (changed lines: {})",
+                    focus_path,
+                    start,
+                    end,
+                    changed_ranges.join(", ")
+                );
+            }
+        }
+    }
+    if args.json {
+        return print_json(repo, metadata, is_range_mode, order);
+    }
+
+    // Following a file animates just that file of each commit
+    let focus = args.focus_file.clone().or(follow).map(|path| FileFocus {
+        path,
+        lines: args.focus_lines,
+    });
+
     // Parse speed rules: CLI args take priority, then config file
     let speed_rules: Vec<SpeedRule> = args
         .speed_rule
@@ -449,14 +1478,21 @@ fn main() -> Result<()> {
         })
         .collect();
 
-    // Create UI with repository reference
-    // Filtered modes (range/author/date) always need repo ref for iteration
-    let repo_ref = if is_range_mode || is_filtered {
-        Some(&repo)
-    } else if is_commit_specified && !loop_playback {
+    // A single candidate commit has nothing to advance to, so play it once unless looping
+    let is_single_commit =
+        !is_multi_repo && !is_range_mode && !is_commit_specified && repo.commit_count()? == 1;
+    let play_once =
+        is_single_commit && !loop_playback && !args.watch && on_end == EndBehavior::Exit;
+
+    // Create UI with repository reference. A single --commit is fetched again from the
+    // repository each time it ends, so it only gets one when it should loop, whatever
+    // filters are set. Ranges and filtered history always need it for iteration.
+    let loops_single_commit = loop_playback || on_end == EndBehavior::Loop;
+    let single_commit_ends = is_commit_specified && !is_range_mode && !loops_single_commit;
+    let repo_ref = if play_once || args.once || single_commit_ends {
         None
     } else {
-        Some(&repo)
+        Some(repo)
     };
     let mut ui = UI::new(
         speed,
@@ -468,8 +1504,124 @@ fn main() -> Result<()> {
         is_range_mode,
         speed_rules,
     );
+    configure_ui(&mut ui, &args, &config, focus);
+    if is_growth_mode {
+        ui.set_growth_codebase(repo.parent_tree_paths(&metadata.hash)?);
+    }
+    // --once plays the first commit only, without moving on to the other repositories
+    if is_multi_repo && !args.once {
+        ui.set_repositories(
+            repo_paths
+                .iter()
+                .zip(&repos)
+                .map(|(path, repo)| (repo_display_name(path), repo))
+                .collect(),
+        );
+    }
     ui.load_commit(metadata);
+
+    let (cols, rows) = args.export_size;
+    if let Some(path) = &args.export_gif {
+        export::export_gif(&mut ui, path, cols, rows)?;
+        println!("Saved replay to {}", path.display());
+        return Ok(());
+    }
+    if let Some(path) = &args.export_asciicast {
+        export::export_asciicast(&mut ui, path, cols, rows)?;
+        println!("Saved replay to {}", path.display());
+        return Ok(());
+    }
+
+    ui.set_on_end(on_end);
+    ui.set_watch(args.watch);
+    ui.set_color_depth(color_depth(&args, &config));
     ui.run()?;
 
+    // --once asked for a single commit, so there is nothing to hint at
+    if args.once {
+        return Ok(());
+    }
+    if play_once {
+        println!("Only one commit available to play. Use --loop to replay it continuously.");
+    } else if ui.history_ended() && on_end == EndBehavior::Exit {
+        println!(
+            "Reached the end of history. Use --loop, or set on_end = \"loop\", to start over."
+        );
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cps_to_speed_ms() {
+        assert_eq!(cps_to_speed_ms(1.0), 1000);
+        assert_eq!(cps_to_speed_ms(33.0), 30);
+        assert_eq!(cps_to_speed_ms(12.5), 80);
+        // Rates faster than one character per millisecond are capped
+        assert_eq!(cps_to_speed_ms(5000.0), 1);
+    }
+
+    #[test]
+    fn test_parse_cps_rejects_non_positive_rates() {
+        assert_eq!(parse_cps("40"), Ok(40.0));
+        assert!(parse_cps("0").is_err());
+        assert!(parse_cps("-5").is_err());
+        assert!(parse_cps("fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_jitter_accepts_fractions_only() {
+        assert_eq!(parse_jitter("0"), Ok(0.0));
+        assert_eq!(parse_jitter("0.45"), Ok(0.45));
+        assert_eq!(parse_jitter("1"), Ok(1.0));
+        assert!(parse_jitter("1.5").is_err());
+        assert!(parse_jitter("-0.1").is_err());
+    }
+
+    #[test]
+    fn test_cps_conflicts_with_speed() {
+        let result = Args::try_parse_from(["gitlogue", "--speed", "30", "--cps", "40"]);
+        assert!(result.is_err());
+
+        let args = Args::try_parse_from(["gitlogue", "--cps", "40"]).unwrap();
+        assert_eq!(args.cps, Some(40.0));
+
+        let result = Args::try_parse_from(["gitlogue", "--speed", "30", "--speed-cps", "40"]);
+        assert!(result.is_err());
+        let args = Args::try_parse_from(["gitlogue", "--speed-cps", "40"]).unwrap();
+        assert_eq!(args.cps, Some(40.0));
+    }
+
+    #[test]
+    fn test_range_requires_a_range() {
+        let args = Args::try_parse_from(["gitlogue", "--range", "HEAD~5..HEAD"]).unwrap();
+        assert_eq!(args.range.as_deref(), Some("HEAD~5..HEAD"));
+
+        assert!(Args::try_parse_from(["gitlogue", "--range", "HEAD~5"]).is_err());
+        let both = ["gitlogue", "--range", "a..b", "--commit", "c"];
+        assert!(Args::try_parse_from(both).is_err());
+    }
+
+    #[test]
+    fn test_watch_rejects_multiple_repositories() {
+        assert!(Args::try_parse_from(["gitlogue", "--watch"]).is_ok());
+        let both = ["gitlogue", "--watch", "--repos", "a,b"];
+        assert!(Args::try_parse_from(both).is_err());
+    }
+
+    #[test]
+    fn test_config_speed_cps_replaces_speed() {
+        let mut config = Config::default();
+        assert_eq!(config_speed(&config).unwrap(), config.speed);
+
+        config.speed_cps = Some(50.0);
+        assert_eq!(config_speed(&config).unwrap(), 20);
+
+        config.speed_cps = Some(0.0);
+        assert!(config_speed(&config).is_err());
+    }
+}
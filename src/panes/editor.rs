@@ -1,16 +1,47 @@
+use std::cell::RefCell;
+use std::ops::Range;
+
+use unicode_width::UnicodeWidthChar;
+
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Padding},
+    widgets::{Block, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 
-use crate::animation::{ActivePane, AnimationEngine};
+use crate::animation::{tab_stop_width, ActivePane, AnimationEngine, LineMark};
 use crate::theme::Theme;
 use crate::widgets::SelectableParagraph;
 
-pub struct EditorPane;
+// Opacity of lines above the cursor while dim_typed_lines is on
+const TYPED_LINE_OPACITY: f32 = 0.6;
+
+// Columns taken by the minimap, and the editor width below which it is left out
+const MINIMAP_WIDTH: u16 = 2;
+const MINIMAP_MIN_EDITOR_WIDTH: u16 = 40;
+
+// Lines searched above and below the cursor for the brackets enclosing it
+const BRACKET_SCAN_LINES: usize = 500;
+
+pub struct EditorPane {
+    max_line_length: Option<usize>,
+    dim_typed_lines: bool,
+    show_line_numbers: bool,
+    show_whitespace: bool,
+    show_minimap: bool,
+    match_brackets: bool,
+    /// Last bracket pair found, with the cursor line, column, line count and cursor line
+    /// text it was found for, so frames where the cursor doesn't move skip the scan
+    bracket_cache: RefCell<Option<(BracketKey, Option<BracketPair>)>>,
+    /// Characters of the `--highlight` term, already folded when matching ignores case
+    highlight_term: Vec<char>,
+    highlight_case_sensitive: bool,
+}
+
+type BracketPair = [(usize, usize); 2];
+type BracketKey = (usize, usize, usize, String);
 
 struct HighlightContext<'a> {
     line_content: &'a str,
@@ -23,11 +54,85 @@ struct HighlightContext<'a> {
     old_line_offsets: &'a [usize],
     new_line_offsets: &'a [usize],
     line_offset: isize,
+    /// Character ranges of words changed within this line
+    intraline: &'a [Range<usize>],
+    /// Whether to mark tabs and trailing whitespace, set for added lines only
+    show_whitespace: bool,
+    /// Columns between tab stops; tabs are drawn as spaces up to the next one
+    tab_width: usize,
     theme: &'a Theme,
 }
 
 impl EditorPane {
+    pub fn new() -> Self {
+        Self {
+            max_line_length: None,
+            dim_typed_lines: false,
+            show_line_numbers: true,
+            show_whitespace: false,
+            show_minimap: false,
+            match_brackets: false,
+            bracket_cache: RefCell::new(None),
+            highlight_term: Vec::new(),
+            highlight_case_sensitive: false,
+        }
+    }
+
+    /// Clips lines longer than `max_line_length` characters and marks them with `›`
+    /// instead of wrapping them. `None` keeps the default wrapping behavior.
+    pub fn set_max_line_length(&mut self, max_line_length: Option<usize>) {
+        self.max_line_length = max_line_length;
+    }
+
+    /// Dims lines above the cursor while a file is being typed, spotlighting the active line.
+    pub fn set_dim_typed_lines(&mut self, dim_typed_lines: bool) {
+        self.dim_typed_lines = dim_typed_lines;
+    }
+
+    /// Shows or hides the line number gutter.
+    pub fn set_show_line_numbers(&mut self, show_line_numbers: bool) {
+        self.show_line_numbers = show_line_numbers;
+    }
+
+    /// Marks trailing whitespace and shows tabs as `→` on added lines.
+    pub fn set_show_whitespace(&mut self, show_whitespace: bool) {
+        self.show_whitespace = show_whitespace;
+    }
+
+    /// Shows an overview of the whole file at the right edge, in place of the scrollbar.
+    pub fn set_show_minimap(&mut self, show_minimap: bool) {
+        self.show_minimap = show_minimap;
+    }
+
+    /// Tints the pair of brackets enclosing the cursor.
+    pub fn set_match_brackets(&mut self, match_brackets: bool) {
+        self.match_brackets = match_brackets;
+    }
+
+    pub fn toggle_line_numbers(&mut self) {
+        self.show_line_numbers = !self.show_line_numbers;
+    }
+
+    /// Gives every occurrence of `term` in the editor the theme's search match background.
+    pub fn set_highlight(&mut self, term: Option<&str>, case_sensitive: bool) {
+        self.highlight_case_sensitive = case_sensitive;
+        self.highlight_term = term
+            .unwrap_or("")
+            .chars()
+            .map(|ch| fold_case(ch, case_sensitive))
+            .collect();
+    }
+
     pub fn render(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
+        let (area, minimap_area) = if self.show_minimap && area.width >= MINIMAP_MIN_EDITOR_WIDTH {
+            let columns =
+                Layout::horizontal([Constraint::Min(0), Constraint::Length(MINIMAP_WIDTH)])
+                    .split(area);
+            (columns[0], Some(columns[1]))
+        } else {
+            (area, None)
+        };
+
         let block = Block::default()
             .style(Style::default().bg(theme.background_right))
             .padding(Padding::vertical(1));
@@ -35,7 +140,23 @@ impl EditorPane {
         let content_height = area.height.saturating_sub(2) as usize; // Subtract top and bottom padding
         let scroll_offset = engine.buffer.scroll_offset;
         let buffer_lines = &engine.buffer.lines;
-        let line_num_width = format!("{}", buffer_lines.len()).len().max(3);
+        let max_line_number = engine
+            .buffer
+            .line_numbers
+            .as_ref()
+            .and_then(|numbers| numbers.iter().max().copied())
+            .unwrap_or(0)
+            .max(buffer_lines.len());
+        let line_num_width = format!("{}", max_line_number).len().max(3);
+        let brackets = if self.match_brackets {
+            self.cached_enclosing_brackets(
+                buffer_lines,
+                engine.buffer.cursor_line,
+                engine.buffer.cursor_col,
+            )
+        } else {
+            None
+        };
 
         let visible_lines: Vec<Line> = buffer_lines
             .iter()
@@ -44,7 +165,15 @@ impl EditorPane {
             .enumerate()
             .map(|(idx, line_content)| {
                 let line_num = scroll_offset + idx;
-                self.build_line(line_content, line_num, line_num_width, engine, theme)
+                let brackets = brackets.as_ref().map_or(&[][..], |pair| &pair[..]);
+                self.build_line(
+                    line_content,
+                    line_num,
+                    line_num_width,
+                    brackets,
+                    engine,
+                    theme,
+                )
             })
             .collect();
 
@@ -60,14 +189,89 @@ impl EditorPane {
             None
         };
 
-        let content = SelectableParagraph::new(visible_lines)
+        let mut content = SelectableParagraph::new(visible_lines)
             .block(block)
             .selected_line(selected_line_index)
             .selected_style(Style::default().bg(theme.editor_cursor_line_bg))
             .background_style(Style::default().bg(theme.background_right))
             .padding(Padding::horizontal(2))
             .dim(20, 0.6);
+        // Back to full brightness once the file has no edits left
+        if self.dim_typed_lines && engine.is_typing() {
+            content = content.dim_above(TYPED_LINE_OPACITY);
+        }
         f.render_widget(content, area);
+
+        if let Some(minimap_area) = minimap_area {
+            self.render_minimap(f, minimap_area, engine, theme);
+            return;
+        }
+
+        // Drawn in the right padding, only when the file doesn't fit
+        if buffer_lines.len() > content_height {
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_symbol(None)
+                .thumb_symbol("▐")
+                .thumb_style(Style::default().fg(theme.scrollbar));
+            // One position per possible offset, so the thumb reaches the bottom at the end
+            let mut state = ScrollbarState::new(buffer_lines.len() - content_height + 1)
+                .position(scroll_offset)
+                .viewport_content_length(content_height);
+            f.render_stateful_widget(
+                scrollbar,
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut state,
+            );
+        }
+    }
+
+    /// Draws the file compressed to one row per group of lines, colored by the kind of
+    /// change in the group, with the lines currently on screen highlighted
+    fn render_minimap(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
+        let buffer = &engine.buffer;
+        let rows = area.height.saturating_sub(2) as usize;
+        let total = buffer.lines.len();
+        let lines_per_row = total.div_ceil(rows.max(1)).max(1);
+        let visible = buffer.scroll_offset..buffer.scroll_offset + rows;
+
+        let minimap_lines: Vec<Line> = (0..total.div_ceil(lines_per_row))
+            .map(|row| {
+                let group = row * lines_per_row..((row + 1) * lines_per_row).min(total);
+                let marks: Vec<LineMark> =
+                    group.clone().map(|line| buffer.line_mark(line)).collect();
+                let color = if marks.contains(&LineMark::Added) {
+                    theme.file_tree_added
+                } else if marks.contains(&LineMark::Deleted) {
+                    theme.file_tree_deleted
+                } else {
+                    theme.editor_line_number
+                };
+                let has_text = group
+                    .clone()
+                    .any(|line| !buffer.lines[line].trim().is_empty());
+                let symbol = if has_text || color != theme.editor_line_number {
+                    "▬".repeat(MINIMAP_WIDTH as usize)
+                } else {
+                    " ".repeat(MINIMAP_WIDTH as usize)
+                };
+
+                let mut style = Style::default().fg(color);
+                if group.start < visible.end && visible.start < group.end {
+                    style = style.bg(theme.editor_cursor_line_bg);
+                }
+                Line::from(Span::styled(symbol, style))
+            })
+            .collect();
+
+        let minimap = Paragraph::new(minimap_lines)
+            .style(Style::default().bg(theme.background_right))
+            .block(Block::default().padding(Padding::vertical(1)));
+        f.render_widget(minimap, area);
     }
 
     fn build_line(
@@ -75,25 +279,46 @@ impl EditorPane {
         line_content: &str,
         line_num: usize,
         line_num_width: usize,
+        brackets: &[(usize, usize)],
         engine: &AnimationEngine,
         theme: &Theme,
     ) -> Line<'_> {
         let cursor_line = engine.buffer.cursor_line;
         let is_cursor_line = line_num == cursor_line;
 
+        // Never clip the part of the line the cursor is currently typing into
+        let truncate_at = self.max_line_length.filter(|&max| {
+            line_content.chars().count() > max
+                && !(is_cursor_line && engine.buffer.cursor_col >= max)
+        });
+        let line_content = match truncate_at {
+            Some(max) => line_content
+                .char_indices()
+                .nth(max)
+                .map_or(line_content, |(idx, _)| &line_content[..idx]),
+            None => line_content,
+        };
+
         let mut spans = Vec::new();
 
-        spans.push(self.render_line_number(line_num, is_cursor_line, line_num_width, theme));
+        if self.show_line_numbers {
+            spans.push(self.render_line_number(
+                engine.buffer.display_line_number(line_num),
+                is_cursor_line,
+                line_num_width,
+                theme,
+            ));
 
-        spans.push(Span::styled(
-            "  ",
-            Style::default().fg(theme.editor_separator),
-        ));
+            spans.push(Span::styled(
+                "  ",
+                Style::default().fg(theme.editor_separator),
+            ));
+        }
 
         let show_cursor =
             is_cursor_line && engine.cursor_visible && engine.active_pane == ActivePane::Editor;
 
-        let line_spans = self.highlight_line(HighlightContext {
+        let mut line_spans = self.highlight_line(HighlightContext {
             line_content,
             line_num,
             show_cursor,
@@ -104,22 +329,76 @@ impl EditorPane {
             old_line_offsets: &engine.buffer.old_content_line_offsets,
             new_line_offsets: &engine.buffer.new_content_line_offsets,
             line_offset: engine.line_offset,
+            intraline: engine
+                .buffer
+                .intraline
+                .get(line_num)
+                .map_or(&[], Vec::as_slice),
+            show_whitespace: self.show_whitespace
+                && engine.buffer.line_mark(line_num) == LineMark::Added,
+            tab_width: engine.tab_width(),
             theme,
         });
 
+        // Overlay pass: highlight_line emits one span per character, so the match
+        // mask lines up with the spans. The cursor keeps its own background.
+        let matches = self.highlight_matches(line_content);
+        for (span, _) in line_spans.iter_mut().zip(matches).filter(|(_, hit)| *hit) {
+            if span.style.bg.is_none() {
+                span.style = span.style.bg(theme.editor_search_match_bg);
+            }
+        }
+        for &(_, col) in brackets.iter().filter(|(line, _)| *line == line_num) {
+            if let Some(span) = line_spans
+                .get_mut(col)
+                .filter(|span| span.style.bg.is_none())
+            {
+                span.style = span.style.bg(theme.editor_bracket_match);
+            }
+        }
+
         spans.extend(line_spans);
 
+        if truncate_at.is_some() {
+            spans.push(Span::styled(
+                "›",
+                Style::default().fg(theme.editor_truncation_marker),
+            ));
+        }
+
         Line::from(spans)
     }
 
+    /// Marks the characters of `line` covered by an occurrence of the highlight term
+    fn highlight_matches(&self, line: &str) -> Vec<bool> {
+        let term = &self.highlight_term;
+        if term.is_empty() {
+            return Vec::new();
+        }
+
+        let chars: Vec<char> = line
+            .chars()
+            .map(|ch| fold_case(ch, self.highlight_case_sensitive))
+            .collect();
+        let mut matches = vec![false; chars.len()];
+        if chars.len() >= term.len() {
+            for start in 0..=chars.len() - term.len() {
+                if chars[start..start + term.len()] == term[..] {
+                    matches[start..start + term.len()].fill(true);
+                }
+            }
+        }
+        matches
+    }
+
     fn render_line_number(
         &self,
-        line_num: usize,
+        display_num: usize,
         is_cursor_line: bool,
         width: usize,
         theme: &Theme,
     ) -> Span<'_> {
-        let line_num_str = format!("{:>width$} ", line_num + 1, width = width);
+        let line_num_str = format!("{:>width$} ", display_num, width = width);
 
         if is_cursor_line {
             Span::styled(
@@ -219,27 +498,62 @@ impl EditorPane {
         let chars: Vec<char> = ctx.line_content.chars().collect();
         let mut spans = Vec::new();
 
+        // Whitespace at the end of the line being typed may still be followed by more text
+        let trailing_start = if ctx.show_whitespace && ctx.line_num != ctx.cursor_line {
+            chars.len()
+                - chars
+                    .iter()
+                    .rev()
+                    .take_while(|ch| ch.is_whitespace())
+                    .count()
+        } else {
+            chars.len()
+        };
+
+        // Highlights are matched on the original bytes; tabs are only expanded in the
+        // span text, so a tab still gets one span and the offsets never drift
         let mut relative_byte = 0;
+        let mut col = 0;
         for (char_idx, ch) in chars.iter().enumerate() {
             let char_byte_start = byte_offset + relative_byte;
             let char_byte_end = char_byte_start + ch.len_utf8();
             relative_byte += ch.len_utf8();
 
+            let text = if *ch == '\t' {
+                let width = tab_stop_width(col, ctx.tab_width);
+                col += width;
+                let marker = if ctx.show_whitespace { "→" } else { " " };
+                format!("{}{}", marker, " ".repeat(width - 1))
+            } else {
+                col += ch.width().unwrap_or(0);
+                ch.to_string()
+            };
+
             let color =
                 self.get_char_color(char_byte_start, char_byte_end, line_highlights, ctx.theme);
 
             if ctx.show_cursor && char_idx == ctx.cursor_col {
                 // Cursor character - bright highlight
                 spans.push(Span::styled(
-                    ch.to_string(),
+                    text,
                     Style::default()
                         .bg(ctx.theme.editor_cursor_char_bg)
                         .fg(ctx.theme.editor_cursor_char_fg)
                         .add_modifier(Modifier::BOLD),
                 ));
             } else {
-                // Normal character
-                spans.push(Span::styled(ch.to_string(), Style::default().fg(color)));
+                // Normal character, with a background where a word changed within the line
+                let mut style = Style::default().fg(color);
+                if ctx.intraline.iter().any(|range| range.contains(&char_idx)) {
+                    style = style.bg(ctx.theme.editor_intraline_change);
+                }
+                if char_idx >= trailing_start {
+                    style = style.bg(ctx.theme.editor_trailing_whitespace);
+                }
+                if ctx.show_whitespace && *ch == '\t' {
+                    style = style.fg(ctx.theme.editor_line_number);
+                }
+                spans.push(Span::styled(text, style));
             }
         }
 
@@ -267,6 +581,355 @@ impl EditorPane {
             .iter()
             .find(|h| char_byte_start >= h.0 && char_byte_end <= h.1)
             .map(|h| h.2.color(theme))
-            .unwrap_or(theme.syntax_variable) // Use theme color instead of Color::White
+            .unwrap_or(theme.syntax.variable) // Use theme color instead of Color::White
+    }
+}
+
+impl EditorPane {
+    /// [`enclosing_brackets`], scanning again only once the cursor or its line changes
+    fn cached_enclosing_brackets(
+        &self,
+        lines: &[String],
+        cursor_line: usize,
+        cursor_col: usize,
+    ) -> Option<BracketPair> {
+        let line_text = lines.get(cursor_line).map_or("", String::as_str);
+        let mut cache = self.bracket_cache.borrow_mut();
+        if let Some(((line, col, count, text), pair)) = cache.as_ref() {
+            if (*line, *col, *count) == (cursor_line, cursor_col, lines.len()) && text == line_text
+            {
+                return *pair;
+            }
+        }
+
+        let pair = enclosing_brackets(lines, cursor_line, cursor_col);
+        let key = (cursor_line, cursor_col, lines.len(), line_text.to_string());
+        *cache = Some((key, pair));
+        pair
+    }
+}
+
+impl Default for EditorPane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Finds the innermost `()`, `[]` or `{}` pair around the cursor, as (line, char column)
+/// positions of the opening and closing bracket. Only `BRACKET_SCAN_LINES` lines on either
+/// side are searched, and mismatched brackets end the search.
+fn enclosing_brackets(
+    lines: &[String],
+    cursor_line: usize,
+    cursor_col: usize,
+) -> Option<BracketPair> {
+    let closing_for = |ch: char| match ch {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    };
+    let is_closing = |ch: char| matches!(ch, ')' | ']' | '}');
+    let chars = |line: usize| lines.get(line).map(|text| text.chars().collect::<Vec<_>>());
+
+    // Walk back to the first opening bracket whose pair isn't closed before the cursor
+    let mut pending = Vec::new();
+    let mut open = None;
+    'back: for line in (cursor_line.saturating_sub(BRACKET_SCAN_LINES)..=cursor_line).rev() {
+        let Some(text) = chars(line) else { continue };
+        let end = if line == cursor_line {
+            cursor_col.min(text.len())
+        } else {
+            text.len()
+        };
+        for col in (0..end).rev() {
+            let ch = text[col];
+            if is_closing(ch) {
+                pending.push(ch);
+            } else if let Some(closing) = closing_for(ch) {
+                match pending.pop() {
+                    None => {
+                        open = Some((line, col, closing));
+                        break 'back;
+                    }
+                    Some(expected) if expected == closing => {}
+                    Some(_) => return None,
+                }
+            }
+        }
+    }
+    let (open_line, open_col, closing) = open?;
+
+    // Then forward to the bracket closing it
+    let mut pending = Vec::new();
+    for line in cursor_line..=cursor_line + BRACKET_SCAN_LINES {
+        let Some(text) = chars(line) else { break };
+        let start = if line == cursor_line { cursor_col } else { 0 };
+        for (col, &ch) in text.iter().enumerate().skip(start) {
+            if let Some(inner) = closing_for(ch) {
+                pending.push(inner);
+            } else if is_closing(ch) {
+                match pending.pop() {
+                    None if ch == closing => return Some([(open_line, open_col), (line, col)]),
+                    Some(expected) if expected == ch => {}
+                    _ => return None,
+                }
+            }
+        }
+    }
+    None
+}
+
+fn fold_case(ch: char, case_sensitive: bool) -> char {
+    if case_sensitive {
+        ch
+    } else {
+        ch.to_lowercase().next().unwrap_or(ch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::{Highlighter, TokenType};
+
+    #[test]
+    fn test_highlights_land_on_multibyte_glyphs() {
+        let source = "let a = \"é\";\nlet s = \"日本語\"; // ✓ ok\n";
+        let mut highlighter = Highlighter::new();
+        assert!(highlighter.set_language_from_path("main.rs"));
+        let highlights = highlighter.highlight(source);
+        let line_offsets: Vec<usize> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        let theme = Theme::default();
+
+        // The second line starts after multibyte content on the first line
+        let line_content = source.lines().nth(1).unwrap();
+        let pane = EditorPane::new();
+        let spans = pane.highlight_line(HighlightContext {
+            line_content,
+            line_num: 1,
+            show_cursor: false,
+            cursor_col: 0,
+            cursor_line: 1,
+            old_highlights: &highlights,
+            new_highlights: &highlights,
+            old_line_offsets: &line_offsets,
+            new_line_offsets: &line_offsets,
+            line_offset: 0,
+            intraline: &[],
+            show_whitespace: false,
+            tab_width: 4,
+            theme: &theme,
+        });
+
+        // One span per character, each colored by the token it belongs to
+        let color_of = |glyph: &str| {
+            spans
+                .iter()
+                .find(|span| span.content == glyph)
+                .and_then(|span| span.style.fg)
+                .unwrap()
+        };
+        assert_eq!(spans.len(), line_content.chars().count());
+        assert_eq!(color_of("日"), TokenType::String.color(&theme));
+        assert_eq!(color_of("語"), TokenType::String.color(&theme));
+        assert_eq!(color_of("✓"), TokenType::Comment.color(&theme));
+        assert_eq!(color_of("k"), TokenType::Comment.color(&theme));
+        assert_eq!(color_of(";"), TokenType::Punctuation.color(&theme));
+    }
+
+    #[test]
+    fn test_line_number_gutter_can_be_hidden() {
+        let mut engine = AnimationEngine::new(10);
+        engine.buffer = crate::animation::EditorBuffer::from_content("first\nsecond\n");
+        let theme = Theme::default();
+        let text = |line: Line| -> String {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+
+        let mut pane = EditorPane::new();
+        assert_eq!(
+            text(pane.build_line("second", 1, 3, &[], &engine, &theme)),
+            "  2   second"
+        );
+
+        pane.toggle_line_numbers();
+        assert_eq!(
+            text(pane.build_line("second", 1, 3, &[], &engine, &theme)),
+            "second"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_is_marked_on_added_lines_only() {
+        let mut engine = AnimationEngine::new(10);
+        engine.buffer = crate::animation::EditorBuffer::from_content("kept  \n");
+        engine.buffer.insert_line(1, "\tadded  ".to_string(), None);
+        engine.buffer.cursor_line = 2;
+        let theme = Theme::default();
+        let mut pane = EditorPane::new();
+        pane.toggle_line_numbers();
+        let marked = |pane: &EditorPane, line: &str, line_num: usize| -> String {
+            pane.build_line(line, line_num, 3, &[], &engine, &theme)
+                .spans
+                .iter()
+                .map(|span| match span.style.bg {
+                    Some(bg) if bg == theme.editor_trailing_whitespace => "_",
+                    _ => span.content.as_ref(),
+                })
+                .collect()
+        };
+
+        assert_eq!(marked(&pane, "\tadded  ", 1), "    added  ");
+
+        pane.set_show_whitespace(true);
+        assert_eq!(marked(&pane, "kept  ", 0), "kept  ");
+        assert_eq!(marked(&pane, "\tadded  ", 1), "→   added__");
+    }
+
+    #[test]
+    fn test_tabs_expand_to_tab_stops() {
+        let mut engine = AnimationEngine::new(10);
+        engine.buffer = crate::animation::EditorBuffer::from_content("\tx\ty\n");
+        let theme = Theme::default();
+        let mut pane = EditorPane::new();
+        pane.toggle_line_numbers();
+        let text = |engine: &AnimationEngine| -> String {
+            pane.build_line("\tx\ty", 0, 3, &[], engine, &theme)
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+
+        assert_eq!(text(&engine), "    x   y");
+        engine.set_tab_width(2);
+        assert_eq!(text(&engine), "  x y");
+    }
+
+    #[test]
+    fn test_minimap_marks_changes_and_viewport() {
+        let content: String = (1..=40).map(|i| format!("line {}\n", i)).collect();
+        let mut engine = AnimationEngine::new(10);
+        engine.buffer = crate::animation::EditorBuffer::from_content(&content);
+        engine.buffer.insert_line(0, "new".to_string(), None);
+        engine.buffer.delete_line(30);
+        let theme = Theme::default();
+        let mut pane = EditorPane::new();
+        pane.set_show_minimap(true);
+        // 12 rows leave 10 minimap rows of 4 lines each
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 12)).unwrap();
+        terminal
+            .draw(|f| pane.render(f, f.area(), &engine, &theme))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let cell = |row: u16| &buffer[(58, row + 1)];
+
+        assert_eq!(cell(0).symbol(), "▬");
+        assert_eq!(cell(0).fg, theme.file_tree_added);
+        assert_eq!(cell(7).fg, theme.file_tree_deleted);
+        assert_eq!(cell(3).fg, theme.editor_line_number);
+        // The first 10 lines are on screen
+        assert_eq!(cell(2).bg, theme.editor_cursor_line_bg);
+        assert_eq!(cell(3).bg, theme.background_right);
+        // No scrollbar next to the minimap
+        assert!((0..12).all(|y| buffer[(57, y)].symbol() != "▐"));
+    }
+
+    #[test]
+    fn test_brackets_around_the_cursor_are_matched() {
+        let lines: Vec<String> = ["fn main() {", "    call(a, [b]);", "}"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        // Inside `call(...)`, past the nested `[b]`
+        assert_eq!(enclosing_brackets(&lines, 1, 15), Some([(1, 8), (1, 15)]));
+        // Between the lines of the function body
+        assert_eq!(enclosing_brackets(&lines, 1, 2), Some([(0, 10), (2, 0)]));
+        // Nothing encloses the start of the file, and a mismatch gives up
+        assert_eq!(enclosing_brackets(&lines, 0, 0), None);
+        let mismatched = vec!["(a]".to_string()];
+        assert_eq!(enclosing_brackets(&mismatched, 0, 2), None);
+
+        // The pair is reused while the cursor stays put, and found again once its line changes
+        let pane = EditorPane::new();
+        assert_eq!(
+            pane.cached_enclosing_brackets(&lines, 1, 15),
+            Some([(1, 8), (1, 15)])
+        );
+        assert!(pane.bracket_cache.borrow().is_some());
+        let mut edited = lines.clone();
+        edited[1] = "    call(a, b);".to_string();
+        assert_eq!(
+            pane.cached_enclosing_brackets(&edited, 1, 15),
+            Some([(0, 10), (2, 0)])
+        );
+
+        let mut engine = AnimationEngine::new(10);
+        engine.buffer = crate::animation::EditorBuffer::from_content("f(x)\n");
+        let theme = Theme::default();
+        let pane = EditorPane::new();
+        let line = pane.build_line("f(x)", 0, 3, &[(0, 1), (0, 3)], &engine, &theme);
+        let tinted: Vec<&str> = line
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(theme.editor_bracket_match))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(tinted, vec!["(", ")"]);
+    }
+
+    #[test]
+    fn test_scrollbar_follows_scroll_offset() {
+        let content: String = (1..=50).map(|i| format!("line {}\n", i)).collect();
+        let mut engine = AnimationEngine::new(10);
+        engine.buffer = crate::animation::EditorBuffer::from_content(&content);
+        let theme = Theme::default();
+        let pane = EditorPane::new();
+        // 12 rows leave 10 for content between the vertical padding
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(30, 12)).unwrap();
+        let mut thumb_rows = |engine: &AnimationEngine| -> Vec<u16> {
+            terminal
+                .draw(|f| pane.render(f, f.area(), engine, &theme))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..12)
+                .filter(|&y| buffer[(29, y)].symbol() == "▐")
+                .collect()
+        };
+
+        assert_eq!(thumb_rows(&engine), vec![1, 2]);
+        engine.buffer.scroll_offset = 40;
+        assert_eq!(thumb_rows(&engine), vec![9, 10]);
+
+        engine.buffer = crate::animation::EditorBuffer::from_content("short\n");
+        assert!(thumb_rows(&engine).is_empty());
+    }
+
+    #[test]
+    fn test_highlight_term_matches_ignore_case_by_default() {
+        let mut editor = EditorPane::new();
+        assert!(editor.highlight_matches("parse_config").is_empty());
+
+        editor.set_highlight(Some("Config"), false);
+        let hits = |editor: &EditorPane, line: &str| -> String {
+            editor
+                .highlight_matches(line)
+                .iter()
+                .map(|&hit| if hit { '^' } else { ' ' })
+                .collect()
+        };
+        assert_eq!(hits(&editor, "load_CONFIG(config)"), "     ^^^^^^ ^^^^^^ ");
+        assert_eq!(hits(&editor, "conf"), "    ");
+
+        editor.set_highlight(Some("Config"), true);
+        assert_eq!(hits(&editor, "Config config"), "^^^^^^       ");
     }
 }
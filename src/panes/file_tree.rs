@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 use ratatui::{
     layout::Rect,
@@ -8,27 +8,64 @@ use ratatui::{
     Frame,
 };
 
-use crate::git::{CommitMetadata, LineChangeType};
+use crate::git::{
+    file_tree_sort, path_matches_filter, split_tree_path, tree_order, CommitMetadata, FileStatus,
+};
 use crate::theme::Theme;
 use crate::widgets::SelectableParagraph;
 
-type FileEntry = (usize, String, String, Color, usize, usize);
-type FileTree = BTreeMap<String, Vec<FileEntry>>;
+/// Change summary shown after each file name
+#[derive(Clone)]
+enum FileStats {
+    Lines {
+        additions: usize,
+        deletions: usize,
+    },
+    Binary {
+        size_delta: Option<i64>,
+    },
+    /// A submodule whose recorded commit changed
+    Submodule,
+    /// Growth mode: a file in the codebase that this commit didn't touch
+    Unchanged,
+}
+
+// File index (usize::MAX for unchanged files), path, status marker, color, stats and sort rank
+type FileEntry = (usize, String, &'static str, Color, FileStats, usize);
 
 pub struct FileTreePane {
     cached_lines: Vec<Line<'static>>,
+    /// File index each cached line shows, None for directories and unchanged files
+    cached_line_files: Vec<Option<usize>>,
     cached_current_line_index: Option<usize>,
     cached_metadata_id: Option<String>,
     cached_current_file_index: Option<usize>,
+    /// Display line scrolled to with the mouse, until the current file changes
+    scroll: Option<usize>,
+    /// Query typed with `/`; only files whose path contains it are listed
+    filter: Option<String>,
 }
 
 impl FileTreePane {
     pub fn new() -> Self {
         Self {
             cached_lines: vec![Line::from("No commit loaded")],
+            cached_line_files: Vec::new(),
             cached_current_line_index: None,
             cached_metadata_id: None,
             cached_current_file_index: None,
+            scroll: None,
+            filter: None,
+        }
+    }
+
+    /// Lists only the files whose path contains `filter` (ignoring case), with the query
+    /// shown above them. None lists every file again.
+    pub fn set_filter(&mut self, filter: Option<&str>) {
+        if self.filter.as_deref() != filter {
+            self.filter = filter.map(String::from);
+            // Rebuild the lines on the next update
+            self.cached_metadata_id = None;
         }
     }
 
@@ -36,6 +73,7 @@ impl FileTreePane {
         &mut self,
         metadata: &CommitMetadata,
         current_file_index: usize,
+        codebase: Option<&BTreeSet<String>>,
         theme: &Theme,
     ) {
         let metadata_id = metadata.hash.clone();
@@ -47,16 +85,45 @@ impl FileTreePane {
             return;
         }
 
-        let (lines, current_line_index) =
-            Self::build_tree_lines(metadata, current_file_index, theme);
+        let (lines, line_files, current_line_index) = Self::build_tree_lines(
+            metadata,
+            current_file_index,
+            codebase,
+            self.filter.as_deref(),
+            theme,
+        );
 
         self.cached_lines = lines;
+        self.cached_line_files = line_files;
         self.cached_current_line_index = current_line_index;
+        self.scroll = None;
         self.cached_metadata_id = Some(metadata_id);
         self.cached_current_file_index = Some(current_file_index);
     }
 
     pub fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        f.render_widget(self.paragraph(theme), area);
+    }
+
+    /// Scrolls the tree by `delta` lines (negative is up) when rendered into `area`
+    pub fn scroll_by(&mut self, area: Rect, delta: isize, theme: &Theme) {
+        let (offset, _) = self.paragraph(theme).layout(area);
+        // Laying out again clamps the offset to the end of the tree
+        let (offset, _) = self
+            .paragraph(theme)
+            .scroll(Some(offset.saturating_add_signed(delta)))
+            .layout(area);
+        self.scroll = Some(offset);
+    }
+
+    /// Index of the file shown at screen row `row` when rendered into `area`
+    pub fn file_at(&self, area: Rect, row: u16, theme: &Theme) -> Option<usize> {
+        let (_, rows) = self.paragraph(theme).layout(area);
+        let line = (*rows.get(row.checked_sub(area.y)? as usize)?)?;
+        self.cached_line_files.get(line).copied().flatten()
+    }
+
+    fn paragraph(&self, theme: &Theme) -> SelectableParagraph<'static> {
         let block = Block::default()
             .style(Style::default().bg(theme.background_left))
             .padding(Padding {
@@ -66,23 +133,26 @@ impl FileTreePane {
                 bottom: 1,
             });
 
-        let content = SelectableParagraph::new(self.cached_lines.clone())
+        SelectableParagraph::new(self.cached_lines.clone())
             .block(block)
             .selected_line(self.cached_current_line_index)
+            .scroll(self.scroll)
             .selected_style(Style::default().bg(theme.file_tree_current_file_bg))
             .background_style(Style::default().bg(theme.background_left))
             .padding(Padding::horizontal(2))
-            .dim(20, 0.6);
-        f.render_widget(content, area);
+            .dim(20, 0.6)
     }
 
     fn build_tree_lines(
         metadata: &CommitMetadata,
         current_file_index: usize,
+        codebase: Option<&BTreeSet<String>>,
+        filter: Option<&str>,
         theme: &Theme,
-    ) -> (Vec<Line<'static>>, Option<usize>) {
-        // Build directory tree
-        let mut tree: FileTree = BTreeMap::new();
+    ) -> (Vec<Line<'static>>, Vec<Option<usize>>, Option<usize>) {
+        // Changed files, plus in growth mode the rest of the codebase around them
+        let mut entries: Vec<FileEntry> = Vec::new();
+        let sort = file_tree_sort();
 
         for (index, change) in metadata.changes.iter().enumerate() {
             let (status_char, color) = match change.status.as_str() {
@@ -90,119 +160,367 @@ impl FileTreePane {
                 "D" => ("-", theme.file_tree_deleted),
                 "M" => ("~", theme.file_tree_modified),
                 "R" => (">", theme.file_tree_renamed),
+                "C" => ("=", theme.file_tree_renamed),
+                "S" => ("@", theme.file_tree_modified),
                 _ => (" ", theme.file_tree_default),
             };
 
-            // Count additions and deletions
-            let mut additions = 0;
-            let mut deletions = 0;
-            for hunk in &change.hunks {
-                for line in &hunk.lines {
-                    match line.change_type {
-                        LineChangeType::Addition => additions += 1,
-                        LineChangeType::Deletion => deletions += 1,
-                        _ => {}
-                    }
+            // Binary files have no hunks, so show their size change instead of line counts
+            let stats = if change.status == FileStatus::Submodule {
+                FileStats::Submodule
+            } else if change.is_binary {
+                FileStats::Binary {
+                    size_delta: change.size_delta(),
                 }
-            }
-
-            let parts: Vec<&str> = change.path.split('/').collect();
-            if parts.len() == 1 {
-                // Root level file
-                tree.entry("".to_string()).or_default().push((
-                    index,
-                    change.path.clone(),
-                    status_char.to_string(),
-                    color,
-                    additions,
-                    deletions,
-                ));
             } else {
-                // File in directory
-                let dir = parts[..parts.len() - 1].join("/");
-                let filename = parts[parts.len() - 1].to_string();
-                tree.entry(dir).or_default().push((
-                    index,
-                    filename,
-                    status_char.to_string(),
-                    color,
+                let (additions, deletions) = change.line_counts();
+                FileStats::Lines {
                     additions,
                     deletions,
+                }
+            };
+
+            entries.push((
+                index,
+                change.path.clone(),
+                status_char,
+                color,
+                stats,
+                sort.rank(change),
+            ));
+        }
+
+        if let Some(codebase) = codebase {
+            let changed: BTreeSet<&str> = metadata
+                .changes
+                .iter()
+                .map(|change| change.path.as_str())
+                .collect();
+            for path in codebase
+                .iter()
+                .filter(|path| !changed.contains(path.as_str()))
+            {
+                entries.push((
+                    usize::MAX,
+                    path.clone(),
+                    " ",
+                    theme.file_tree_default,
+                    FileStats::Unchanged,
+                    sort.unchanged_rank(),
                 ));
             }
         }
 
         let mut lines = Vec::new();
+        let mut line_files = Vec::new();
         let mut current_line_index = None;
-        let sorted_dirs: Vec<_> = tree.keys().cloned().collect();
-
-        for dir in sorted_dirs {
-            let mut files = tree.get(&dir).unwrap().clone();
-            // Sort files by filename within each directory
-            files.sort_by(|a, b| a.1.cmp(&b.1));
-
-            // Add directory header if not root
-            if !dir.is_empty() {
-                let dir_text = format!("{}/", dir);
-                let dir_spans = vec![Span::styled(
-                    dir_text,
+        let mut current_dir = None;
+
+        // The query goes above the files it leaves
+        if let Some(filter) = filter {
+            entries.retain(|entry| path_matches_filter(&entry.1, filter));
+            lines.push(Line::from(Span::styled(
+                format!("/{}", filter),
+                Style::default()
+                    .fg(theme.status_message)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            line_files.push(None);
+            if entries.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No matching files",
                     Style::default()
-                        .fg(theme.file_tree_directory)
-                        .add_modifier(Modifier::BOLD),
-                )];
-                lines.push(Line::from(dir_spans));
+                        .fg(theme.file_tree_default)
+                        .add_modifier(Modifier::ITALIC | Modifier::DIM),
+                )));
+                line_files.push(None);
             }
+        }
 
-            // Add files
-            for (index, filename, status_char, color, additions, deletions) in &files {
-                let is_current = *index == current_file_index;
+        // The same ordering as CommitMetadata::sorted_file_indices, which playback follows
+        let order = tree_order(
+            &entries
+                .iter()
+                .map(|entry| (entry.1.as_str(), entry.5))
+                .collect::<Vec<_>>(),
+        );
+        for (index, path, status_char, color, stats, _) in order.into_iter().map(|i| &entries[i]) {
+            let (dir, filename) = split_tree_path(path);
 
-                // Track the line index of the current file (before adding the line)
-                if is_current {
-                    current_line_index = Some(lines.len());
+            // Add a header when entering a directory, except for the root
+            if current_dir != Some(dir) {
+                current_dir = Some(dir);
+                if !dir.is_empty() {
+                    let dir_text = format!("{}/", dir);
+                    let dir_spans = vec![Span::styled(
+                        dir_text,
+                        Style::default()
+                            .fg(theme.file_tree_directory)
+                            .add_modifier(Modifier::BOLD),
+                    )];
+                    lines.push(Line::from(dir_spans));
+                    line_files.push(None);
                 }
+            }
+
+            let is_current = *index == current_file_index;
+
+            // Track the line index of the current file (before adding the line)
+            if is_current {
+                current_line_index = Some(lines.len());
+            }
+
+            let indent = if dir.is_empty() { "" } else { "  " }.to_string();
+            let status_str = format!("{} ", status_char);
+
+            let fg_color = if is_current {
+                theme.file_tree_current_file_fg
+            } else {
+                theme.file_tree_default
+            };
+
+            let modifier = if is_current {
+                Modifier::BOLD
+            } else if matches!(stats, FileStats::Unchanged) {
+                Modifier::DIM
+            } else {
+                Modifier::empty()
+            };
 
-                let indent = if dir.is_empty() { "" } else { "  " }.to_string();
-                let status_str = format!("{} ", status_char);
-                let additions_str = format!(" +{}", additions);
-                let deletions_str = format!(" -{}", deletions);
-
-                let fg_color = if is_current {
-                    theme.file_tree_current_file_fg
-                } else {
-                    theme.file_tree_default
-                };
-
-                let modifier = if is_current {
-                    Modifier::BOLD
-                } else {
-                    Modifier::empty()
-                };
-
-                let spans = vec![
-                    Span::raw(indent),
-                    Span::styled(
-                        status_str,
-                        Style::default().fg(*color).add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        filename.to_string(),
-                        Style::default().fg(fg_color).add_modifier(modifier),
-                    ),
-                    Span::styled(
-                        additions_str,
-                        Style::default().fg(theme.file_tree_stats_added),
-                    ),
-                    Span::styled(
-                        deletions_str,
-                        Style::default().fg(theme.file_tree_stats_deleted),
-                    ),
-                ];
-
-                lines.push(Line::from(spans));
+            let mut spans = vec![
+                Span::raw(indent),
+                Span::styled(
+                    status_str,
+                    Style::default().fg(*color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    filename.to_string(),
+                    Style::default().fg(fg_color).add_modifier(modifier),
+                ),
+            ];
+            spans.extend(Self::stats_spans(stats, theme));
+
+            lines.push(Line::from(spans));
+            line_files.push((*index != usize::MAX).then_some(*index));
+        }
+
+        if metadata.hidden_files > 0 {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "... and {} more file{} not shown",
+                    metadata.hidden_files,
+                    if metadata.hidden_files == 1 { "" } else { "s" }
+                ),
+                Style::default()
+                    .fg(theme.file_tree_default)
+                    .add_modifier(Modifier::ITALIC | Modifier::DIM),
+            )));
+            line_files.push(None);
+        }
+
+        (lines, line_files, current_line_index)
+    }
+
+    fn stats_spans(stats: &FileStats, theme: &Theme) -> Vec<Span<'static>> {
+        match stats {
+            FileStats::Lines {
+                additions,
+                deletions,
+            } => vec![
+                Span::styled(
+                    format!(" +{}", additions),
+                    Style::default().fg(theme.file_tree_stats_added),
+                ),
+                Span::styled(
+                    format!(" -{}", deletions),
+                    Style::default().fg(theme.file_tree_stats_deleted),
+                ),
+            ],
+            FileStats::Binary { size_delta } => {
+                let mut spans = vec![Span::styled(
+                    " binary",
+                    Style::default()
+                        .fg(theme.file_tree_default)
+                        .add_modifier(Modifier::ITALIC),
+                )];
+                if let Some(delta) = size_delta {
+                    let color = if *delta < 0 {
+                        theme.file_tree_stats_deleted
+                    } else {
+                        theme.file_tree_stats_added
+                    };
+                    spans.push(Span::styled(
+                        format!(" {}", format_size_delta(*delta)),
+                        Style::default().fg(color),
+                    ));
+                }
+                spans
             }
+            FileStats::Submodule => vec![Span::styled(
+                " submodule",
+                Style::default()
+                    .fg(theme.file_tree_default)
+                    .add_modifier(Modifier::ITALIC),
+            )],
+            FileStats::Unchanged => Vec::new(),
         }
+    }
+}
+
+impl Default for FileTreePane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats a byte size change as a signed, human-readable string (e.g. `+1.5KB`)
+fn format_size_delta(delta: i64) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    let bytes = delta.unsigned_abs() as f64;
+    if bytes < 1024.0 {
+        format!("{}{}B", sign, bytes)
+    } else if bytes < 1024.0 * 1024.0 {
+        format!("{}{:.1}KB", sign, bytes / 1024.0)
+    } else {
+        format!("{}{:.1}MB", sign, bytes / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{FileChange, FileStatus};
+
+    fn line_text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_binary_file_shows_tag_and_size_delta() {
+        let binary = FileChange {
+            is_binary: true,
+            old_content: None,
+            new_content: None,
+            old_size: Some(2048),
+            new_size: Some(3584),
+            ..FileChange::test_modified("assets/logo.png")
+        };
+        let removed = FileChange {
+            path: "blob.bin".to_string(),
+            status: FileStatus::Deleted,
+            old_size: Some(512),
+            new_size: None,
+            ..binary.clone()
+        };
+        let metadata = CommitMetadata {
+            message: "Update assets".to_string(),
+            ..CommitMetadata::test_with(vec![binary, removed])
+        };
+
+        let (lines, _, _) =
+            FileTreePane::build_tree_lines(&metadata, 0, None, None, &Theme::default());
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(
+            texts,
+            vec![
+                "- blob.bin binary -512B",
+                "assets/",
+                "  ~ logo.png binary +1.5KB"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_growth_mode_lists_unchanged_codebase_files() {
+        let change = FileChange {
+            status: FileStatus::Added,
+            old_content: None,
+            new_content: Some("fn main() {}\n".to_string()),
+            old_size: None,
+            new_size: Some(13),
+            ..FileChange::test_modified("src/lib.rs")
+        };
+        let metadata = CommitMetadata::test_with(vec![change]);
+        let codebase: BTreeSet<String> = ["README.md", "src/lib.rs", "src/main.rs"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let theme = Theme::default();
+        let (lines, line_files, current) =
+            FileTreePane::build_tree_lines(&metadata, 0, Some(&codebase), None, &theme);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(
+            texts,
+            vec!["  README.md", "src/", "  + lib.rs +0 -0", "    main.rs"]
+        );
+        assert_eq!(current, Some(2));
+        assert_eq!(line_files, vec![None, None, Some(0), None]);
+
+        // Clicks land on the changed file only, below the top padding row
+        let mut pane = FileTreePane::new();
+        pane.set_commit_metadata(&metadata, 0, Some(&codebase), &theme);
+        let area = Rect::new(0, 5, 30, 10);
+        let files: Vec<_> = (5..10).map(|row| pane.file_at(area, row, &theme)).collect();
+        assert_eq!(files, vec![None, None, None, Some(0), None]);
+    }
+
+    #[test]
+    fn test_tree_lists_files_in_playback_order() {
+        // Prefixes, separators that sort before '/', nesting and case all differ between
+        // plain path order and the grouped tree
+        let paths = [
+            "a/b/c.rs",
+            "a/b.rs",
+            "a-b/x.rs",
+            "a.rs",
+            "a/b/c/d.rs",
+            "A.rs",
+            "a/z.rs",
+        ];
+        let metadata =
+            CommitMetadata::test_with(paths.into_iter().map(FileChange::test_modified).collect());
+
+        let (_, line_files, _) =
+            FileTreePane::build_tree_lines(&metadata, 0, None, None, &Theme::default());
+        let listed: Vec<usize> = line_files.into_iter().flatten().collect();
+
+        assert_eq!(listed, metadata.sorted_file_indices());
+        assert_eq!(listed, metadata.playback_file_indices());
+    }
+
+    #[test]
+    fn test_filter_lists_matching_files_under_the_query() {
+        let change = |path: &str| FileChange {
+            status: FileStatus::Added,
+            old_content: None,
+            new_content: Some("x\n".to_string()),
+            old_size: None,
+            ..FileChange::test_modified(path)
+        };
+        let metadata = CommitMetadata::test_with(vec![
+            change("README.md"),
+            change("src/Parser.rs"),
+            change("src/lexer.rs"),
+        ]);
+
+        let theme = Theme::default();
+        let (lines, line_files, current) =
+            FileTreePane::build_tree_lines(&metadata, 1, None, Some("parser"), &theme);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(texts, vec!["/parser", "src/", "  + Parser.rs +0 -0"]);
+        assert_eq!(line_files, vec![None, None, Some(1)]);
+        assert_eq!(current, Some(2));
 
-        (lines, current_line_index)
+        let (lines, _, current) =
+            FileTreePane::build_tree_lines(&metadata, 1, None, Some("zzz"), &theme);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(texts, vec!["/zzz", "No matching files"]);
+        assert_eq!(current, None);
     }
 }
@@ -1,9 +1,11 @@
 mod editor;
 mod file_tree;
+mod overlay;
 mod status_bar;
 mod terminal;
 
 pub use editor::EditorPane;
 pub use file_tree::FileTreePane;
-pub use status_bar::StatusBarPane;
+pub use overlay::OverlayPane;
+pub use status_bar::{DateDisplay, Progress, StatusBarPane};
 pub use terminal::TerminalPane;
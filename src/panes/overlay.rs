@@ -0,0 +1,138 @@
+use unicode_width::UnicodeWidthStr;
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use crate::animation::AnimationEngine;
+use crate::theme::Theme;
+
+/// Popups drawn over the panes: the diff stat summary, the card for a skipped file and the
+/// "Open File..." dialog
+pub struct OverlayPane;
+
+impl OverlayPane {
+    /// Draws whichever popups the engine currently shows. The diff stat and the excluded
+    /// file card are centered over `editor_area`, and the dialog over the whole `area`.
+    pub fn render(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        editor_area: Rect,
+        engine: &AnimationEngine,
+        theme: &Theme,
+    ) {
+        // Render the diff stat summary, or why a file is skipped, centered over the editor
+        for card in [&engine.diff_stat, &engine.excluded_card]
+            .into_iter()
+            .flatten()
+        {
+            let card_width = (card.width() as u16 + 4).min(editor_area.width);
+            let card_height = 3.min(editor_area.height);
+            let card_area = Rect {
+                x: editor_area.x + (editor_area.width - card_width) / 2,
+                y: editor_area.y + (editor_area.height - card_height) / 2,
+                width: card_width,
+                height: card_height,
+            };
+
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1))
+                .style(
+                    Style::default()
+                        .fg(theme.file_tree_current_file_fg)
+                        .bg(theme.editor_cursor_line_bg),
+                );
+            f.render_widget(Paragraph::new(card.as_str()).block(block), card_area);
+        }
+
+        // Render dialog if present
+        if let Some(ref title) = engine.dialog_title {
+            let text = &engine.dialog_typing_text;
+            let text_display_width = text.width();
+            let dialog_width = (text_display_width + 10).max(60).min(area.width as usize) as u16;
+            let dialog_height = 3.min(area.height);
+            let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+            let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+
+            let dialog_area = Rect {
+                x: dialog_x,
+                y: dialog_y,
+                width: dialog_width,
+                height: dialog_height,
+            };
+
+            // Calculate content width (dialog_width - borders(2) - padding(2))
+            let content_width = dialog_width.saturating_sub(4) as usize;
+            let padding_len = content_width.saturating_sub(text_display_width);
+
+            let spans = vec![
+                Span::styled(
+                    text.clone(),
+                    Style::default().fg(theme.file_tree_current_file_fg),
+                ),
+                Span::styled(
+                    " ".repeat(padding_len),
+                    Style::default().bg(theme.editor_cursor_line_bg),
+                ),
+            ];
+
+            let dialog_text = vec![Line::from(spans)];
+
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(title.clone())
+                .padding(Padding::horizontal(1))
+                .style(
+                    Style::default()
+                        .fg(theme.file_tree_current_file_fg)
+                        .bg(theme.editor_cursor_line_bg),
+                );
+
+            let dialog = Paragraph::new(dialog_text).block(block);
+            f.render_widget(dialog, dialog_area);
+        }
+    }
+
+    /// Draws a boxed message centered over `area`, e.g. once playback has finished
+    pub fn render_notice(&self, f: &mut Frame, area: Rect, lines: &[&str], theme: &Theme) {
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 4;
+        let width = width.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let notice_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let text: Vec<Line> = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                // The first line is the message, the rest are hints
+                let color = if index == 0 {
+                    theme.status_message
+                } else {
+                    theme.status_no_commit
+                };
+                Line::styled(line.to_string(), Style::default().fg(color))
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(1))
+            .style(
+                Style::default()
+                    .fg(theme.file_tree_current_file_fg)
+                    .bg(theme.editor_cursor_line_bg),
+            );
+        f.render_widget(Clear, notice_area);
+        f.render_widget(Paragraph::new(text).block(block), notice_area);
+    }
+}
@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
+use unicode_width::UnicodeWidthStr;
+
 use ratatui::{
     layout::Rect,
-    style::Style,
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Padding},
     Frame,
@@ -10,14 +13,61 @@ use crate::git::CommitMetadata;
 use crate::theme::Theme;
 use crate::widgets::SelectableParagraph;
 
-pub struct StatusBarPane;
+// Columns left blank on each side of the text
+const HORIZONTAL_PADDING: u16 = 2;
+
+#[derive(Default)]
+pub struct StatusBarPane {
+    date_display: DateDisplay,
+}
+
+/// How the commit date is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DateDisplay {
+    /// `2024-05-01 12:30:00`
+    #[default]
+    Absolute,
+    /// `3 days ago`
+    Relative,
+    /// `2024-05-01 12:30:00 (3 days ago)`
+    Both,
+}
+
+impl DateDisplay {
+    /// Parses `absolute`, `relative` or `both`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "absolute" => Some(Self::Absolute),
+            "relative" => Some(Self::Relative),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+
+/// How far playback has got through the commits to play
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    /// Sequential playback: the commit's 1-based position among all of them
+    Position { current: usize, total: usize },
+    /// Random playback: how many distinct commits have been shown so far
+    Played(usize),
+}
 
 impl StatusBarPane {
+    pub fn set_date_display(&mut self, date_display: DateDisplay) {
+        self.date_display = date_display;
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         &self,
         f: &mut Frame,
         area: Rect,
         metadata: Option<&CommitMetadata>,
+        repo_name: Option<&str>,
+        progress: Option<Progress>,
+        speed_ms: Option<u64>,
         theme: &Theme,
     ) {
         let block = Block::default()
@@ -32,34 +82,119 @@ impl StatusBarPane {
                 &meta.hash[..7.min(meta.hash.len())]
             };
 
-            let mut lines = vec![
-                Line::from(vec![
-                    Span::raw("hash: "),
-                    Span::styled(hash_display, Style::default().fg(theme.status_hash)),
-                ]),
+            let mut lines = Vec::new();
+
+            // Only label the repository when playing several of them
+            if let Some(name) = repo_name {
+                lines.push(Line::from(vec![
+                    Span::raw("repo: "),
+                    Span::styled(name, Style::default().fg(theme.file_tree_directory)),
+                ]));
+            }
+
+            // Tag timeline: the release name leads
+            if !meta.tags.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::raw("tag: "),
+                    Span::styled(
+                        meta.tags.join(", "),
+                        Style::default()
+                            .fg(theme.status_hash)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ]));
+            }
+
+            let mut hash_line = vec![
+                Span::raw("hash: "),
+                Span::styled(hash_display, Style::default().fg(theme.status_hash)),
+            ];
+            if meta.signed {
+                hash_line.push(Span::styled(
+                    " 🔏 signed",
+                    Style::default().fg(theme.status_signed),
+                ));
+            }
+
+            lines.extend([
+                Line::from(hash_line),
                 Line::from(vec![
                     Span::raw("author: "),
+                    Span::styled(
+                        format!(" {} ", initials(&meta.author)),
+                        Style::default()
+                            .fg(theme.status_badge_fg)
+                            .bg(badge_color(&meta.author, theme))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" "),
                     Span::styled(&meta.author, Style::default().fg(theme.status_author)),
                 ]),
-            ];
+            ]);
 
             // Only show date for actual commits (not working tree)
             if !is_working_tree {
-                let date_str = meta.date.format("%Y-%m-%d %H:%M:%S").to_string();
+                let absolute = meta.date.format("%Y-%m-%d %H:%M:%S");
+                let date_str = match self.date_display {
+                    DateDisplay::Absolute => absolute.to_string(),
+                    DateDisplay::Relative => time_ago(meta.date, Utc::now()),
+                    DateDisplay::Both => {
+                        format!("{} ({})", absolute, time_ago(meta.date, Utc::now()))
+                    }
+                };
                 lines.push(Line::from(vec![
                     Span::raw("date: "),
                     Span::styled(date_str, Style::default().fg(theme.status_date)),
                 ]));
             }
 
-            // Add commit message lines (skip empty lines)
-            for msg_line in meta.message.lines() {
-                if !msg_line.trim().is_empty() {
-                    lines.push(Line::from(vec![Span::styled(
-                        msg_line,
-                        Style::default().fg(theme.status_message),
-                    )]));
-                }
+            if let Some(progress) = progress {
+                let (label, value) = match progress {
+                    Progress::Position { current, total } => {
+                        ("commit: ", format!("{}/{}", current, total))
+                    }
+                    Progress::Played(count) => ("played: ", count.to_string()),
+                };
+                lines.push(Line::from(vec![
+                    Span::raw(label),
+                    Span::styled(value, Style::default().fg(theme.status_date)),
+                ]));
+            }
+
+            // Briefly shown after the speed is changed with +/-
+            if let Some(speed_ms) = speed_ms {
+                lines.push(Line::from(vec![
+                    Span::raw("speed: "),
+                    Span::styled(
+                        format!("{}ms/char", speed_ms),
+                        Style::default().fg(theme.status_hash),
+                    ),
+                ]));
+            }
+
+            // Add commit message lines (skip empty lines), wrapped at word boundaries
+            // with the subject in bold
+            let message_width = area.width.saturating_sub(2 * HORIZONTAL_PADDING) as usize;
+            let subject_style = Style::default()
+                .fg(theme.status_message)
+                .add_modifier(Modifier::BOLD);
+            let body_style = Style::default().fg(theme.status_message);
+            for (index, msg_line) in meta
+                .message
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+            {
+                let style = if index == 0 {
+                    subject_style
+                } else {
+                    body_style
+                };
+                lines.extend(
+                    word_wrap(msg_line, message_width)
+                        .into_iter()
+                        .map(|part| Line::from(Span::styled(part, style))),
+                );
             }
 
             lines
@@ -73,8 +208,167 @@ impl StatusBarPane {
         let content = SelectableParagraph::new(status_text)
             .block(block)
             .background_style(Style::default().bg(theme.background_left))
-            .padding(Padding::horizontal(2));
+            .padding(Padding::horizontal(HORIZONTAL_PADDING));
 
         f.render_widget(content, area);
     }
 }
+
+// How long before `now` a date was, in its largest whole unit, e.g. "3 days ago".
+// Dates after `now` (clock skew between machines) read "in 2 hours", or "just now" when
+// they're within a minute.
+fn time_ago(date: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = now.signed_duration_since(date).num_seconds();
+    let elapsed = seconds.unsigned_abs();
+    if elapsed < 60 {
+        return "just now".to_string();
+    }
+
+    const UNITS: [(u64, &str); 6] = [
+        (365 * 24 * 60 * 60, "year"),
+        (30 * 24 * 60 * 60, "month"),
+        (7 * 24 * 60 * 60, "week"),
+        (24 * 60 * 60, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+    let (count, unit) = UNITS
+        .iter()
+        .find(|(size, _)| elapsed >= *size)
+        .map(|&(size, unit)| (elapsed / size, unit))
+        .unwrap_or((elapsed / 60, "minute"));
+    let plural = if count == 1 { "" } else { "s" };
+
+    if seconds < 0 {
+        format!("in {} {}{}", count, unit, plural)
+    } else {
+        format!("{} {}{} ago", count, unit, plural)
+    }
+}
+
+// First letters of the first and last words of a name, e.g. "AL" for "Ada King Lovelace"
+fn initials(name: &str) -> String {
+    let mut words = name
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|ch| ch.is_alphanumeric()));
+    let first = words.next();
+    let last = words.next_back();
+    let initials: String = first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+// Background of an author's badge, one of the theme's accent colors picked by a hash of
+// the name so each author keeps the same badge across commits and runs
+fn badge_color(name: &str, theme: &Theme) -> Color {
+    let accents = [
+        theme.syntax.keyword,
+        theme.syntax.function,
+        theme.syntax.string,
+        theme.syntax.type_,
+        theme.syntax.number,
+        theme.syntax.constant,
+        theme.status_author,
+        theme.status_date,
+    ];
+    let hash = name.bytes().fold(0u32, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(byte as u32)
+    });
+    accents[hash as usize % accents.len()]
+}
+
+// Breaks a line into pieces of at most `width` columns at spaces. Words longer than the
+// width are kept whole and left for the paragraph to break.
+fn word_wrap(line: &str, width: usize) -> Vec<String> {
+    let line = line.trim_end();
+    if width == 0 || line.width() <= width {
+        return vec![line.to_string()];
+    }
+
+    let words = line.trim_start();
+    let mut pieces = Vec::new();
+    let mut current = line[..line.len() - words.len()].to_string();
+    let mut has_word = false;
+    for word in words.split(' ') {
+        if has_word && current.width() + 1 + word.width() > width {
+            pieces.push(std::mem::take(&mut current));
+        } else if has_word {
+            current.push(' ');
+        }
+        current.push_str(word);
+        has_word = true;
+    }
+    pieces.push(current);
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_wrap_breaks_at_spaces() {
+        assert_eq!(word_wrap("fix: short", 20), vec!["fix: short"]);
+        assert_eq!(
+            word_wrap("feat: wrap long commit messages in the sidebar", 16),
+            vec!["feat: wrap long", "commit messages", "in the sidebar"]
+        );
+        // A word wider than the pane stays whole on its own line
+        assert_eq!(
+            word_wrap("see https://example.com/a/very/long/path now", 12),
+            vec!["see", "https://example.com/a/very/long/path", "now"]
+        );
+        // Leading indentation of the first piece is kept
+        assert_eq!(
+            word_wrap("  - item one two", 10),
+            vec!["  - item", "one two"]
+        );
+    }
+
+    #[test]
+    fn test_author_badge_is_stable() {
+        assert_eq!(initials("Ada King Lovelace"), "AL");
+        assert_eq!(initials("grace hopper"), "GH");
+        assert_eq!(initials("linus"), "L");
+        assert_eq!(initials("  (bot) renovate"), "BR");
+        assert_eq!(initials(""), "?");
+
+        let theme = Theme::default();
+        assert_eq!(
+            badge_color("Ada Lovelace", &theme),
+            badge_color("Ada Lovelace", &theme)
+        );
+        let colors: std::collections::HashSet<_> = ["alice", "bob", "carol", "dave", "erin"]
+            .iter()
+            .map(|name| format!("{:?}", badge_color(name, &theme)))
+            .collect();
+        assert!(colors.len() > 1);
+    }
+
+    #[test]
+    fn test_time_ago_picks_the_largest_unit() {
+        let now = DateTime::parse_from_rfc3339("2024-05-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ago = |seconds: i64| time_ago(now - chrono::Duration::seconds(seconds), now);
+
+        assert_eq!(ago(30), "just now");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(5 * 60 + 59), "5 minutes ago");
+        assert_eq!(ago(3600), "1 hour ago");
+        assert_eq!(ago(3 * 86400), "3 days ago");
+        assert_eq!(ago(14 * 86400), "2 weeks ago");
+        assert_eq!(ago(65 * 86400), "2 months ago");
+        assert_eq!(ago(800 * 86400), "2 years ago");
+        // Commits from a machine whose clock ran ahead
+        assert_eq!(ago(-20), "just now");
+        assert_eq!(ago(-2 * 3600), "in 2 hours");
+    }
+}
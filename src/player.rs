@@ -0,0 +1,279 @@
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::Paragraph,
+    Frame, Terminal,
+};
+
+use anyhow::Result;
+
+use crate::animation::AnimationEngine;
+use crate::git::CommitMetadata;
+use crate::panes::{EditorPane, FileTreePane, OverlayPane, StatusBarPane, TerminalPane};
+use crate::theme::Theme;
+
+/// Typing speed of a new [`Player`], the same as the gitlogue default
+pub const DEFAULT_SPEED_MS: u64 = 30;
+
+/// Animates commits into a ratatui frame or buffer, for embedding gitlogue's replay in
+/// another TUI.
+///
+/// Load a commit (e.g. from a [`Session`](crate::Session)), then call [`Player::tick`]
+/// regularly and draw with [`Player::render`] or [`Player::render_to_buffer`] whenever
+/// it reports a change. The layout matches the gitlogue app: file tree and commit info
+/// on the left, editor and terminal on the right.
+pub struct Player {
+    engine: AnimationEngine,
+    // Off-screen terminal for render_to_buffer, kept between frames
+    buffer_terminal: Option<Terminal<TestBackend>>,
+    theme: Theme,
+    editor: EditorPane,
+    terminal: TerminalPane,
+    file_tree: FileTreePane,
+    status_bar: StatusBarPane,
+    overlay: OverlayPane,
+}
+
+impl Player {
+    /// Creates a player drawing with `theme`, typing at [`DEFAULT_SPEED_MS`]
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            engine: AnimationEngine::new(DEFAULT_SPEED_MS),
+            buffer_terminal: None,
+            theme,
+            editor: EditorPane::new(),
+            terminal: TerminalPane,
+            file_tree: FileTreePane::new(),
+            status_bar: StatusBarPane::default(),
+            overlay: OverlayPane,
+        }
+    }
+
+    /// Sets the typing speed in milliseconds per character
+    pub fn speed(mut self, speed_ms: u64) -> Self {
+        self.engine.set_speed(speed_ms);
+        self
+    }
+
+    /// Animates only changed lines, skipping unchanged file content
+    pub fn changes_only(mut self, changes_only: bool) -> Self {
+        self.engine.set_changes_only(changes_only);
+        self
+    }
+
+    /// Plays each file's edit backwards, from its new content to its old one
+    pub fn reverse_file(mut self, reverse_file: bool) -> Self {
+        self.engine.set_reverse_file(reverse_file);
+        self
+    }
+
+    #[cfg(test)]
+    fn engine_mut(&mut self) -> &mut AnimationEngine {
+        &mut self.engine
+    }
+
+    /// Starts animating a commit, replacing the one being played
+    pub fn load_commit(&mut self, metadata: &CommitMetadata) {
+        self.engine.load_commit(metadata);
+    }
+
+    /// Advances the animation. Returns true when the frame changed and should be drawn.
+    pub fn tick(&mut self) -> bool {
+        self.engine.tick()
+    }
+
+    /// Returns true once the loaded commit has been fully played
+    pub fn is_finished(&self) -> bool {
+        self.engine.is_finished()
+    }
+
+    /// Draws the current frame into `area` of a ratatui frame
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .split(area);
+        let split = |area: Rect| {
+            Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(80),
+                    Constraint::Length(1),
+                    Constraint::Percentage(20),
+                ])
+                .split(area)
+        };
+        let left = split(columns[0]);
+        let right = split(columns[1]);
+
+        // Scrolling follows the cursor within the editor as it is actually drawn
+        self.engine.set_viewport_height(right[0].height as usize);
+        self.engine.set_content_width(right[0].width as usize);
+
+        if let Some(metadata) = self.engine.current_metadata() {
+            self.file_tree.set_commit_metadata(
+                metadata,
+                self.engine.current_file_index,
+                self.engine.codebase(),
+                &self.theme,
+            );
+        }
+
+        self.file_tree.render(f, left[0], &self.theme);
+        self.render_separator(f, left[1], self.theme.background_left);
+        self.status_bar.render(
+            f,
+            left[2],
+            self.engine.current_metadata(),
+            None,
+            None,
+            None,
+            &self.theme,
+        );
+
+        self.editor.render(f, right[0], &self.engine, &self.theme);
+        self.render_separator(f, right[1], self.theme.background_right);
+        self.terminal.render(f, right[2], &self.engine, &self.theme);
+
+        self.overlay
+            .render(f, area, right[0], &self.engine, &self.theme);
+    }
+
+    /// Draws the current frame into a buffer, filling its whole area
+    pub fn render_to_buffer(&mut self, buffer: &mut Buffer) -> Result<()> {
+        let area = buffer.area;
+        let mut terminal = match self.buffer_terminal.take() {
+            Some(mut terminal) => {
+                // Follows the buffer if it was resized since the last frame
+                if terminal.size()? != area.as_size() {
+                    terminal.backend_mut().resize(area.width, area.height);
+                    terminal.resize(Rect::new(0, 0, area.width, area.height))?;
+                }
+                terminal
+            }
+            None => Terminal::new(TestBackend::new(area.width, area.height))?,
+        };
+        terminal.draw(|f| self.render(f, f.area()))?;
+
+        // The frame is drawn at the origin, so shift it to where the buffer sits
+        let frame = terminal.backend().buffer();
+        for y in 0..area.height {
+            for x in 0..area.width {
+                buffer[(area.x + x, area.y + y)] = frame[(x, y)].clone();
+            }
+        }
+        self.buffer_terminal = Some(terminal);
+        Ok(())
+    }
+
+    fn render_separator(&self, f: &mut Frame, area: Rect, background: Color) {
+        let separator = Paragraph::new(Line::from("─".repeat(area.width as usize)))
+            .style(Style::default().fg(self.theme.separator).bg(background));
+        f.render_widget(separator, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffHunk, FileChange, FileStatus, LineChange, LineChangeType};
+
+    #[test]
+    fn test_plays_a_commit_into_a_buffer() {
+        let content = "hello player\n";
+        let change = FileChange {
+            status: FileStatus::Added,
+            old_content: None,
+            new_content: Some(content.to_string()),
+            old_size: None,
+            new_size: Some(content.len()),
+            hunks: vec![DiffHunk {
+                old_start: 0,
+                old_lines: 0,
+                new_start: 1,
+                new_lines: 1,
+                lines: vec![LineChange {
+                    change_type: LineChangeType::Addition,
+                    content: content.to_string(),
+                    old_line_no: None,
+                    new_line_no: Some(1),
+                    intraline: None,
+                }],
+            }],
+            ..FileChange::test_modified("hello.txt")
+        };
+        let metadata = CommitMetadata {
+            message: "Add greeting".to_string(),
+            ..CommitMetadata::test_with(vec![change])
+        };
+
+        let mut player = Player::new(Theme::default()).speed(0);
+        player.load_commit(&metadata);
+        let mut buffer = Buffer::empty(Rect::new(5, 2, 100, 30));
+        let mut typed = false;
+        while !player.is_finished() {
+            player.tick();
+            player.render_to_buffer(&mut buffer).unwrap();
+            let text: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+            typed |= text.contains("hello player");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert!(typed);
+        let text: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Add greeting"));
+        assert!(text.contains("hello.txt"));
+    }
+
+    #[test]
+    fn test_excluded_files_show_why_they_are_skipped() {
+        let lock = FileChange {
+            is_excluded: true,
+            exclusion_reason: Some("lock/generated file".to_string()),
+            ..FileChange::test_modified("Cargo.lock")
+        };
+        let metadata =
+            CommitMetadata::test_with(vec![lock, FileChange::test_modified("src/main.rs")]);
+
+        let mut player = Player::new(Theme::default()).speed(0);
+        player.engine_mut().set_excluded_card_ms(20);
+        player.load_commit(&metadata);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 120, 30));
+        let mut shown = false;
+        while !player.is_finished() {
+            player.tick();
+            player.render_to_buffer(&mut buffer).unwrap();
+            let text: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+            shown |= text.contains("Cargo.lock skipped: lock/generated file");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(shown);
+        assert!(player.engine_mut().excluded_card.is_none());
+    }
+
+    #[test]
+    fn test_file_filter_limits_the_animated_files() {
+        let metadata = CommitMetadata::test_with(
+            ["src/a.rs", "docs/b.md", "src/c.rs"]
+                .into_iter()
+                .map(FileChange::test_modified)
+                .collect(),
+        );
+
+        let mut player = Player::new(Theme::default()).speed(0);
+        player.engine_mut().set_file_filter("SRC");
+        player.load_commit(&metadata);
+        while !player.is_finished() {
+            player.tick();
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        // Each animated file is staged in the terminal
+        let terminal = player.engine_mut().terminal_lines.join("\n");
+        assert!(terminal.contains("git add src/a.rs"));
+        assert!(terminal.contains("git add src/c.rs"));
+        assert!(!terminal.contains("docs/b.md"));
+    }
+}
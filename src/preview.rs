@@ -0,0 +1,169 @@
+use chrono::Utc;
+
+use crate::git::{
+    annotate_intraline, CommitMetadata, DiffHunk, FileChange, FileStatus, LineChange,
+    LineChangeType,
+};
+
+const OLD_GREETING: &str = "use std::fmt;
+
+pub fn greet(name: &str) -> String {
+    format!(\"Hello, {}\", name)
+}
+";
+
+const NEW_GREETING: &str = "use std::fmt;
+
+/// Greets someone by name
+pub fn greet(name: &str) -> String {
+    let name = name.trim();
+    format!(\"Hello, {}!\", name)
+}
+";
+
+const NEW_LIB: &str = "pub mod greeting;
+
+pub const VERSION: u32 = 2;
+";
+
+const OLD_LEGACY: &str = "// Replaced by greeting.rs
+pub fn hello() {}
+";
+
+/// A small made-up commit touching a few files, played by `theme preview` so a theme
+/// can be judged on added, modified and deleted files and highlighted code.
+pub fn sample_commit() -> CommitMetadata {
+    CommitMetadata {
+        hash: "5a3f1e0c9b7d2468ace013579bdf2468ace01357".to_string(),
+        author: "gitlogue".to_string(),
+        date: Utc::now(),
+        message: "Tidy up the greeting module".to_string(),
+        changes: vec![
+            sample_change(
+                "src/greeting.rs",
+                FileStatus::Modified,
+                Some(OLD_GREETING),
+                Some(NEW_GREETING),
+                DiffHunk {
+                    old_start: 1,
+                    old_lines: 5,
+                    new_start: 1,
+                    new_lines: 7,
+                    lines: vec![
+                        line(LineChangeType::Context, "use std::fmt;", Some(1), Some(1)),
+                        line(LineChangeType::Context, "", Some(2), Some(2)),
+                        line(
+                            LineChangeType::Addition,
+                            "/// Greets someone by name",
+                            None,
+                            Some(3),
+                        ),
+                        line(
+                            LineChangeType::Context,
+                            "pub fn greet(name: &str) -> String {",
+                            Some(3),
+                            Some(4),
+                        ),
+                        line(
+                            LineChangeType::Addition,
+                            "    let name = name.trim();",
+                            None,
+                            Some(5),
+                        ),
+                        line(
+                            LineChangeType::Deletion,
+                            "    format!(\"Hello, {}\", name)",
+                            Some(4),
+                            None,
+                        ),
+                        line(
+                            LineChangeType::Addition,
+                            "    format!(\"Hello, {}!\", name)",
+                            None,
+                            Some(6),
+                        ),
+                        line(LineChangeType::Context, "}", Some(5), Some(7)),
+                    ],
+                },
+            ),
+            sample_change(
+                "src/legacy.rs",
+                FileStatus::Deleted,
+                Some(OLD_LEGACY),
+                None,
+                DiffHunk {
+                    old_start: 1,
+                    old_lines: 2,
+                    new_start: 0,
+                    new_lines: 0,
+                    lines: OLD_LEGACY
+                        .lines()
+                        .enumerate()
+                        .map(|(i, text)| line(LineChangeType::Deletion, text, Some(i + 1), None))
+                        .collect(),
+                },
+            ),
+            sample_change(
+                "src/lib.rs",
+                FileStatus::Added,
+                None,
+                Some(NEW_LIB),
+                DiffHunk {
+                    old_start: 0,
+                    old_lines: 0,
+                    new_start: 1,
+                    new_lines: 3,
+                    lines: NEW_LIB
+                        .lines()
+                        .enumerate()
+                        .map(|(i, text)| line(LineChangeType::Addition, text, None, Some(i + 1)))
+                        .collect(),
+                },
+            ),
+        ],
+        file_order: None,
+        tags: Vec::new(),
+        signed: true,
+        hidden_files: 0,
+    }
+}
+
+fn sample_change(
+    path: &str,
+    status: FileStatus,
+    old_content: Option<&str>,
+    new_content: Option<&str>,
+    mut hunk: DiffHunk,
+) -> FileChange {
+    annotate_intraline(&mut hunk.lines);
+    FileChange {
+        path: path.to_string(),
+        old_path: None,
+        status,
+        is_binary: false,
+        is_excluded: false,
+        exclusion_reason: None,
+        old_content: old_content.map(String::from),
+        new_content: new_content.map(String::from),
+        old_size: old_content.map(str::len),
+        new_size: new_content.map(str::len),
+        hunks: vec![hunk],
+        diff: String::new(),
+        submodule: None,
+    }
+}
+
+fn line(
+    change_type: LineChangeType,
+    content: &str,
+    old_line_no: Option<usize>,
+    new_line_no: Option<usize>,
+) -> LineChange {
+    LineChange {
+        change_type,
+        content: format!("{}\n", content),
+        old_line_no,
+        new_line_no,
+        intraline: None,
+    }
+}
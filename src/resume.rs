@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where sequential playback stood when a commit was last loaded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPosition {
+    /// Number of commits played so far in this order
+    pub commit_index: usize,
+    /// Hash of the last commit played, used to find the position again after new commits
+    pub commit: String,
+}
+
+/// A small JSON file remembering the last played position of each playback order,
+/// so a later run on the same repository can carry on instead of starting over.
+#[derive(Debug, Clone)]
+pub struct ResumeFile {
+    path: PathBuf,
+}
+
+impl ResumeFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The state file for a repository, `~/.cache/gitlogue/<repo-hash>.json` on Linux
+    pub fn for_repository(repo_dir: &Path) -> Option<Self> {
+        let repo_dir = repo_dir
+            .canonicalize()
+            .unwrap_or_else(|_| repo_dir.to_path_buf());
+        let file_name = format!("{:016x}.json", fnv1a(repo_dir.to_string_lossy().as_bytes()));
+        Some(Self::new(
+            dirs::cache_dir()?.join("gitlogue").join(file_name),
+        ))
+    }
+
+    /// The saved position for an order, if any. A missing or unreadable file counts as none.
+    pub fn load(&self, order: &str) -> Option<SavedPosition> {
+        self.read().remove(order)
+    }
+
+    pub fn save(&self, order: &str, position: SavedPosition) -> Result<()> {
+        let mut positions = self.read();
+        positions.insert(order.to_string(), position);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create state directory: {}", parent.display())
+            })?;
+        }
+        let content = serde_json::to_string_pretty(&positions)?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write state file: {}", self.path.display()))
+    }
+
+    fn read(&self) -> BTreeMap<String, SavedPosition> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+}
+
+// A stable hash, unlike `DefaultHasher`, so the file name survives toolchain updates
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::git::{CommitMetadata, GitRepository};
+
+/// Order in which a [`Session`] plays commits
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Order {
+    #[default]
+    Random,
+    Asc,
+    Desc,
+}
+
+type CommitHook = Box<dyn FnMut(&CommitMetadata)>;
+
+/// Drives commit playback for embedders, without owning a terminal.
+///
+/// Each commit loaded through [`Session::next_commit`] is passed to every
+/// registered [`Session::on_commit`] hook before it is returned for animation.
+pub struct Session {
+    repo: GitRepository,
+    order: Order,
+    on_commit: Vec<CommitHook>,
+}
+
+impl Session {
+    /// Opens a session on the Git repository at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::new(GitRepository::open(path)?))
+    }
+
+    /// Creates a session over an already configured repository (e.g. with filters set)
+    pub fn new(repo: GitRepository) -> Self {
+        Self {
+            repo,
+            order: Order::default(),
+            on_commit: Vec::new(),
+        }
+    }
+
+    /// Sets the playback order
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Registers a hook called with each commit's metadata as it is loaded.
+    /// Hooks run in registration order.
+    pub fn on_commit<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&CommitMetadata) + 'static,
+    {
+        self.on_commit.push(Box::new(hook));
+        self
+    }
+
+    pub fn repository(&self) -> &GitRepository {
+        &self.repo
+    }
+
+    /// Loads the next commit in playback order and notifies the hooks.
+    /// Returns an error once `Asc`/`Desc` playback runs out of commits.
+    pub fn next_commit(&mut self) -> Result<CommitMetadata> {
+        let metadata = match self.order {
+            Order::Random => self.repo.random_commit()?,
+            Order::Asc => self.repo.next_asc_commit()?,
+            Order::Desc => self.repo.next_desc_commit()?,
+        };
+
+        for hook in &mut self.on_commit {
+            hook(&metadata);
+        }
+
+        Ok(metadata)
+    }
+}
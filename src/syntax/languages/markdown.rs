@@ -58,3 +58,6 @@ pub const HIGHLIGHT_QUERY: &str = r#"
   (backslash_escape)
 ] @string.escape
 "#;
+
+// Fenced code blocks by their info string, HTML blocks and front matter
+pub const INJECTION_QUERY: &str = tree_sitter_md::INJECTION_QUERY_BLOCK;
@@ -21,9 +21,11 @@ pub mod python;
 pub mod ruby;
 pub mod rust;
 pub mod scala;
+pub mod sql;
 pub mod svelte;
 pub mod swift;
 pub mod typescript;
+pub mod vue;
 pub mod xml;
 pub mod yaml;
 pub mod zig;
@@ -32,10 +34,18 @@ use std::path::Path;
 use tree_sitter::Language;
 
 pub fn get_language(path: &Path) -> Option<(Language, &'static str)> {
+    if let Some(language) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(get_language_for_filename)
+    {
+        return Some(language);
+    }
+
     let extension = path.extension()?.to_str()?;
 
     match extension {
-        "sh" | "bash" | "zsh" => Some((bash::language(), bash::HIGHLIGHT_QUERY)),
+        "sh" | "bash" | "zsh" | "ksh" => Some((bash::language(), bash::HIGHLIGHT_QUERY)),
         // C++ before C to handle .h files (can be either)
         "cpp" | "cc" | "cxx" | "c++" | "C" | "CPP" | "hpp" | "hh" | "hxx" | "h++" | "H" | "HPP"
         | "tcc" | "inl" => Some((cpp::language(), cpp::HIGHLIGHT_QUERY)),
@@ -60,12 +70,142 @@ pub fn get_language(path: &Path) -> Option<(Language, &'static str)> {
         "rb" | "rbw" | "rake" | "gemspec" => Some((ruby::language(), ruby::HIGHLIGHT_QUERY)),
         "rs" => Some((rust::language(), rust::HIGHLIGHT_QUERY)),
         "scala" | "sc" | "sbt" => Some((scala::language(), scala::HIGHLIGHT_QUERY)),
+        "sql" => Some((sql::language(), sql::HIGHLIGHT_QUERY)),
         "svelte" => Some((svelte::language(), svelte::HIGHLIGHT_QUERY)),
         "swift" => Some((swift::language(), swift::HIGHLIGHT_QUERY)),
         "ts" | "tsx" | "mts" | "cts" => Some((typescript::language(), typescript::HIGHLIGHT_QUERY)),
+        "vue" => Some((vue::language(), vue::HIGHLIGHT_QUERY)),
         "xml" | "svg" | "xsl" | "xslt" => Some((xml::language(), xml::HIGHLIGHT_QUERY)),
         "yaml" | "yml" => Some((yaml::language(), yaml::HIGHLIGHT_QUERY)),
         "zig" => Some((zig::language(), zig::HIGHLIGHT_QUERY)),
         _ => None,
     }
 }
+
+/// Matches well-known file names that have no extension, or whose extension doesn't
+/// tell the language (e.g. shell dotfiles)
+pub fn get_language_for_filename(name: &str) -> Option<(Language, &'static str)> {
+    match name {
+        ".bashrc" | ".bash_profile" | ".bash_login" | ".bash_logout" | ".bash_aliases"
+        | ".profile" | ".zshrc" | ".zshenv" | ".zprofile" | ".zlogin" | ".kshrc" | ".envrc"
+        | "PKGBUILD" | "APKBUILD" => Some((bash::language(), bash::HIGHLIGHT_QUERY)),
+        // No Dockerfile or make grammar is bundled; their RUN lines and recipes are shell
+        "Dockerfile" | "Containerfile" | "Makefile" | "makefile" | "GNUmakefile" => {
+            Some((bash::language(), bash::HIGHLIGHT_QUERY))
+        }
+        "Gemfile" | "Rakefile" | "Guardfile" | "Podfile" | "Vagrantfile" | "Brewfile"
+        | "Fastfile" | "Appfile" | "Capfile" | "Dangerfile" | "Berksfile" | "Thorfile"
+        | ".irbrc" | ".pryrc" | "config.ru" => Some((ruby::language(), ruby::HIGHLIGHT_QUERY)),
+        ".babelrc" | ".eslintrc" | ".prettierrc" | ".jshintrc" | ".swcrc" | "composer.lock"
+        | "flake.lock" => Some((json::language(), json::HIGHLIGHT_QUERY)),
+        ".clang-format" | ".clang-tidy" | ".clangd" => {
+            Some((yaml::language(), yaml::HIGHLIGHT_QUERY))
+        }
+        "SConstruct" | "SConscript" | "BUCK" | "Snakefile" => {
+            Some((python::language(), python::HIGHLIGHT_QUERY))
+        }
+        _ => None,
+    }
+}
+
+/// Maps a `#!` line such as `#!/usr/bin/env python3` to the interpreter's grammar
+pub fn get_language_for_shebang(line: &str) -> Option<(Language, &'static str)> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    // `env` runs the first argument that isn't one of its own options
+    if interpreter == "env" {
+        interpreter = words.find(|word| !word.starts_with('-'))?;
+    }
+    // Drop version suffixes: python3.12 -> python, node18 -> node
+    let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    match name {
+        "sh" | "bash" | "zsh" | "ksh" | "dash" | "ash" => {
+            Some((bash::language(), bash::HIGHLIGHT_QUERY))
+        }
+        "python" | "pypy" => Some((python::language(), python::HIGHLIGHT_QUERY)),
+        "ruby" => Some((ruby::language(), ruby::HIGHLIGHT_QUERY)),
+        "node" | "nodejs" => Some((javascript::language(), javascript::HIGHLIGHT_QUERY)),
+        "deno" | "ts-node" | "tsx" => Some((typescript::language(), typescript::HIGHLIGHT_QUERY)),
+        "php" => Some((php::language(), php::HIGHLIGHT_QUERY)),
+        "lua" | "luajit" => Some((lua::language(), lua::HIGHLIGHT_QUERY)),
+        "elixir" => Some((elixir::language(), elixir::HIGHLIGHT_QUERY)),
+        "escript" => Some((erlang::language(), erlang::HIGHLIGHT_QUERY)),
+        _ => None,
+    }
+}
+
+/// The injection query of a grammar, marking regions written in another language
+/// (e.g. `<style>` in HTML), if it has one
+pub fn get_injection_query(language: &Language) -> Option<&'static str> {
+    if *language == html::language() {
+        Some(html::INJECTION_QUERY)
+    } else if *language == javascript::language() {
+        Some(javascript::INJECTION_QUERY)
+    } else if *language == markdown::language() {
+        Some(markdown::INJECTION_QUERY)
+    } else if *language == svelte::language() {
+        Some(svelte::INJECTION_QUERY)
+    } else {
+        None
+    }
+}
+
+/// Resolves a language name as written in injection queries and Markdown code fences,
+/// like `javascript`, `py` or `shell`
+pub fn get_language_for_name(name: &str) -> Option<(Language, &'static str)> {
+    let name = name.to_ascii_lowercase();
+    let extension = match name.as_str() {
+        "shell" | "console" => "sh",
+        "c#" | "csharp" => "cs",
+        "clojure" => "clj",
+        "elixir" => "ex",
+        "erlang" => "erl",
+        "golang" => "go",
+        "haskell" => "hs",
+        "javascript" => "js",
+        "kotlin" => "kt",
+        "python" => "py",
+        "ruby" => "rb",
+        "rust" => "rs",
+        "typescript" => "ts",
+        name => name,
+    };
+    get_language(Path::new(&format!("injected.{}", extension)))
+}
+
+/// Every bundled grammar with its display name, in alphabetical order
+pub fn all_languages() -> Vec<(&'static str, Language)> {
+    vec![
+        ("bash", bash::language()),
+        ("c", c::language()),
+        ("clojure", clojure::language()),
+        ("cpp", cpp::language()),
+        ("csharp", csharp::language()),
+        ("css", css::language()),
+        ("dart", dart::language()),
+        ("elixir", elixir::language()),
+        ("erlang", erlang::language()),
+        ("go", go_lang::language()),
+        ("haskell", haskell::language()),
+        ("html", html::language()),
+        ("java", java::language()),
+        ("javascript", javascript::language()),
+        ("json", json::language()),
+        ("kotlin", kotlin::language()),
+        ("lua", lua::language()),
+        ("markdown", markdown::language()),
+        ("php", php::language()),
+        ("python", python::language()),
+        ("ruby", ruby::language()),
+        ("rust", rust::language()),
+        ("scala", scala::language()),
+        ("sql", sql::language()),
+        ("svelte", svelte::language()),
+        ("swift", swift::language()),
+        ("typescript", typescript::language()),
+        ("xml", xml::language()),
+        ("yaml", yaml::language()),
+        ("zig", zig::language()),
+    ]
+}
@@ -0,0 +1,14 @@
+; Script and style blocks, like the HTML injections the Svelte query inherits
+
+((script_element
+  (raw_text) @injection.content)
+ (#set! injection.language "javascript"))
+
+((style_element
+  (raw_text) @injection.content)
+ (#set! injection.language "css"))
+
+; Expressions in braces, such as {count} or {#if visible}
+((expression
+  (svelte_raw_text) @injection.content)
+ (#set! injection.language "javascript"))
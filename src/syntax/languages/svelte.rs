@@ -2,4 +2,7 @@ pub fn language() -> tree_sitter::Language {
     tree_sitter_svelte_ng::LANGUAGE.into()
 }
 
-pub const HIGHLIGHT_QUERY: &str = tree_sitter_svelte_ng::HIGHLIGHTS_QUERY;
+// The bundled query only adds to the HTML one it inherits from, so both are combined here
+pub const HIGHLIGHT_QUERY: &str = include_str!("queries/svelte_highlights.scm");
+
+pub const INJECTION_QUERY: &str = include_str!("queries/svelte_injections.scm");
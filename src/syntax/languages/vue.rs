@@ -0,0 +1,9 @@
+// Vue single-file components are HTML-shaped, so the HTML grammar highlights the
+// template, script and style blocks along with their tags and attributes. Sharing the
+// grammar means the code inside script and style blocks gets HTML's JavaScript and CSS
+// injections too.
+pub fn language() -> tree_sitter::Language {
+    tree_sitter_html::LANGUAGE.into()
+}
+
+pub const HIGHLIGHT_QUERY: &str = tree_sitter_html::HIGHLIGHTS_QUERY;
@@ -2,11 +2,17 @@ pub mod languages;
 
 use crate::theme::Theme;
 use ratatui::style::Color;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use std::path::Path;
 use streaming_iterator::StreamingIterator;
-use tree_sitter::{Language, Parser, Query, QueryCursor};
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor};
 
-pub use languages::get_language;
+pub use languages::{
+    get_injection_query, get_language, get_language_for_name, get_language_for_shebang,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenType {
@@ -28,19 +34,19 @@ pub enum TokenType {
 impl TokenType {
     pub fn color(&self, theme: &Theme) -> Color {
         match self {
-            TokenType::Comment => theme.syntax_comment,
-            TokenType::Constant => theme.syntax_constant,
-            TokenType::Function => theme.syntax_function,
-            TokenType::Keyword => theme.syntax_keyword,
-            TokenType::Label => theme.syntax_label,
-            TokenType::Number => theme.syntax_number,
-            TokenType::Operator => theme.syntax_operator,
-            TokenType::Parameter => theme.syntax_parameter,
-            TokenType::Property => theme.syntax_property,
-            TokenType::Punctuation => theme.syntax_punctuation,
-            TokenType::String => theme.syntax_string,
-            TokenType::Type => theme.syntax_type,
-            TokenType::Variable => theme.syntax_variable,
+            TokenType::Comment => theme.syntax.comment,
+            TokenType::Constant => theme.syntax.constant,
+            TokenType::Function => theme.syntax.function,
+            TokenType::Keyword => theme.syntax.keyword,
+            TokenType::Label => theme.syntax.label,
+            TokenType::Number => theme.syntax.number,
+            TokenType::Operator => theme.syntax.operator,
+            TokenType::Parameter => theme.syntax.parameter,
+            TokenType::Property => theme.syntax.property,
+            TokenType::Punctuation => theme.syntax.punctuation,
+            TokenType::String => theme.syntax.string,
+            TokenType::Type => theme.syntax.type_,
+            TokenType::Variable => theme.syntax.variable,
         }
     }
 }
@@ -52,6 +58,77 @@ pub struct HighlightSpan {
     pub token_type: TokenType,
 }
 
+/// Number of highlighted files kept by default, see [`Highlighter::set_cache_size`]
+pub const DEFAULT_HIGHLIGHT_CACHE_SIZE: usize = 64;
+
+/// Highlight results of recently seen files, keyed by a hash of the query and content,
+/// so files shown again (e.g. each time a range loops) aren't parsed again.
+/// The least recently used entry is dropped once `capacity` is reached.
+#[derive(Debug, Clone)]
+struct HighlightCache {
+    capacity: usize,
+    order: VecDeque<u64>,
+    entries: HashMap<u64, CachedHighlights>,
+}
+
+// The query and content are kept with their spans so a hash collision isn't mistaken
+// for a hit
+#[derive(Debug, Clone)]
+struct CachedHighlights {
+    query_source: String,
+    source: String,
+    spans: Vec<HighlightSpan>,
+}
+
+impl HighlightCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: u64, query_source: &str, source: &str) -> Option<Vec<HighlightSpan>> {
+        let entry = self
+            .entries
+            .get(&key)
+            .filter(|entry| entry.query_source == query_source && entry.source == source)?;
+        let spans = entry.spans.clone();
+        self.order.retain(|&k| k != key);
+        self.order.push_back(key);
+        Some(spans)
+    }
+
+    fn insert(&mut self, key: u64, entry: CachedHighlights) {
+        if self.capacity == 0 {
+            return;
+        }
+        // A colliding entry is replaced
+        if self.entries.remove(&key).is_some() {
+            self.order.retain(|&k| k != key);
+        }
+        while self.entries.len() >= self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.order.push_back(key);
+        self.entries.insert(key, entry);
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
 pub struct Highlighter {
     parser: Parser,
     language: Option<Language>,
@@ -59,6 +136,11 @@ pub struct Highlighter {
     query_source: Option<String>,
     cached_tree: Option<tree_sitter::Tree>,
     cached_source: String,
+    // Marks regions in another language, highlighted by that language's grammar
+    injection_query: Option<Query>,
+    // Highlighters for injected languages by name, None for languages without a grammar
+    injected: HashMap<String, Option<Highlighter>>,
+    cache: HighlightCache,
 }
 
 impl Clone for Highlighter {
@@ -71,6 +153,10 @@ impl Clone for Highlighter {
         } else {
             None
         };
+        let injection_query = self.injection_query.as_ref().and_then(|_| {
+            let lang = self.language.as_ref()?;
+            Query::new(lang, get_injection_query(lang)?).ok()
+        });
 
         Self {
             parser: new_parser,
@@ -79,6 +165,9 @@ impl Clone for Highlighter {
             query_source: self.query_source.clone(),
             cached_tree: None,
             cached_source: String::new(),
+            injection_query,
+            injected: HashMap::new(),
+            cache: HighlightCache::new(self.cache.capacity),
         }
     }
 }
@@ -92,13 +181,47 @@ impl Highlighter {
             query_source: None,
             cached_tree: None,
             cached_source: String::new(),
+            injection_query: None,
+            injected: HashMap::new(),
+            cache: HighlightCache::new(DEFAULT_HIGHLIGHT_CACHE_SIZE),
+        }
+    }
+
+    /// Sets how many files' highlights are kept for reuse, 0 to always parse
+    pub fn set_cache_size(&mut self, size: usize) {
+        self.cache.set_capacity(size);
+    }
+
+    // Highlighter for a region injected into another language. Injections don't nest,
+    // so it ignores its own grammar's injections.
+    fn for_injection(name: &str) -> Option<Self> {
+        let mut highlighter = Self::new();
+        if !highlighter.apply_language(get_language_for_name(name)) {
+            return None;
         }
+        highlighter.injection_query = None;
+        // The whole file's result, injections included, is cached by the outer highlighter
+        highlighter.cache.set_capacity(0);
+        Some(highlighter)
     }
 
     pub fn set_language_from_path(&mut self, path: &str) -> bool {
-        if let Some((language, query_source)) = get_language(Path::new(path)) {
+        self.apply_language(get_language(Path::new(path)))
+    }
+
+    /// Picks the grammar from a `#!` interpreter line, for scripts whose path
+    /// doesn't identify the language. Only the first line of `content` is read.
+    pub fn set_language_from_content(&mut self, content: &str) -> bool {
+        let first_line = content.lines().next().unwrap_or("");
+        self.apply_language(get_language_for_shebang(first_line))
+    }
+
+    fn apply_language(&mut self, language: Option<(Language, &'static str)>) -> bool {
+        if let Some((language, query_source)) = language {
             if self.parser.set_language(&language).is_ok() {
                 if let Ok(query) = Query::new(&language, query_source) {
+                    self.injection_query = get_injection_query(&language)
+                        .and_then(|source| Query::new(&language, source).ok());
                     self.language = Some(language);
                     self.query = Some(query);
                     self.query_source = Some(query_source.to_string());
@@ -112,12 +235,40 @@ impl Highlighter {
         self.language = None;
         self.query = None;
         self.query_source = None;
+        self.injection_query = None;
         self.cached_tree = None;
         self.cached_source = String::new();
         false
     }
 
     pub fn highlight(&mut self, source: &str) -> Vec<HighlightSpan> {
+        let Some(query_source) = &self.query_source else {
+            return Vec::new();
+        };
+
+        let mut hasher = DefaultHasher::new();
+        query_source.hash(&mut hasher);
+        source.hash(&mut hasher);
+        let key = hasher.finish();
+        if let Some(spans) = self.cache.get(key, query_source, source) {
+            return spans;
+        }
+
+        let spans = self.parse_and_highlight(source);
+        if self.cache.capacity > 0 {
+            self.cache.insert(
+                key,
+                CachedHighlights {
+                    query_source: self.query_source.clone().unwrap_or_default(),
+                    source: source.to_string(),
+                    spans: spans.clone(),
+                },
+            );
+        }
+        spans
+    }
+
+    fn parse_and_highlight(&mut self, source: &str) -> Vec<HighlightSpan> {
         let mut spans = Vec::new();
 
         let Some(query) = &self.query else {
@@ -199,13 +350,326 @@ impl Highlighter {
             }
         }
 
+        // Regions in another language take that language's highlighting instead
+        if let Some(injection_query) = &self.injection_query {
+            for (name, range) in injection_ranges(injection_query, tree.root_node(), source) {
+                let injected = self
+                    .injected
+                    .entry(name)
+                    .or_insert_with_key(|name| Self::for_injection(name));
+                let Some(injected) = injected else {
+                    continue;
+                };
+
+                clip_spans(&mut spans, &range);
+                spans.extend(
+                    injected
+                        .highlight(&source[range.clone()])
+                        .into_iter()
+                        .map(|span| HighlightSpan {
+                            start: span.start + range.start,
+                            end: span.end + range.start,
+                            token_type: span.token_type,
+                        }),
+                );
+            }
+        }
+
         spans.sort_by_key(|span| span.start);
         spans
     }
 }
 
+/// Regions an injection query marks, with the name of the language each is written in.
+/// The language comes from an `@injection.language` capture or an `injection.language`
+/// property set on the pattern.
+fn injection_ranges(query: &Query, root: Node, source: &str) -> Vec<(String, Range<usize>)> {
+    let content_index = query.capture_index_for_name("injection.content");
+    let language_index = query.capture_index_for_name("injection.language");
+
+    let mut ranges = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, root, source.as_bytes());
+    while let Some(query_match) = matches.next() {
+        let capture_node = |index: Option<u32>| {
+            query_match
+                .captures
+                .iter()
+                .find(|capture| Some(capture.index) == index)
+                .map(|capture| capture.node)
+        };
+        let Some(content) = capture_node(content_index) else {
+            continue;
+        };
+        let name = capture_node(language_index)
+            .and_then(|node| source.get(node.byte_range()))
+            .or_else(|| {
+                query
+                    .property_settings(query_match.pattern_index)
+                    .iter()
+                    .find(|property| &*property.key == "injection.language")
+                    .and_then(|property| property.value.as_deref())
+            });
+
+        if let Some(name) = name {
+            if !content.byte_range().is_empty() {
+                ranges.push((name.to_string(), content.byte_range()));
+            }
+        }
+    }
+    ranges
+}
+
+/// Cuts `range` out of the spans, keeping the parts before and after it
+fn clip_spans(spans: &mut Vec<HighlightSpan>, range: &Range<usize>) {
+    let mut clipped = Vec::with_capacity(spans.len());
+    for span in spans.drain(..) {
+        if span.end <= range.start || span.start >= range.end {
+            clipped.push(span);
+            continue;
+        }
+        if span.start < range.start {
+            clipped.push(HighlightSpan {
+                end: range.start,
+                ..span.clone()
+            });
+        }
+        if span.end > range.end {
+            clipped.push(HighlightSpan {
+                start: range.end,
+                ..span
+            });
+        }
+    }
+    *spans = clipped;
+}
+
 impl Default for Highlighter {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The token type of the span covering the first occurrence of `text` in `source`
+    fn token_at(spans: &[HighlightSpan], source: &str, text: &str) -> Option<TokenType> {
+        let start = source.find(text).unwrap();
+        spans
+            .iter()
+            .find(|span| span.start <= start && start < span.end)
+            .map(|span| span.token_type)
+    }
+
+    #[test]
+    fn test_shell_scripts_are_highlighted() {
+        let source = "# deploy\nname=\"world\"\necho \"hello $name\"\n";
+
+        for path in ["deploy.sh", "setup.bash", "prompt.zsh", "legacy.ksh"] {
+            let mut highlighter = Highlighter::new();
+            assert!(highlighter.set_language_from_path(path), "{}", path);
+            let spans = highlighter.highlight(source);
+
+            assert_eq!(
+                token_at(&spans, source, "# deploy"),
+                Some(TokenType::Comment)
+            );
+            assert_eq!(token_at(&spans, source, "name="), Some(TokenType::Property));
+            assert_eq!(token_at(&spans, source, "echo"), Some(TokenType::Function));
+            assert_eq!(
+                token_at(&spans, source, "\"world\""),
+                Some(TokenType::String)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sql_is_highlighted() {
+        let source = "-- add users\nSELECT name FROM users WHERE id = 1;\n";
+        let mut highlighter = Highlighter::new();
+        assert!(highlighter.set_language_from_path("migrations/001_users.sql"));
+        let spans = highlighter.highlight(source);
+
+        assert_eq!(token_at(&spans, source, "-- add"), Some(TokenType::Comment));
+        assert_eq!(token_at(&spans, source, "SELECT"), Some(TokenType::Keyword));
+    }
+
+    #[test]
+    fn test_lua_is_highlighted() {
+        let source = "-- greet\nlocal function greet(name)\n  return \"hi \" .. name, 42\nend\n";
+        let mut highlighter = Highlighter::new();
+        assert!(highlighter.set_language_from_path("nvim/lua/plugins.lua"));
+        let spans = highlighter.highlight(source);
+
+        assert_eq!(
+            token_at(&spans, source, "-- greet"),
+            Some(TokenType::Comment)
+        );
+        assert_eq!(token_at(&spans, source, "local"), Some(TokenType::Keyword));
+        assert_eq!(
+            token_at(&spans, source, "function"),
+            Some(TokenType::Keyword)
+        );
+        assert_eq!(
+            token_at(&spans, source, "greet("),
+            Some(TokenType::Function)
+        );
+        assert_eq!(token_at(&spans, source, "\"hi"), Some(TokenType::String));
+        assert_eq!(token_at(&spans, source, "42"), Some(TokenType::Number));
+        assert_eq!(token_at(&spans, source, "end"), Some(TokenType::Keyword));
+    }
+
+    #[test]
+    fn test_single_file_components_are_highlighted() {
+        let source = "<template>\n  <p class=\"greeting\">{{ msg }}</p>\n</template>\n\n<script>\nexport default {}\n</script>\n";
+        let mut highlighter = Highlighter::new();
+        assert!(highlighter.set_language_from_path("src/components/Hello.vue"));
+        let spans = highlighter.highlight(source);
+        assert_eq!(token_at(&spans, source, "template>"), Some(TokenType::Type));
+        assert_eq!(token_at(&spans, source, "script>"), Some(TokenType::Type));
+        assert_eq!(
+            token_at(&spans, source, "greeting"),
+            Some(TokenType::String)
+        );
+        // The script block is highlighted as JavaScript
+        assert_eq!(token_at(&spans, source, "export"), Some(TokenType::Keyword));
+
+        let source = "<script>\n  let count = 0;\n</script>\n\n<button>{count}</button>\n";
+        assert!(highlighter.set_language_from_path("src/lib/Counter.svelte"));
+        let spans = highlighter.highlight(source);
+        assert_eq!(token_at(&spans, source, "button>"), Some(TokenType::Type));
+    }
+
+    #[test]
+    fn test_injected_languages_are_highlighted() {
+        let source =
+            "<style>\n  p { color: red; }\n</style>\n<script>\n  const answer = 42;\n</script>\n";
+        let mut highlighter = Highlighter::new();
+        assert!(highlighter.set_language_from_path("index.html"));
+        let spans = highlighter.highlight(source);
+        assert_eq!(token_at(&spans, source, "color"), Some(TokenType::Property));
+        assert_eq!(token_at(&spans, source, "const"), Some(TokenType::Keyword));
+        assert_eq!(token_at(&spans, source, "42"), Some(TokenType::Number));
+
+        // Code fences use the language named on the fence, and other blocks keep theirs
+        let source = "# Usage\n\n```rust\nfn main() {}\n```\n\n```unknown\nfn main() {}\n```\n";
+        assert!(highlighter.set_language_from_path("README.md"));
+        let spans = highlighter.highlight(source);
+        assert_eq!(
+            token_at(&spans, source, "fn main"),
+            Some(TokenType::Keyword)
+        );
+        let unknown = source.rfind("fn main").unwrap();
+        assert!(spans.iter().any(|span| span.start <= unknown
+            && unknown < span.end
+            && span.token_type == TokenType::String));
+    }
+
+    #[test]
+    fn test_language_from_file_name() {
+        let mut highlighter = Highlighter::new();
+        for path in [
+            "Gemfile",
+            "app/Rakefile",
+            ".bashrc",
+            "pkg/PKGBUILD",
+            ".eslintrc",
+            "Dockerfile",
+            "docker/Containerfile",
+            "Makefile",
+            "GNUmakefile",
+        ] {
+            assert!(highlighter.set_language_from_path(path), "{}", path);
+        }
+
+        // Matching is by exact file name, and extensions still work alongside it
+        assert!(!highlighter.set_language_from_path("Gemfile.old"));
+        assert!(!highlighter.set_language_from_path("notes/gemfile"));
+        assert!(highlighter.set_language_from_path("config/boot.rb"));
+
+        // Dockerfiles and Makefiles borrow the shell grammar for their commands
+        for path in ["Dockerfile", "Makefile"] {
+            let query = get_language(Path::new(path)).map(|(_, query)| query);
+            assert_eq!(query, Some(languages::bash::HIGHLIGHT_QUERY), "{}", path);
+        }
+
+        let source = "gem \"rails\"\n";
+        highlighter.set_language_from_path("Gemfile");
+        let spans = highlighter.highlight(source);
+        assert!(spans
+            .iter()
+            .any(|span| span.start == 4 && span.token_type == TokenType::String));
+    }
+
+    #[test]
+    fn test_language_from_shebang() {
+        use languages::{bash, javascript, python, ruby};
+
+        let query = |line: &str| get_language_for_shebang(line).map(|(_, query)| query);
+        assert_eq!(
+            query("#!/usr/bin/env python3"),
+            Some(python::HIGHLIGHT_QUERY)
+        );
+        assert_eq!(
+            query("#!/usr/bin/python3.12 -u"),
+            Some(python::HIGHLIGHT_QUERY)
+        );
+        assert_eq!(
+            query("#!/usr/bin/env -S ruby -w"),
+            Some(ruby::HIGHLIGHT_QUERY)
+        );
+        assert_eq!(
+            query("#!/usr/bin/env node"),
+            Some(javascript::HIGHLIGHT_QUERY)
+        );
+        assert_eq!(query("#!/bin/bash -e"), Some(bash::HIGHLIGHT_QUERY));
+        assert_eq!(query("#!/usr/bin/env awk"), None);
+        assert_eq!(query("# not a shebang"), None);
+
+        // Path detection wins; the shebang is only a fallback
+        let mut highlighter = Highlighter::new();
+        assert!(!highlighter.set_language_from_path("bin/deploy"));
+        assert!(highlighter.set_language_from_content("#!/bin/sh\necho hi\n"));
+        assert!(!highlighter.set_language_from_content("echo hi\n"));
+    }
+
+    #[test]
+    fn test_highlights_are_cached_per_content() {
+        let mut highlighter = Highlighter::new();
+        highlighter.set_cache_size(2);
+        assert!(highlighter.set_language_from_path("main.rs"));
+
+        let first = highlighter.highlight("fn a() {}\n");
+        assert!(!first.is_empty());
+        highlighter.highlight("fn b() {}\n");
+        let again = highlighter.highlight("fn a() {}\n");
+        assert_eq!(again.len(), first.len());
+        assert_eq!(highlighter.cache.entries.len(), 2);
+
+        // The least recently used entry ("fn b") makes room for the new one
+        highlighter.highlight("fn c() {}\n");
+        assert_eq!(highlighter.cache.entries.len(), 2);
+        assert_eq!(highlighter.cache.order.len(), 2);
+        let rust_key = highlighter.cache.order[0];
+
+        // The same content in another language is a different entry, evicting "fn a" in Rust
+        assert!(highlighter.set_language_from_path("main.py"));
+        highlighter.highlight("fn a() {}\n");
+        assert!(!highlighter.cache.entries.contains_key(&rust_key));
+
+        // An entry under the same key but for other content isn't a hit
+        let python_key = *highlighter.cache.order.back().unwrap();
+        let query_source = highlighter.query_source.clone().unwrap();
+        assert!(highlighter
+            .cache
+            .get(python_key, &query_source, "fn z() {}\n")
+            .is_none());
+
+        highlighter.set_cache_size(0);
+        assert!(highlighter.cache.entries.is_empty());
+        highlighter.highlight("def a(): pass\n");
+        assert!(highlighter.cache.entries.is_empty());
+    }
+}
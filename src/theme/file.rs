@@ -0,0 +1,314 @@
+use super::{SyntaxTheme, Theme};
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// On-disk TOML representation of a theme, grouped by UI component
+#[derive(Deserialize, Serialize)]
+pub(super) struct ThemeFile {
+    separator: HexColor,
+    /// Optional, defaults to the editor's line number color
+    scrollbar: Option<HexColor>,
+    background: BackgroundColors,
+    editor: EditorColors,
+    file_tree: FileTreeColors,
+    terminal: TerminalColors,
+    status: StatusColors,
+    syntax: SyntaxColors,
+}
+
+/// On-disk TOML representation of a syntax theme: just the `[syntax]` table of a theme file
+#[derive(Deserialize)]
+pub(super) struct SyntaxThemeFile {
+    syntax: SyntaxColors,
+}
+
+#[derive(Deserialize, Serialize)]
+struct BackgroundColors {
+    left: HexColor,
+    right: HexColor,
+}
+
+#[derive(Deserialize, Serialize)]
+struct EditorColors {
+    line_number: HexColor,
+    line_number_cursor: HexColor,
+    separator: HexColor,
+    cursor_char_bg: HexColor,
+    cursor_char_fg: HexColor,
+    cursor_line_bg: HexColor,
+    /// Optional so theme files written before the marker existed keep loading
+    truncation_marker: Option<HexColor>,
+    /// Optional, like `truncation_marker`
+    search_match_bg: Option<HexColor>,
+    /// Optional, like `truncation_marker`
+    intraline_change: Option<HexColor>,
+    /// Optional, like `truncation_marker`
+    trailing_whitespace: Option<HexColor>,
+    /// Optional, defaults to `search_match_bg`
+    bracket_match: Option<HexColor>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct FileTreeColors {
+    added: HexColor,
+    deleted: HexColor,
+    modified: HexColor,
+    renamed: HexColor,
+    directory: HexColor,
+    current_file_bg: HexColor,
+    current_file_fg: HexColor,
+    default: HexColor,
+    stats_added: HexColor,
+    stats_deleted: HexColor,
+}
+
+#[derive(Deserialize, Serialize)]
+struct TerminalColors {
+    command: HexColor,
+    output: HexColor,
+    cursor_bg: HexColor,
+    cursor_fg: HexColor,
+}
+
+#[derive(Deserialize, Serialize)]
+struct StatusColors {
+    hash: HexColor,
+    author: HexColor,
+    date: HexColor,
+    message: HexColor,
+    no_commit: HexColor,
+    /// Optional, defaults to the left background
+    badge_fg: Option<HexColor>,
+    /// Optional, defaults to the file tree's added color
+    signed: Option<HexColor>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SyntaxColors {
+    keyword: HexColor,
+    #[serde(rename = "type")]
+    type_: HexColor,
+    function: HexColor,
+    variable: HexColor,
+    string: HexColor,
+    number: HexColor,
+    comment: HexColor,
+    operator: HexColor,
+    punctuation: HexColor,
+    constant: HexColor,
+    parameter: HexColor,
+    property: HexColor,
+    label: HexColor,
+}
+
+/// Color written as a `"#rrggbb"` hex string, or `"reset"` for the terminal default
+#[derive(Clone, Copy)]
+struct HexColor(Color);
+
+impl Serialize for HexColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Color::Rgb(r, g, b) => {
+                serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}", r, g, b))
+            }
+            Color::Reset => serializer.serialize_str("reset"),
+            other => Err(serde::ser::Error::custom(format!(
+                "color {:?} can't be written as a hex string",
+                other
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_color(&value)
+            .map(HexColor)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    if value.eq_ignore_ascii_case("reset") {
+        return Ok(Color::Reset);
+    }
+
+    let rgb = value
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6)
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| {
+            format!(
+                "invalid color \"{}\", expected a hex string like \"#1a1b26\"",
+                value
+            )
+        })?;
+
+    Ok(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+impl From<ThemeFile> for Theme {
+    fn from(file: ThemeFile) -> Self {
+        Theme {
+            background_left: file.background.left.0,
+            background_right: file.background.right.0,
+
+            editor_line_number: file.editor.line_number.0,
+            editor_line_number_cursor: file.editor.line_number_cursor.0,
+            editor_separator: file.editor.separator.0,
+            editor_cursor_char_bg: file.editor.cursor_char_bg.0,
+            editor_cursor_char_fg: file.editor.cursor_char_fg.0,
+            editor_cursor_line_bg: file.editor.cursor_line_bg.0,
+            editor_truncation_marker: file
+                .editor
+                .truncation_marker
+                .unwrap_or(file.editor.line_number)
+                .0,
+            editor_search_match_bg: file
+                .editor
+                .search_match_bg
+                .unwrap_or(file.editor.cursor_line_bg)
+                .0,
+            editor_intraline_change: file
+                .editor
+                .intraline_change
+                .unwrap_or(file.editor.cursor_line_bg)
+                .0,
+            editor_trailing_whitespace: file
+                .editor
+                .trailing_whitespace
+                .unwrap_or(file.editor.cursor_line_bg)
+                .0,
+            editor_bracket_match: file
+                .editor
+                .bracket_match
+                .or(file.editor.search_match_bg)
+                .unwrap_or(file.editor.cursor_line_bg)
+                .0,
+
+            file_tree_added: file.file_tree.added.0,
+            file_tree_deleted: file.file_tree.deleted.0,
+            file_tree_modified: file.file_tree.modified.0,
+            file_tree_renamed: file.file_tree.renamed.0,
+            file_tree_directory: file.file_tree.directory.0,
+            file_tree_current_file_bg: file.file_tree.current_file_bg.0,
+            file_tree_current_file_fg: file.file_tree.current_file_fg.0,
+            file_tree_default: file.file_tree.default.0,
+            file_tree_stats_added: file.file_tree.stats_added.0,
+            file_tree_stats_deleted: file.file_tree.stats_deleted.0,
+
+            terminal_command: file.terminal.command.0,
+            terminal_output: file.terminal.output.0,
+            terminal_cursor_bg: file.terminal.cursor_bg.0,
+            terminal_cursor_fg: file.terminal.cursor_fg.0,
+
+            status_hash: file.status.hash.0,
+            status_author: file.status.author.0,
+            status_date: file.status.date.0,
+            status_message: file.status.message.0,
+            status_no_commit: file.status.no_commit.0,
+            status_badge_fg: file.status.badge_fg.unwrap_or(file.background.left).0,
+            status_signed: file.status.signed.unwrap_or(file.file_tree.added).0,
+
+            separator: file.separator.0,
+            scrollbar: file.scrollbar.unwrap_or(file.editor.line_number).0,
+
+            syntax: file.syntax.into(),
+        }
+    }
+}
+
+impl From<SyntaxColors> for SyntaxTheme {
+    fn from(colors: SyntaxColors) -> Self {
+        SyntaxTheme {
+            keyword: colors.keyword.0,
+            type_: colors.type_.0,
+            function: colors.function.0,
+            variable: colors.variable.0,
+            string: colors.string.0,
+            number: colors.number.0,
+            comment: colors.comment.0,
+            operator: colors.operator.0,
+            punctuation: colors.punctuation.0,
+            constant: colors.constant.0,
+            parameter: colors.parameter.0,
+            property: colors.property.0,
+            label: colors.label.0,
+        }
+    }
+}
+
+impl From<SyntaxThemeFile> for SyntaxTheme {
+    fn from(file: SyntaxThemeFile) -> Self {
+        file.syntax.into()
+    }
+}
+
+impl From<&Theme> for ThemeFile {
+    fn from(theme: &Theme) -> Self {
+        ThemeFile {
+            separator: HexColor(theme.separator),
+            scrollbar: Some(HexColor(theme.scrollbar)),
+            background: BackgroundColors {
+                left: HexColor(theme.background_left),
+                right: HexColor(theme.background_right),
+            },
+            editor: EditorColors {
+                line_number: HexColor(theme.editor_line_number),
+                line_number_cursor: HexColor(theme.editor_line_number_cursor),
+                separator: HexColor(theme.editor_separator),
+                cursor_char_bg: HexColor(theme.editor_cursor_char_bg),
+                cursor_char_fg: HexColor(theme.editor_cursor_char_fg),
+                cursor_line_bg: HexColor(theme.editor_cursor_line_bg),
+                truncation_marker: Some(HexColor(theme.editor_truncation_marker)),
+                search_match_bg: Some(HexColor(theme.editor_search_match_bg)),
+                intraline_change: Some(HexColor(theme.editor_intraline_change)),
+                trailing_whitespace: Some(HexColor(theme.editor_trailing_whitespace)),
+                bracket_match: Some(HexColor(theme.editor_bracket_match)),
+            },
+            file_tree: FileTreeColors {
+                added: HexColor(theme.file_tree_added),
+                deleted: HexColor(theme.file_tree_deleted),
+                modified: HexColor(theme.file_tree_modified),
+                renamed: HexColor(theme.file_tree_renamed),
+                directory: HexColor(theme.file_tree_directory),
+                current_file_bg: HexColor(theme.file_tree_current_file_bg),
+                current_file_fg: HexColor(theme.file_tree_current_file_fg),
+                default: HexColor(theme.file_tree_default),
+                stats_added: HexColor(theme.file_tree_stats_added),
+                stats_deleted: HexColor(theme.file_tree_stats_deleted),
+            },
+            terminal: TerminalColors {
+                command: HexColor(theme.terminal_command),
+                output: HexColor(theme.terminal_output),
+                cursor_bg: HexColor(theme.terminal_cursor_bg),
+                cursor_fg: HexColor(theme.terminal_cursor_fg),
+            },
+            status: StatusColors {
+                hash: HexColor(theme.status_hash),
+                author: HexColor(theme.status_author),
+                date: HexColor(theme.status_date),
+                message: HexColor(theme.status_message),
+                no_commit: HexColor(theme.status_no_commit),
+                badge_fg: Some(HexColor(theme.status_badge_fg)),
+                signed: Some(HexColor(theme.status_signed)),
+            },
+            syntax: SyntaxColors {
+                keyword: HexColor(theme.syntax.keyword),
+                type_: HexColor(theme.syntax.type_),
+                function: HexColor(theme.syntax.function),
+                variable: HexColor(theme.syntax.variable),
+                string: HexColor(theme.syntax.string),
+                number: HexColor(theme.syntax.number),
+                comment: HexColor(theme.syntax.comment),
+                operator: HexColor(theme.syntax.operator),
+                punctuation: HexColor(theme.syntax.punctuation),
+                constant: HexColor(theme.syntax.constant),
+                parameter: HexColor(theme.syntax.parameter),
+                property: HexColor(theme.syntax.property),
+                label: HexColor(theme.syntax.label),
+            },
+        }
+    }
+}
@@ -1,7 +1,38 @@
+mod file;
+mod palette;
 mod themes;
 
+pub use palette::ColorDepth;
+
 use anyhow::{Context, Result};
 use ratatui::style::Color;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BUILTIN_THEMES: &[&str] = &[
+    "ayu-dark",
+    "catppuccin",
+    "dracula",
+    "everforest",
+    "github-dark",
+    "gruvbox",
+    "material",
+    "monokai",
+    "night-owl",
+    "nord",
+    "one-dark",
+    "rose-pine",
+    "solarized-dark",
+    "solarized-light",
+    "telemetry",
+    "tokyo-night",
+];
+
+/// A theme file found in the user themes directory
+pub struct UserTheme {
+    pub name: String,
+    pub theme: Result<Theme>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -16,6 +47,11 @@ pub struct Theme {
     pub editor_cursor_char_bg: Color,
     pub editor_cursor_char_fg: Color,
     pub editor_cursor_line_bg: Color,
+    pub editor_truncation_marker: Color,
+    pub editor_search_match_bg: Color,
+    pub editor_intraline_change: Color,
+    pub editor_trailing_whitespace: Color,
+    pub editor_bracket_match: Color,
 
     // File tree colors
     pub file_tree_added: Color,
@@ -41,24 +77,55 @@ pub struct Theme {
     pub status_date: Color,
     pub status_message: Color,
     pub status_no_commit: Color,
+    pub status_badge_fg: Color,
+    pub status_signed: Color,
 
     // Separator colors
     pub separator: Color,
+    pub scrollbar: Color,
 
     // Syntax highlighting colors
-    pub syntax_keyword: Color,
-    pub syntax_type: Color,
-    pub syntax_function: Color,
-    pub syntax_variable: Color,
-    pub syntax_string: Color,
-    pub syntax_number: Color,
-    pub syntax_comment: Color,
-    pub syntax_operator: Color,
-    pub syntax_punctuation: Color,
-    pub syntax_constant: Color,
-    pub syntax_parameter: Color,
-    pub syntax_property: Color,
-    pub syntax_label: Color,
+    pub syntax: SyntaxTheme,
+}
+
+/// Colors of highlighted code tokens, kept apart from the UI colors so code can be shown
+/// in another theme's colors (see [`Theme::with_syntax`])
+#[derive(Debug, Clone)]
+pub struct SyntaxTheme {
+    pub keyword: Color,
+    pub type_: Color,
+    pub function: Color,
+    pub variable: Color,
+    pub string: Color,
+    pub number: Color,
+    pub comment: Color,
+    pub operator: Color,
+    pub punctuation: Color,
+    pub constant: Color,
+    pub parameter: Color,
+    pub property: Color,
+    pub label: Color,
+}
+
+impl SyntaxTheme {
+    /// Load the syntax colors of a built-in or user theme by name
+    pub fn load(name: &str) -> Result<Self> {
+        Theme::load(name)
+            .map(|theme| theme.syntax)
+            .with_context(|| format!("Failed to load syntax theme '{}'", name))
+    }
+
+    /// Load syntax colors from a TOML file with a `[syntax]` table, such as a theme file.
+    /// Other tables are ignored, so the file doesn't need the UI colors.
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read syntax theme file: {}", path.display()))?;
+
+        let file: file::SyntaxThemeFile = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse syntax theme file: {}", path.display()))?;
+
+        Ok(file.into())
+    }
 }
 
 impl Default for Theme {
@@ -68,7 +135,8 @@ impl Default for Theme {
 }
 
 impl Theme {
-    /// Load theme by name
+    /// Load theme by name, falling back to the user themes directory.
+    /// Built-in themes take precedence over user themes with the same name.
     pub fn load(name: &str) -> Result<Self> {
         match name {
             "ayu-dark" => Ok(themes::ayu_dark()),
@@ -87,11 +155,82 @@ impl Theme {
             "solarized-light" => Ok(themes::solarized_light()),
             "telemetry" => Ok(themes::telemetry()),
             "tokyo-night" => Ok(themes::tokyo_night()),
-            _ => Err(anyhow::anyhow!("Unknown theme: {}", name))
-                .context("Available themes: ayu-dark, catppuccin, dracula, everforest, github-dark, gruvbox, material, monokai, night-owl, nord, one-dark, rose-pine, solarized-dark, solarized-light, telemetry, tokyo-night"),
+            _ => Self::load_user_theme(Self::user_themes_dir().as_deref(), name),
         }
     }
 
+    // Loads the theme file named after `name` from `dir`, the user themes directory
+    fn load_user_theme(dir: Option<&Path>, name: &str) -> Result<Self> {
+        match dir
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .filter(|path| path.is_file())
+        {
+            Some(path) => Self::load_from_file(&path),
+            None => Err(anyhow::anyhow!("Unknown theme: {}", name)).with_context(|| {
+                format!("Available themes: {}", Self::available_themes().join(", "))
+            }),
+        }
+    }
+
+    /// Load a theme from a TOML theme file
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+
+        let file: file::ThemeFile = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+
+        Ok(file.into())
+    }
+
+    /// Serialize the theme in the TOML theme file format read by `load_from_file`
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(&file::ThemeFile::from(self)).context("Failed to serialize theme")
+    }
+
+    /// Directory custom theme files are loaded from
+    pub fn user_themes_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("gitlogue").join("themes"))
+    }
+
+    /// Load every `*.toml` theme in the user themes directory, sorted by name.
+    /// Files that fail to parse are returned with their error instead of aborting.
+    pub fn user_themes() -> Vec<UserTheme> {
+        Self::user_themes_dir()
+            .map(|dir| Self::user_themes_in(&dir))
+            .unwrap_or_default()
+    }
+
+    fn user_themes_in(dir: &Path) -> Vec<UserTheme> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut user_themes: Vec<UserTheme> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_str()?.to_string();
+                let theme = Self::load_from_file(&path);
+                Some(UserTheme { name, theme })
+            })
+            .collect();
+
+        user_themes.sort_by(|a, b| a.name.cmp(&b.name));
+        user_themes
+    }
+
+    /// Whether a theme name belongs to a built-in theme
+    pub fn is_builtin(name: &str) -> bool {
+        BUILTIN_THEMES.contains(&name)
+    }
+
+    /// Replace the syntax highlighting colors, keeping the UI colors of this theme
+    pub fn with_syntax(mut self, syntax: SyntaxTheme) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
     /// Remove background colors for transparent terminal background
     pub fn with_transparent_background(mut self) -> Self {
         self.background_left = Color::Reset;
@@ -99,25 +238,120 @@ impl Theme {
         self
     }
 
-    /// List all available built-in themes
-    pub fn available_themes() -> Vec<&'static str> {
-        vec![
-            "ayu-dark",
-            "catppuccin",
-            "dracula",
-            "everforest",
-            "github-dark",
-            "gruvbox",
-            "material",
-            "monokai",
-            "night-owl",
-            "nord",
-            "one-dark",
-            "rose-pine",
-            "solarized-dark",
-            "solarized-light",
-            "telemetry",
-            "tokyo-night",
-        ]
+    /// List all built-in themes
+    pub fn builtin_themes() -> &'static [&'static str] {
+        BUILTIN_THEMES
+    }
+
+    /// List all selectable themes: built-ins followed by user themes that don't shadow them
+    pub fn available_themes() -> Vec<String> {
+        let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|s| s.to_string()).collect();
+        names.extend(
+            Self::user_themes()
+                .into_iter()
+                .map(|t| t.name)
+                .filter(|name| !Self::is_builtin(name)),
+        );
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_file_errors_name_the_problem() {
+        let path = std::env::temp_dir().join(format!("gitlogue_theme_{}.toml", std::process::id()));
+        let load_error = |contents: &str| {
+            fs::write(&path, contents).unwrap();
+            format!("{:#}", Theme::load_from_file(&path).unwrap_err())
+        };
+
+        let error = load_error("separator = \"#zzz\"\n");
+        assert!(error.contains("Failed to parse theme file"), "{}", error);
+        assert!(error.contains("invalid color \"#zzz\""), "{}", error);
+
+        let error = load_error("separator = \"#112233\"\n");
+        assert!(error.contains("missing field `background`"), "{}", error);
+
+        fs::remove_file(&path).ok();
+        let error = format!("{:#}", Theme::load_from_file(&path).unwrap_err());
+        assert!(error.contains("Failed to read theme file"), "{}", error);
+    }
+
+    #[test]
+    fn test_user_themes_are_listed_and_loaded_by_name() {
+        let dir = std::env::temp_dir().join(format!("gitlogue_themes_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let exported = Theme::load("nord").unwrap().to_toml().unwrap();
+        fs::write(dir.join("zen.toml"), &exported).unwrap();
+        fs::write(dir.join("broken.toml"), "separator = 1\n").unwrap();
+        fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+
+        let listed = Theme::user_themes_in(&dir);
+        let loaded = Theme::load_user_theme(Some(&dir), "zen");
+        let broken = Theme::load_user_theme(Some(&dir), "broken");
+        let unknown = Theme::load_user_theme(Some(&dir), "notes");
+        fs::remove_dir_all(&dir).unwrap();
+
+        // Sorted by name, with files that fail to parse listed along with their error
+        let names: Vec<&str> = listed.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["broken", "zen"]);
+        assert!(listed[0].theme.is_err());
+        assert!(listed[1].theme.is_ok());
+
+        assert_eq!(
+            format!("{:?}", loaded.unwrap()),
+            format!("{:?}", Theme::load("nord").unwrap())
+        );
+        let error = format!("{:#}", broken.unwrap_err());
+        assert!(error.contains("Failed to parse theme file"), "{}", error);
+        let error = format!("{:#}", unknown.unwrap_err());
+        assert!(error.contains("Unknown theme: notes"), "{}", error);
+        assert!(error.contains("tokyo-night"), "{}", error);
+        assert!(Theme::user_themes_in(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_exported_themes_round_trip() {
+        for name in Theme::builtin_themes() {
+            let theme = Theme::load(name).unwrap();
+            let exported = theme.to_toml().unwrap();
+            let file: file::ThemeFile = toml::from_str(&exported).unwrap();
+            let imported: Theme = file.into();
+            assert_eq!(
+                format!("{:?}", imported),
+                format!("{:?}", theme),
+                "{}",
+                name
+            );
+        }
+
+        let transparent = Theme::default().with_transparent_background();
+        assert!(transparent.to_toml().unwrap().contains("left = \"reset\""));
+    }
+
+    #[test]
+    fn test_syntax_theme_replaces_only_code_colors() {
+        let light = Theme::load("solarized-light").unwrap();
+        let dark = Theme::load("tokyo-night").unwrap();
+        let mixed = light
+            .clone()
+            .with_syntax(SyntaxTheme::load("tokyo-night").unwrap());
+        assert_eq!(mixed.syntax.keyword, dark.syntax.keyword);
+        assert_eq!(mixed.background_left, light.background_left);
+
+        // A file with only the [syntax] table is enough, and so is a full theme file
+        let path =
+            std::env::temp_dir().join(format!("gitlogue_syntax_{}.toml", std::process::id()));
+        let exported = dark.to_toml().unwrap();
+        let syntax_table = &exported[exported.find("[syntax]").unwrap()..];
+        for contents in [syntax_table, exported.as_str()] {
+            fs::write(&path, contents).unwrap();
+            let syntax = SyntaxTheme::load_from_file(&path).unwrap();
+            assert_eq!(format!("{:?}", syntax), format!("{:?}", dark.syntax));
+        }
+        fs::remove_file(&path).ok();
     }
 }
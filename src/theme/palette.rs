@@ -0,0 +1,206 @@
+use ratatui::style::Color;
+
+/// How many colors the terminal can show. Themes are written in truecolor and
+/// quantized to the nearest palette entry on terminals that can't display it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ColorDepth {
+    #[default]
+    TrueColor,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// The 16 basic ANSI colors
+    Ansi16,
+}
+
+// TERM names of terminals that support truecolor whether or not COLORTERM says so
+const TRUECOLOR_TERMS: [&str; 5] = ["kitty", "alacritty", "foot", "wezterm", "ghostty"];
+
+// Levels of each channel in the 6x6x6 color cube of the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// The basic ANSI colors with their usual xterm values
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+impl ColorDepth {
+    /// Parses `truecolor`, `256` or `16`; `auto` (or anything else) gives `None`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            "256" => Some(Self::Ansi256),
+            "16" => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+
+    /// Guesses the terminal's color support from `COLORTERM` and `TERM`
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default().to_ascii_lowercase();
+        Self::from_env(&var("COLORTERM"), &var("TERM"))
+    }
+
+    // COLORTERM is often not forwarded over SSH, so TERM alone has to tell the terminals
+    // known to support truecolor apart from the few limited to 16 colors
+    fn from_env(colorterm: &str, term: &str) -> Self {
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Self::TrueColor
+        } else if term.is_empty() || term.contains("truecolor") || term.contains("direct") {
+            // Without TERM (e.g. on Windows) there's no way to tell, so assume a modern terminal
+            Self::TrueColor
+        } else if TRUECOLOR_TERMS.iter().any(|name| term.contains(name)) {
+            Self::TrueColor
+        } else if term == "linux" || term == "xterm" || term.starts_with("vt") {
+            Self::Ansi16
+        } else {
+            Self::Ansi256
+        }
+    }
+
+    /// Maps a color to the nearest one this depth can show. Named and reset colors
+    /// are left alone since the terminal already knows them.
+    pub fn quantize(self, color: Color) -> Color {
+        let rgb = match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(index) if self == Self::Ansi16 && index >= 16 => indexed_rgb(index),
+            _ => return color,
+        };
+        match self {
+            Self::TrueColor => color,
+            Self::Ansi256 => Color::Indexed(nearest_256(rgb)),
+            Self::Ansi16 => nearest_16(rgb),
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+// Picks the closer of the nearest color cube entry and the nearest gray ramp entry
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    let nearest_level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (
+        nearest_level(rgb.0),
+        nearest_level(rgb.1),
+        nearest_level(rgb.2),
+    );
+    let cube_index = 16 + 36 * r + 6 * g + b;
+    let cube_rgb = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+
+    if distance(rgb, (gray, gray, gray)) < distance(rgb, cube_rgb) {
+        232 + gray_step
+    } else {
+        cube_index as u8
+    }
+}
+
+fn nearest_16(rgb: (u8, u8, u8)) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+// RGB value of an entry of the 256-color palette beyond the basic 16
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let gray = 8 + 10 * (index - 232);
+        return (gray, gray, gray);
+    }
+    let cube = (index - 16) as usize;
+    (
+        CUBE_LEVELS[cube / 36],
+        CUBE_LEVELS[(cube / 6) % 6],
+        CUBE_LEVELS[cube % 6],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize_to_nearest_palette_entry() {
+        let depth = ColorDepth::Ansi256;
+        assert_eq!(depth.quantize(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(
+            depth.quantize(Color::Rgb(0x28, 0x2a, 0x36)),
+            Color::Indexed(236)
+        );
+        assert_eq!(depth.quantize(Color::Reset), Color::Reset);
+
+        let depth = ColorDepth::Ansi16;
+        assert_eq!(depth.quantize(Color::Rgb(250, 10, 20)), Color::LightRed);
+        assert_eq!(depth.quantize(Color::Rgb(30, 30, 40)), Color::Black);
+        assert_eq!(depth.quantize(Color::Indexed(196)), Color::LightRed);
+
+        let color = Color::Rgb(1, 2, 3);
+        assert_eq!(ColorDepth::TrueColor.quantize(color), color);
+        assert_eq!(ColorDepth::parse("256"), Some(ColorDepth::Ansi256));
+        assert_eq!(ColorDepth::parse("auto"), None);
+    }
+
+    #[test]
+    fn test_detect_from_term_without_colorterm() {
+        for term in [
+            "xterm-kitty",
+            "alacritty",
+            "foot",
+            "wezterm",
+            "xterm-direct",
+            "",
+        ] {
+            assert_eq!(
+                ColorDepth::from_env("", term),
+                ColorDepth::TrueColor,
+                "{}",
+                term
+            );
+        }
+        for term in ["linux", "xterm", "vt100", "vt220"] {
+            assert_eq!(
+                ColorDepth::from_env("", term),
+                ColorDepth::Ansi16,
+                "{}",
+                term
+            );
+        }
+        for term in ["xterm-256color", "screen", "tmux-256color", "rxvt-unicode"] {
+            assert_eq!(
+                ColorDepth::from_env("", term),
+                ColorDepth::Ansi256,
+                "{}",
+                term
+            );
+        }
+        assert_eq!(
+            ColorDepth::from_env("truecolor", "xterm"),
+            ColorDepth::TrueColor
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Ayu Dark inspired color scheme
@@ -13,6 +13,11 @@ pub fn ayu_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 180, 84),
         editor_cursor_char_fg: Color::Rgb(15, 20, 25),
         editor_cursor_line_bg: Color::Rgb(22, 29, 37),
+        editor_truncation_marker: Color::Rgb(62, 68, 82),
+        editor_search_match_bg: Color::Rgb(86, 68, 30),
+        editor_intraline_change: Color::Rgb(66, 83, 55),
+        editor_trailing_whitespace: Color::Rgb(83, 43, 48),
+        editor_bracket_match: Color::Rgb(37, 69, 82),
 
         file_tree_added: Color::Rgb(186, 230, 126),
         file_tree_deleted: Color::Rgb(242, 97, 103),
@@ -35,21 +40,26 @@ pub fn ayu_dark() -> Theme {
         status_date: Color::Rgb(89, 182, 215),
         status_message: Color::Rgb(230, 237, 243),
         status_no_commit: Color::Rgb(62, 68, 82),
+        status_badge_fg: Color::Rgb(10, 14, 20),
+        status_signed: Color::Rgb(186, 230, 126),
 
         separator: Color::Rgb(62, 68, 82),
+        scrollbar: Color::Rgb(62, 68, 82),
 
-        syntax_keyword: Color::Rgb(255, 140, 99),
-        syntax_type: Color::Rgb(229, 181, 103),
-        syntax_function: Color::Rgb(255, 214, 111),
-        syntax_variable: Color::Rgb(230, 237, 243),
-        syntax_string: Color::Rgb(186, 230, 126),
-        syntax_number: Color::Rgb(239, 158, 222),
-        syntax_comment: Color::Rgb(92, 99, 112),
-        syntax_operator: Color::Rgb(242, 151, 24),
-        syntax_punctuation: Color::Rgb(230, 237, 243),
-        syntax_constant: Color::Rgb(89, 182, 215),
-        syntax_parameter: Color::Rgb(255, 214, 111),
-        syntax_property: Color::Rgb(115, 184, 205),
-        syntax_label: Color::Rgb(255, 140, 99),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(255, 140, 99),
+            type_: Color::Rgb(229, 181, 103),
+            function: Color::Rgb(255, 214, 111),
+            variable: Color::Rgb(230, 237, 243),
+            string: Color::Rgb(186, 230, 126),
+            number: Color::Rgb(239, 158, 222),
+            comment: Color::Rgb(92, 99, 112),
+            operator: Color::Rgb(242, 151, 24),
+            punctuation: Color::Rgb(230, 237, 243),
+            constant: Color::Rgb(89, 182, 215),
+            parameter: Color::Rgb(255, 214, 111),
+            property: Color::Rgb(115, 184, 205),
+            label: Color::Rgb(255, 140, 99),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Catppuccin Mocha inspired color scheme
@@ -13,6 +13,11 @@ pub fn catppuccin() -> Theme {
         editor_cursor_char_bg: Color::Rgb(245, 194, 231),
         editor_cursor_char_fg: Color::Rgb(30, 30, 46),
         editor_cursor_line_bg: Color::Rgb(49, 50, 68),
+        editor_truncation_marker: Color::Rgb(108, 112, 134),
+        editor_search_match_bg: Color::Rgb(88, 91, 112),
+        editor_intraline_change: Color::Rgb(71, 89, 80),
+        editor_trailing_whitespace: Color::Rgb(94, 63, 83),
+        editor_bracket_match: Color::Rgb(82, 71, 106),
 
         file_tree_added: Color::Rgb(166, 227, 161),
         file_tree_deleted: Color::Rgb(243, 139, 168),
@@ -35,21 +40,26 @@ pub fn catppuccin() -> Theme {
         status_date: Color::Rgb(137, 180, 250),
         status_message: Color::Rgb(205, 214, 244),
         status_no_commit: Color::Rgb(108, 112, 134),
+        status_badge_fg: Color::Rgb(24, 24, 37),
+        status_signed: Color::Rgb(166, 227, 161),
 
         separator: Color::Rgb(108, 112, 134),
+        scrollbar: Color::Rgb(108, 112, 134),
 
-        syntax_keyword: Color::Rgb(203, 166, 247),
-        syntax_type: Color::Rgb(249, 226, 175),
-        syntax_function: Color::Rgb(137, 180, 250),
-        syntax_variable: Color::Rgb(205, 214, 244),
-        syntax_string: Color::Rgb(166, 227, 161),
-        syntax_number: Color::Rgb(250, 179, 135),
-        syntax_comment: Color::Rgb(108, 112, 134),
-        syntax_operator: Color::Rgb(148, 226, 213),
-        syntax_punctuation: Color::Rgb(186, 194, 222),
-        syntax_constant: Color::Rgb(250, 179, 135),
-        syntax_parameter: Color::Rgb(245, 194, 231),
-        syntax_property: Color::Rgb(166, 227, 161),
-        syntax_label: Color::Rgb(203, 166, 247),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(203, 166, 247),
+            type_: Color::Rgb(249, 226, 175),
+            function: Color::Rgb(137, 180, 250),
+            variable: Color::Rgb(205, 214, 244),
+            string: Color::Rgb(166, 227, 161),
+            number: Color::Rgb(250, 179, 135),
+            comment: Color::Rgb(108, 112, 134),
+            operator: Color::Rgb(148, 226, 213),
+            punctuation: Color::Rgb(186, 194, 222),
+            constant: Color::Rgb(250, 179, 135),
+            parameter: Color::Rgb(245, 194, 231),
+            property: Color::Rgb(166, 227, 161),
+            label: Color::Rgb(203, 166, 247),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Dracula inspired color scheme
@@ -13,6 +13,11 @@ pub fn dracula() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 121, 198),
         editor_cursor_char_fg: Color::Rgb(40, 42, 54),
         editor_cursor_line_bg: Color::Rgb(68, 71, 90),
+        editor_truncation_marker: Color::Rgb(98, 114, 164),
+        editor_search_match_bg: Color::Rgb(96, 92, 48),
+        editor_intraline_change: Color::Rgb(52, 104, 75),
+        editor_trailing_whitespace: Color::Rgb(104, 55, 63),
+        editor_bracket_match: Color::Rgb(85, 74, 112),
 
         file_tree_added: Color::Rgb(80, 250, 123),
         file_tree_deleted: Color::Rgb(255, 85, 85),
@@ -35,21 +40,26 @@ pub fn dracula() -> Theme {
         status_date: Color::Rgb(139, 233, 253),
         status_message: Color::Rgb(248, 248, 242),
         status_no_commit: Color::Rgb(98, 114, 164),
+        status_badge_fg: Color::Rgb(33, 34, 44),
+        status_signed: Color::Rgb(80, 250, 123),
 
         separator: Color::Rgb(98, 114, 164),
+        scrollbar: Color::Rgb(98, 114, 164),
 
-        syntax_keyword: Color::Rgb(255, 121, 198),
-        syntax_type: Color::Rgb(139, 233, 253),
-        syntax_function: Color::Rgb(80, 250, 123),
-        syntax_variable: Color::Rgb(248, 248, 242),
-        syntax_string: Color::Rgb(241, 250, 140),
-        syntax_number: Color::Rgb(189, 147, 249),
-        syntax_comment: Color::Rgb(98, 114, 164),
-        syntax_operator: Color::Rgb(255, 121, 198),
-        syntax_punctuation: Color::Rgb(248, 248, 242),
-        syntax_constant: Color::Rgb(189, 147, 249),
-        syntax_parameter: Color::Rgb(255, 184, 108),
-        syntax_property: Color::Rgb(80, 250, 123),
-        syntax_label: Color::Rgb(255, 121, 198),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(255, 121, 198),
+            type_: Color::Rgb(139, 233, 253),
+            function: Color::Rgb(80, 250, 123),
+            variable: Color::Rgb(248, 248, 242),
+            string: Color::Rgb(241, 250, 140),
+            number: Color::Rgb(189, 147, 249),
+            comment: Color::Rgb(98, 114, 164),
+            operator: Color::Rgb(255, 121, 198),
+            punctuation: Color::Rgb(248, 248, 242),
+            constant: Color::Rgb(189, 147, 249),
+            parameter: Color::Rgb(255, 184, 108),
+            property: Color::Rgb(80, 250, 123),
+            label: Color::Rgb(255, 121, 198),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Everforest Dark inspired color scheme
@@ -13,6 +13,11 @@ pub fn everforest() -> Theme {
         editor_cursor_char_bg: Color::Rgb(131, 192, 146),
         editor_cursor_char_fg: Color::Rgb(45, 52, 46),
         editor_cursor_line_bg: Color::Rgb(57, 64, 58),
+        editor_truncation_marker: Color::Rgb(125, 135, 116),
+        editor_search_match_bg: Color::Rgb(86, 80, 52),
+        editor_intraline_change: Color::Rgb(71, 94, 76),
+        editor_trailing_whitespace: Color::Rgb(100, 74, 71),
+        editor_bracket_match: Color::Rgb(69, 94, 90),
 
         file_tree_added: Color::Rgb(131, 192, 146),
         file_tree_deleted: Color::Rgb(230, 126, 128),
@@ -35,21 +40,26 @@ pub fn everforest() -> Theme {
         status_date: Color::Rgb(125, 192, 192),
         status_message: Color::Rgb(211, 198, 170),
         status_no_commit: Color::Rgb(125, 135, 116),
+        status_badge_fg: Color::Rgb(41, 48, 42),
+        status_signed: Color::Rgb(131, 192, 146),
 
         separator: Color::Rgb(125, 135, 116),
+        scrollbar: Color::Rgb(125, 135, 116),
 
-        syntax_keyword: Color::Rgb(230, 126, 128),
-        syntax_type: Color::Rgb(219, 188, 127),
-        syntax_function: Color::Rgb(131, 192, 146),
-        syntax_variable: Color::Rgb(211, 198, 170),
-        syntax_string: Color::Rgb(219, 188, 127),
-        syntax_number: Color::Rgb(211, 134, 155),
-        syntax_comment: Color::Rgb(125, 135, 116),
-        syntax_operator: Color::Rgb(230, 126, 128),
-        syntax_punctuation: Color::Rgb(180, 166, 137),
-        syntax_constant: Color::Rgb(211, 134, 155),
-        syntax_parameter: Color::Rgb(219, 188, 127),
-        syntax_property: Color::Rgb(125, 192, 192),
-        syntax_label: Color::Rgb(230, 126, 128),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(230, 126, 128),
+            type_: Color::Rgb(219, 188, 127),
+            function: Color::Rgb(131, 192, 146),
+            variable: Color::Rgb(211, 198, 170),
+            string: Color::Rgb(219, 188, 127),
+            number: Color::Rgb(211, 134, 155),
+            comment: Color::Rgb(125, 135, 116),
+            operator: Color::Rgb(230, 126, 128),
+            punctuation: Color::Rgb(180, 166, 137),
+            constant: Color::Rgb(211, 134, 155),
+            parameter: Color::Rgb(219, 188, 127),
+            property: Color::Rgb(125, 192, 192),
+            label: Color::Rgb(230, 126, 128),
+        },
     }
 }
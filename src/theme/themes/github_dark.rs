@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// GitHub Dark inspired color scheme
@@ -13,6 +13,11 @@ pub fn github_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(88, 166, 255),
         editor_cursor_char_fg: Color::Rgb(22, 27, 34),
         editor_cursor_line_bg: Color::Rgb(33, 38, 45),
+        editor_truncation_marker: Color::Rgb(110, 118, 129),
+        editor_search_match_bg: Color::Rgb(88, 72, 22),
+        editor_intraline_change: Color::Rgb(34, 74, 48),
+        editor_trailing_whitespace: Color::Rgb(90, 43, 46),
+        editor_bracket_match: Color::Rgb(42, 69, 100),
 
         file_tree_added: Color::Rgb(63, 185, 80),
         file_tree_deleted: Color::Rgb(248, 81, 73),
@@ -35,21 +40,26 @@ pub fn github_dark() -> Theme {
         status_date: Color::Rgb(88, 166, 255),
         status_message: Color::Rgb(230, 237, 243),
         status_no_commit: Color::Rgb(110, 118, 129),
+        status_badge_fg: Color::Rgb(13, 17, 23),
+        status_signed: Color::Rgb(63, 185, 80),
 
         separator: Color::Rgb(48, 54, 61),
+        scrollbar: Color::Rgb(110, 118, 129),
 
-        syntax_keyword: Color::Rgb(255, 123, 114),
-        syntax_type: Color::Rgb(255, 186, 77),
-        syntax_function: Color::Rgb(210, 153, 255),
-        syntax_variable: Color::Rgb(201, 209, 217),
-        syntax_string: Color::Rgb(168, 219, 181),
-        syntax_number: Color::Rgb(121, 192, 255),
-        syntax_comment: Color::Rgb(139, 148, 158),
-        syntax_operator: Color::Rgb(255, 123, 114),
-        syntax_punctuation: Color::Rgb(201, 209, 217),
-        syntax_constant: Color::Rgb(121, 192, 255),
-        syntax_parameter: Color::Rgb(255, 186, 77),
-        syntax_property: Color::Rgb(121, 192, 255),
-        syntax_label: Color::Rgb(210, 153, 255),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(255, 123, 114),
+            type_: Color::Rgb(255, 186, 77),
+            function: Color::Rgb(210, 153, 255),
+            variable: Color::Rgb(201, 209, 217),
+            string: Color::Rgb(168, 219, 181),
+            number: Color::Rgb(121, 192, 255),
+            comment: Color::Rgb(139, 148, 158),
+            operator: Color::Rgb(255, 123, 114),
+            punctuation: Color::Rgb(201, 209, 217),
+            constant: Color::Rgb(121, 192, 255),
+            parameter: Color::Rgb(255, 186, 77),
+            property: Color::Rgb(121, 192, 255),
+            label: Color::Rgb(210, 153, 255),
+        },
     }
 }
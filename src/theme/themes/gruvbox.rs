@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Gruvbox Dark inspired color scheme
@@ -13,6 +13,11 @@ pub fn gruvbox() -> Theme {
         editor_cursor_char_bg: Color::Rgb(254, 128, 25),
         editor_cursor_char_fg: Color::Rgb(40, 40, 40),
         editor_cursor_line_bg: Color::Rgb(60, 56, 54),
+        editor_truncation_marker: Color::Rgb(146, 131, 116),
+        editor_search_match_bg: Color::Rgb(102, 92, 84),
+        editor_intraline_change: Color::Rgb(83, 84, 39),
+        editor_trailing_whitespace: Color::Rgb(103, 50, 44),
+        editor_bracket_match: Color::Rgb(67, 78, 74),
 
         file_tree_added: Color::Rgb(184, 187, 38),
         file_tree_deleted: Color::Rgb(251, 73, 52),
@@ -35,21 +40,26 @@ pub fn gruvbox() -> Theme {
         status_date: Color::Rgb(131, 165, 152),
         status_message: Color::Rgb(235, 219, 178),
         status_no_commit: Color::Rgb(146, 131, 116),
+        status_badge_fg: Color::Rgb(29, 32, 33),
+        status_signed: Color::Rgb(184, 187, 38),
 
         separator: Color::Rgb(146, 131, 116),
+        scrollbar: Color::Rgb(146, 131, 116),
 
-        syntax_keyword: Color::Rgb(251, 73, 52),
-        syntax_type: Color::Rgb(250, 189, 47),
-        syntax_function: Color::Rgb(184, 187, 38),
-        syntax_variable: Color::Rgb(235, 219, 178),
-        syntax_string: Color::Rgb(184, 187, 38),
-        syntax_number: Color::Rgb(211, 134, 155),
-        syntax_comment: Color::Rgb(146, 131, 116),
-        syntax_operator: Color::Rgb(251, 73, 52),
-        syntax_punctuation: Color::Rgb(213, 196, 161),
-        syntax_constant: Color::Rgb(211, 134, 155),
-        syntax_parameter: Color::Rgb(254, 128, 25),
-        syntax_property: Color::Rgb(184, 187, 38),
-        syntax_label: Color::Rgb(251, 73, 52),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(251, 73, 52),
+            type_: Color::Rgb(250, 189, 47),
+            function: Color::Rgb(184, 187, 38),
+            variable: Color::Rgb(235, 219, 178),
+            string: Color::Rgb(184, 187, 38),
+            number: Color::Rgb(211, 134, 155),
+            comment: Color::Rgb(146, 131, 116),
+            operator: Color::Rgb(251, 73, 52),
+            punctuation: Color::Rgb(213, 196, 161),
+            constant: Color::Rgb(211, 134, 155),
+            parameter: Color::Rgb(254, 128, 25),
+            property: Color::Rgb(184, 187, 38),
+            label: Color::Rgb(251, 73, 52),
+        },
     }
 }
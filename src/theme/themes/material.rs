@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Material Theme inspired color scheme
@@ -13,6 +13,11 @@ pub fn material() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 203, 107),
         editor_cursor_char_fg: Color::Rgb(38, 50, 56),
         editor_cursor_line_bg: Color::Rgb(55, 71, 79),
+        editor_truncation_marker: Color::Rgb(84, 110, 122),
+        editor_search_match_bg: Color::Rgb(84, 80, 50),
+        editor_intraline_change: Color::Rgb(85, 105, 82),
+        editor_trailing_whitespace: Color::Rgb(103, 60, 73),
+        editor_bracket_match: Color::Rgb(68, 101, 116),
 
         file_tree_added: Color::Rgb(195, 232, 141),
         file_tree_deleted: Color::Rgb(255, 83, 112),
@@ -35,21 +40,26 @@ pub fn material() -> Theme {
         status_date: Color::Rgb(128, 203, 196),
         status_message: Color::Rgb(238, 255, 255),
         status_no_commit: Color::Rgb(84, 110, 122),
+        status_badge_fg: Color::Rgb(38, 50, 56),
+        status_signed: Color::Rgb(195, 232, 141),
 
         separator: Color::Rgb(84, 110, 122),
+        scrollbar: Color::Rgb(84, 110, 122),
 
-        syntax_keyword: Color::Rgb(199, 146, 234),
-        syntax_type: Color::Rgb(255, 203, 107),
-        syntax_function: Color::Rgb(130, 170, 255),
-        syntax_variable: Color::Rgb(238, 255, 255),
-        syntax_string: Color::Rgb(195, 232, 141),
-        syntax_number: Color::Rgb(247, 140, 108),
-        syntax_comment: Color::Rgb(84, 110, 122),
-        syntax_operator: Color::Rgb(137, 221, 255),
-        syntax_punctuation: Color::Rgb(144, 164, 174),
-        syntax_constant: Color::Rgb(137, 221, 255),
-        syntax_parameter: Color::Rgb(255, 203, 107),
-        syntax_property: Color::Rgb(128, 203, 196),
-        syntax_label: Color::Rgb(199, 146, 234),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(199, 146, 234),
+            type_: Color::Rgb(255, 203, 107),
+            function: Color::Rgb(130, 170, 255),
+            variable: Color::Rgb(238, 255, 255),
+            string: Color::Rgb(195, 232, 141),
+            number: Color::Rgb(247, 140, 108),
+            comment: Color::Rgb(84, 110, 122),
+            operator: Color::Rgb(137, 221, 255),
+            punctuation: Color::Rgb(144, 164, 174),
+            constant: Color::Rgb(137, 221, 255),
+            parameter: Color::Rgb(255, 203, 107),
+            property: Color::Rgb(128, 203, 196),
+            label: Color::Rgb(199, 146, 234),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Monokai inspired color scheme
@@ -13,6 +13,11 @@ pub fn monokai() -> Theme {
         editor_cursor_char_bg: Color::Rgb(253, 151, 31),
         editor_cursor_char_fg: Color::Rgb(39, 40, 34),
         editor_cursor_line_bg: Color::Rgb(51, 51, 45),
+        editor_truncation_marker: Color::Rgb(117, 113, 94),
+        editor_search_match_bg: Color::Rgb(84, 82, 40),
+        editor_intraline_change: Color::Rgb(77, 96, 38),
+        editor_trailing_whitespace: Color::Rgb(102, 39, 58),
+        editor_bracket_match: Color::Rgb(80, 67, 100),
 
         file_tree_added: Color::Rgb(166, 226, 46),
         file_tree_deleted: Color::Rgb(249, 38, 114),
@@ -35,21 +40,26 @@ pub fn monokai() -> Theme {
         status_date: Color::Rgb(102, 217, 239),
         status_message: Color::Rgb(248, 248, 242),
         status_no_commit: Color::Rgb(117, 113, 94),
+        status_badge_fg: Color::Rgb(30, 30, 30),
+        status_signed: Color::Rgb(166, 226, 46),
 
         separator: Color::Rgb(117, 113, 94),
+        scrollbar: Color::Rgb(117, 113, 94),
 
-        syntax_keyword: Color::Rgb(249, 38, 114),
-        syntax_type: Color::Rgb(102, 217, 239),
-        syntax_function: Color::Rgb(166, 226, 46),
-        syntax_variable: Color::Rgb(248, 248, 242),
-        syntax_string: Color::Rgb(230, 219, 116),
-        syntax_number: Color::Rgb(174, 129, 255),
-        syntax_comment: Color::Rgb(117, 113, 94),
-        syntax_operator: Color::Rgb(249, 38, 114),
-        syntax_punctuation: Color::Rgb(248, 248, 242),
-        syntax_constant: Color::Rgb(174, 129, 255),
-        syntax_parameter: Color::Rgb(253, 151, 31),
-        syntax_property: Color::Rgb(166, 226, 46),
-        syntax_label: Color::Rgb(249, 38, 114),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(249, 38, 114),
+            type_: Color::Rgb(102, 217, 239),
+            function: Color::Rgb(166, 226, 46),
+            variable: Color::Rgb(248, 248, 242),
+            string: Color::Rgb(230, 219, 116),
+            number: Color::Rgb(174, 129, 255),
+            comment: Color::Rgb(117, 113, 94),
+            operator: Color::Rgb(249, 38, 114),
+            punctuation: Color::Rgb(248, 248, 242),
+            constant: Color::Rgb(174, 129, 255),
+            parameter: Color::Rgb(253, 151, 31),
+            property: Color::Rgb(166, 226, 46),
+            label: Color::Rgb(249, 38, 114),
+        },
     }
 }
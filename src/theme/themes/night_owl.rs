@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Night Owl inspired color scheme
@@ -13,6 +13,11 @@ pub fn night_owl() -> Theme {
         editor_cursor_char_bg: Color::Rgb(122, 162, 247),
         editor_cursor_char_fg: Color::Rgb(1, 22, 39),
         editor_cursor_line_bg: Color::Rgb(1, 41, 72),
+        editor_truncation_marker: Color::Rgb(78, 121, 147),
+        editor_search_match_bg: Color::Rgb(24, 74, 115),
+        editor_intraline_change: Color::Rgb(53, 81, 58),
+        editor_trailing_whitespace: Color::Rgb(72, 40, 51),
+        editor_bracket_match: Color::Rgb(40, 66, 104),
 
         file_tree_added: Color::Rgb(173, 219, 103),
         file_tree_deleted: Color::Rgb(239, 83, 80),
@@ -35,21 +40,26 @@ pub fn night_owl() -> Theme {
         status_date: Color::Rgb(122, 162, 247),
         status_message: Color::Rgb(214, 222, 235),
         status_no_commit: Color::Rgb(78, 121, 147),
+        status_badge_fg: Color::Rgb(1, 22, 39),
+        status_signed: Color::Rgb(173, 219, 103),
 
         separator: Color::Rgb(1, 76, 134),
+        scrollbar: Color::Rgb(78, 121, 147),
 
-        syntax_keyword: Color::Rgb(199, 146, 234),
-        syntax_type: Color::Rgb(255, 203, 107),
-        syntax_function: Color::Rgb(130, 170, 255),
-        syntax_variable: Color::Rgb(214, 222, 235),
-        syntax_string: Color::Rgb(173, 219, 103),
-        syntax_number: Color::Rgb(247, 140, 108),
-        syntax_comment: Color::Rgb(78, 121, 147),
-        syntax_operator: Color::Rgb(199, 146, 234),
-        syntax_punctuation: Color::Rgb(127, 132, 142),
-        syntax_constant: Color::Rgb(128, 203, 196),
-        syntax_parameter: Color::Rgb(255, 203, 107),
-        syntax_property: Color::Rgb(122, 162, 247),
-        syntax_label: Color::Rgb(255, 88, 116),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(199, 146, 234),
+            type_: Color::Rgb(255, 203, 107),
+            function: Color::Rgb(130, 170, 255),
+            variable: Color::Rgb(214, 222, 235),
+            string: Color::Rgb(173, 219, 103),
+            number: Color::Rgb(247, 140, 108),
+            comment: Color::Rgb(78, 121, 147),
+            operator: Color::Rgb(199, 146, 234),
+            punctuation: Color::Rgb(127, 132, 142),
+            constant: Color::Rgb(128, 203, 196),
+            parameter: Color::Rgb(255, 203, 107),
+            property: Color::Rgb(122, 162, 247),
+            label: Color::Rgb(255, 88, 116),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Nord inspired color scheme
@@ -13,6 +13,11 @@ pub fn nord() -> Theme {
         editor_cursor_char_bg: Color::Rgb(136, 192, 208),
         editor_cursor_char_fg: Color::Rgb(46, 52, 64),
         editor_cursor_line_bg: Color::Rgb(59, 66, 82),
+        editor_truncation_marker: Color::Rgb(76, 86, 106),
+        editor_search_match_bg: Color::Rgb(76, 86, 106),
+        editor_intraline_change: Color::Rgb(81, 93, 87),
+        editor_trailing_whitespace: Color::Rgb(90, 66, 77),
+        editor_bracket_match: Color::Rgb(73, 94, 107),
 
         file_tree_added: Color::Rgb(163, 190, 140),
         file_tree_deleted: Color::Rgb(191, 97, 106),
@@ -35,21 +40,26 @@ pub fn nord() -> Theme {
         status_date: Color::Rgb(129, 161, 193),
         status_message: Color::Rgb(236, 239, 244),
         status_no_commit: Color::Rgb(76, 86, 106),
+        status_badge_fg: Color::Rgb(36, 42, 56),
+        status_signed: Color::Rgb(163, 190, 140),
 
         separator: Color::Rgb(76, 86, 106),
+        scrollbar: Color::Rgb(76, 86, 106),
 
-        syntax_keyword: Color::Rgb(180, 142, 173),
-        syntax_type: Color::Rgb(136, 192, 208),
-        syntax_function: Color::Rgb(136, 192, 208),
-        syntax_variable: Color::Rgb(236, 239, 244),
-        syntax_string: Color::Rgb(163, 190, 140),
-        syntax_number: Color::Rgb(180, 142, 173),
-        syntax_comment: Color::Rgb(76, 86, 106),
-        syntax_operator: Color::Rgb(136, 192, 208),
-        syntax_punctuation: Color::Rgb(216, 222, 233),
-        syntax_constant: Color::Rgb(180, 142, 173),
-        syntax_parameter: Color::Rgb(235, 203, 139),
-        syntax_property: Color::Rgb(163, 190, 140),
-        syntax_label: Color::Rgb(180, 142, 173),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(180, 142, 173),
+            type_: Color::Rgb(136, 192, 208),
+            function: Color::Rgb(136, 192, 208),
+            variable: Color::Rgb(236, 239, 244),
+            string: Color::Rgb(163, 190, 140),
+            number: Color::Rgb(180, 142, 173),
+            comment: Color::Rgb(76, 86, 106),
+            operator: Color::Rgb(136, 192, 208),
+            punctuation: Color::Rgb(216, 222, 233),
+            constant: Color::Rgb(180, 142, 173),
+            parameter: Color::Rgb(235, 203, 139),
+            property: Color::Rgb(163, 190, 140),
+            label: Color::Rgb(180, 142, 173),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// One Dark inspired color scheme
@@ -13,6 +13,11 @@ pub fn one_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(97, 175, 239),
         editor_cursor_char_fg: Color::Rgb(40, 44, 52),
         editor_cursor_line_bg: Color::Rgb(47, 52, 61),
+        editor_truncation_marker: Color::Rgb(92, 99, 112),
+        editor_search_match_bg: Color::Rgb(92, 86, 58),
+        editor_intraline_change: Color::Rgb(74, 89, 73),
+        editor_trailing_whitespace: Color::Rgb(95, 63, 72),
+        editor_bracket_match: Color::Rgb(57, 83, 108),
 
         file_tree_added: Color::Rgb(152, 195, 121),
         file_tree_deleted: Color::Rgb(224, 108, 117),
@@ -35,21 +40,26 @@ pub fn one_dark() -> Theme {
         status_date: Color::Rgb(97, 175, 239),
         status_message: Color::Rgb(220, 223, 228),
         status_no_commit: Color::Rgb(92, 99, 112),
+        status_badge_fg: Color::Rgb(33, 37, 43),
+        status_signed: Color::Rgb(152, 195, 121),
 
         separator: Color::Rgb(92, 99, 112),
+        scrollbar: Color::Rgb(92, 99, 112),
 
-        syntax_keyword: Color::Rgb(198, 120, 221),
-        syntax_type: Color::Rgb(229, 192, 123),
-        syntax_function: Color::Rgb(97, 175, 239),
-        syntax_variable: Color::Rgb(220, 223, 228),
-        syntax_string: Color::Rgb(152, 195, 121),
-        syntax_number: Color::Rgb(209, 154, 102),
-        syntax_comment: Color::Rgb(92, 99, 112),
-        syntax_operator: Color::Rgb(198, 120, 221),
-        syntax_punctuation: Color::Rgb(171, 178, 191),
-        syntax_constant: Color::Rgb(209, 154, 102),
-        syntax_parameter: Color::Rgb(229, 192, 123),
-        syntax_property: Color::Rgb(152, 195, 121),
-        syntax_label: Color::Rgb(198, 120, 221),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(198, 120, 221),
+            type_: Color::Rgb(229, 192, 123),
+            function: Color::Rgb(97, 175, 239),
+            variable: Color::Rgb(220, 223, 228),
+            string: Color::Rgb(152, 195, 121),
+            number: Color::Rgb(209, 154, 102),
+            comment: Color::Rgb(92, 99, 112),
+            operator: Color::Rgb(198, 120, 221),
+            punctuation: Color::Rgb(171, 178, 191),
+            constant: Color::Rgb(209, 154, 102),
+            parameter: Color::Rgb(229, 192, 123),
+            property: Color::Rgb(152, 195, 121),
+            label: Color::Rgb(198, 120, 221),
+        },
     }
 }
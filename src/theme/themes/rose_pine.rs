@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Rose Pine inspired color scheme
@@ -13,6 +13,11 @@ pub fn rose_pine() -> Theme {
         editor_cursor_char_bg: Color::Rgb(235, 188, 186),
         editor_cursor_char_fg: Color::Rgb(35, 33, 54),
         editor_cursor_line_bg: Color::Rgb(42, 39, 63),
+        editor_truncation_marker: Color::Rgb(110, 106, 134),
+        editor_search_match_bg: Color::Rgb(82, 79, 103),
+        editor_intraline_change: Color::Rgb(71, 85, 103),
+        editor_trailing_whitespace: Color::Rgb(95, 56, 82),
+        editor_bracket_match: Color::Rgb(83, 73, 107),
 
         file_tree_added: Color::Rgb(156, 207, 216),
         file_tree_deleted: Color::Rgb(235, 111, 146),
@@ -35,21 +40,26 @@ pub fn rose_pine() -> Theme {
         status_date: Color::Rgb(196, 167, 231),
         status_message: Color::Rgb(224, 222, 244),
         status_no_commit: Color::Rgb(110, 106, 134),
+        status_badge_fg: Color::Rgb(25, 23, 36),
+        status_signed: Color::Rgb(156, 207, 216),
 
         separator: Color::Rgb(110, 106, 134),
+        scrollbar: Color::Rgb(110, 106, 134),
 
-        syntax_keyword: Color::Rgb(196, 167, 231),
-        syntax_type: Color::Rgb(246, 193, 119),
-        syntax_function: Color::Rgb(156, 207, 216),
-        syntax_variable: Color::Rgb(224, 222, 244),
-        syntax_string: Color::Rgb(246, 193, 119),
-        syntax_number: Color::Rgb(234, 154, 151),
-        syntax_comment: Color::Rgb(110, 106, 134),
-        syntax_operator: Color::Rgb(235, 111, 146),
-        syntax_punctuation: Color::Rgb(144, 140, 170),
-        syntax_constant: Color::Rgb(235, 188, 186),
-        syntax_parameter: Color::Rgb(246, 193, 119),
-        syntax_property: Color::Rgb(156, 207, 216),
-        syntax_label: Color::Rgb(196, 167, 231),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(196, 167, 231),
+            type_: Color::Rgb(246, 193, 119),
+            function: Color::Rgb(156, 207, 216),
+            variable: Color::Rgb(224, 222, 244),
+            string: Color::Rgb(246, 193, 119),
+            number: Color::Rgb(234, 154, 151),
+            comment: Color::Rgb(110, 106, 134),
+            operator: Color::Rgb(235, 111, 146),
+            punctuation: Color::Rgb(144, 140, 170),
+            constant: Color::Rgb(235, 188, 186),
+            parameter: Color::Rgb(246, 193, 119),
+            property: Color::Rgb(156, 207, 216),
+            label: Color::Rgb(196, 167, 231),
+        },
     }
 }
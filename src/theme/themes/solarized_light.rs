@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Solarized Light color scheme
@@ -13,6 +13,11 @@ pub fn solarized_light() -> Theme {
         editor_cursor_char_bg: Color::Rgb(38, 139, 210),
         editor_cursor_char_fg: Color::Rgb(253, 246, 227),
         editor_cursor_line_bg: Color::Rgb(238, 232, 213),
+        editor_truncation_marker: Color::Rgb(147, 161, 161),
+        editor_search_match_bg: Color::Rgb(238, 216, 140),
+        editor_intraline_change: Color::Rgb(217, 218, 159),
+        editor_trailing_whitespace: Color::Rgb(243, 187, 173),
+        editor_bracket_match: Color::Rgb(190, 220, 204),
 
         file_tree_added: Color::Rgb(133, 153, 0),
         file_tree_deleted: Color::Rgb(220, 50, 47),
@@ -35,21 +40,26 @@ pub fn solarized_light() -> Theme {
         status_date: Color::Rgb(38, 139, 210),
         status_message: Color::Rgb(7, 54, 66),
         status_no_commit: Color::Rgb(147, 161, 161),
+        status_badge_fg: Color::Rgb(250, 245, 225),
+        status_signed: Color::Rgb(133, 153, 0),
 
         separator: Color::Rgb(147, 161, 161),
+        scrollbar: Color::Rgb(147, 161, 161),
 
-        syntax_keyword: Color::Rgb(203, 75, 22),
-        syntax_type: Color::Rgb(181, 137, 0),
-        syntax_function: Color::Rgb(38, 139, 210),
-        syntax_variable: Color::Rgb(101, 123, 131),
-        syntax_string: Color::Rgb(42, 161, 152),
-        syntax_number: Color::Rgb(108, 113, 196),
-        syntax_comment: Color::Rgb(147, 161, 161),
-        syntax_operator: Color::Rgb(203, 75, 22),
-        syntax_punctuation: Color::Rgb(101, 123, 131),
-        syntax_constant: Color::Rgb(108, 113, 196),
-        syntax_parameter: Color::Rgb(181, 137, 0),
-        syntax_property: Color::Rgb(42, 161, 152),
-        syntax_label: Color::Rgb(211, 54, 130),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(203, 75, 22),
+            type_: Color::Rgb(181, 137, 0),
+            function: Color::Rgb(38, 139, 210),
+            variable: Color::Rgb(101, 123, 131),
+            string: Color::Rgb(42, 161, 152),
+            number: Color::Rgb(108, 113, 196),
+            comment: Color::Rgb(147, 161, 161),
+            operator: Color::Rgb(203, 75, 22),
+            punctuation: Color::Rgb(101, 123, 131),
+            constant: Color::Rgb(108, 113, 196),
+            parameter: Color::Rgb(181, 137, 0),
+            property: Color::Rgb(42, 161, 152),
+            label: Color::Rgb(211, 54, 130),
+        },
     }
 }
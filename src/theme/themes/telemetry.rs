@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Telemetry - "Encrypt the signal; the void is listening."
@@ -14,6 +14,11 @@ pub fn telemetry() -> Theme {
         editor_cursor_char_bg: Color::Rgb(244, 174, 89),
         editor_cursor_char_fg: Color::Rgb(15, 27, 29),
         editor_cursor_line_bg: Color::Rgb(29, 46, 49),
+        editor_truncation_marker: Color::Rgb(107, 141, 148),
+        editor_search_match_bg: Color::Rgb(70, 60, 34),
+        editor_intraline_change: Color::Rgb(58, 75, 67),
+        editor_trailing_whitespace: Color::Rgb(74, 58, 56),
+        editor_bracket_match: Color::Rgb(74, 58, 56),
 
         file_tree_added: Color::Rgb(141, 172, 139),
         file_tree_deleted: Color::Rgb(194, 113, 102),
@@ -36,21 +41,26 @@ pub fn telemetry() -> Theme {
         status_date: Color::Rgb(147, 191, 194),
         status_message: Color::Rgb(154, 191, 190),
         status_no_commit: Color::Rgb(107, 141, 148),
+        status_badge_fg: Color::Rgb(15, 27, 29),
+        status_signed: Color::Rgb(141, 172, 139),
 
         separator: Color::Rgb(107, 141, 148),
+        scrollbar: Color::Rgb(107, 141, 148),
 
-        syntax_keyword: Color::Rgb(194, 113, 102),
-        syntax_type: Color::Rgb(212, 154, 79),
-        syntax_function: Color::Rgb(212, 154, 79),
-        syntax_variable: Color::Rgb(154, 191, 190),
-        syntax_string: Color::Rgb(141, 172, 139),
-        syntax_number: Color::Rgb(196, 132, 122),
-        syntax_comment: Color::Rgb(107, 141, 148),
-        syntax_operator: Color::Rgb(143, 181, 179),
-        syntax_punctuation: Color::Rgb(122, 154, 153),
-        syntax_constant: Color::Rgb(196, 132, 122),
-        syntax_parameter: Color::Rgb(122, 154, 153),
-        syntax_property: Color::Rgb(154, 191, 190),
-        syntax_label: Color::Rgb(194, 113, 102),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(194, 113, 102),
+            type_: Color::Rgb(212, 154, 79),
+            function: Color::Rgb(212, 154, 79),
+            variable: Color::Rgb(154, 191, 190),
+            string: Color::Rgb(141, 172, 139),
+            number: Color::Rgb(196, 132, 122),
+            comment: Color::Rgb(107, 141, 148),
+            operator: Color::Rgb(143, 181, 179),
+            punctuation: Color::Rgb(122, 154, 153),
+            constant: Color::Rgb(196, 132, 122),
+            parameter: Color::Rgb(122, 154, 153),
+            property: Color::Rgb(154, 191, 190),
+            label: Color::Rgb(194, 113, 102),
+        },
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxTheme, Theme};
 use ratatui::style::Color;
 
 /// Tokyo Night inspired color scheme
@@ -13,6 +13,11 @@ pub fn tokyo_night() -> Theme {
         editor_cursor_char_bg: Color::Rgb(122, 162, 247),
         editor_cursor_char_fg: Color::Rgb(26, 27, 38),
         editor_cursor_line_bg: Color::Rgb(42, 47, 68),
+        editor_truncation_marker: Color::Rgb(86, 95, 137),
+        editor_search_match_bg: Color::Rgb(61, 89, 161),
+        editor_intraline_change: Color::Rgb(66, 81, 58),
+        editor_trailing_whitespace: Color::Rgb(92, 54, 69),
+        editor_bracket_match: Color::Rgb(55, 68, 101),
 
         file_tree_added: Color::Rgb(158, 206, 106),
         file_tree_deleted: Color::Rgb(247, 118, 142),
@@ -35,21 +40,26 @@ pub fn tokyo_night() -> Theme {
         status_date: Color::Rgb(122, 162, 247),
         status_message: Color::Rgb(192, 202, 245),
         status_no_commit: Color::Rgb(86, 95, 137),
+        status_badge_fg: Color::Rgb(30, 34, 54),
+        status_signed: Color::Rgb(158, 206, 106),
 
         separator: Color::Rgb(86, 95, 137),
+        scrollbar: Color::Rgb(86, 95, 137),
 
-        syntax_keyword: Color::Rgb(187, 154, 247),
-        syntax_type: Color::Rgb(125, 207, 255),
-        syntax_function: Color::Rgb(122, 162, 247),
-        syntax_variable: Color::Rgb(192, 202, 245),
-        syntax_string: Color::Rgb(158, 206, 106),
-        syntax_number: Color::Rgb(255, 158, 100),
-        syntax_comment: Color::Rgb(86, 95, 137),
-        syntax_operator: Color::Rgb(125, 207, 255),
-        syntax_punctuation: Color::Rgb(140, 148, 184),
-        syntax_constant: Color::Rgb(255, 158, 100),
-        syntax_parameter: Color::Rgb(255, 213, 128),
-        syntax_property: Color::Rgb(158, 206, 106),
-        syntax_label: Color::Rgb(187, 154, 247),
+        syntax: SyntaxTheme {
+            keyword: Color::Rgb(187, 154, 247),
+            type_: Color::Rgb(125, 207, 255),
+            function: Color::Rgb(122, 162, 247),
+            variable: Color::Rgb(192, 202, 245),
+            string: Color::Rgb(158, 206, 106),
+            number: Color::Rgb(255, 158, 100),
+            comment: Color::Rgb(86, 95, 137),
+            operator: Color::Rgb(125, 207, 255),
+            punctuation: Color::Rgb(140, 148, 184),
+            constant: Color::Rgb(255, 158, 100),
+            parameter: Color::Rgb(255, 213, 128),
+            property: Color::Rgb(158, 206, 106),
+            label: Color::Rgb(187, 154, 247),
+        },
     }
 }
@@ -1,37 +1,88 @@
-use std::io;
+use std::collections::{BTreeSet, HashSet};
+use std::io::{self, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Position, Rect, Size},
     style::Style,
-    text::{Line, Span},
-    widgets::{Block, Borders, Padding, Paragraph},
-    Frame, Terminal,
+    text::Line,
+    widgets::Paragraph,
+    Frame, Terminal, TerminalOptions, Viewport,
 };
-use unicode_width::UnicodeWidthStr;
 
-use crate::animation::{AnimationEngine, SpeedRule};
+use crate::animation::{AnimationEngine, FileFocus, SpeedRule};
 use crate::git::{CommitMetadata, DiffMode, GitRepository};
-use crate::panes::{EditorPane, FileTreePane, StatusBarPane, TerminalPane};
-use crate::theme::Theme;
-use crate::PlaybackOrder;
+use crate::panes::{
+    DateDisplay, EditorPane, FileTreePane, OverlayPane, Progress, StatusBarPane, TerminalPane,
+};
+use crate::theme::{ColorDepth, Theme};
+
+// Consecutive commits with only excluded files skipped before playing one anyway
+const MAX_EXCLUDED_ONLY_SKIPS: usize = 20;
+
+// How long the status bar shows the speed after it's changed with +/-
+const SPEED_NOTICE_DURATION: Duration = Duration::from_millis(1500);
+
+// Lines scrolled per mouse wheel notch
+const MOUSE_SCROLL_LINES: isize = 3;
+
+// How often HEAD is checked for new commits with --watch
+const WATCH_INTERVAL: Duration = Duration::from_secs(3);
+
+// xterm window title stack operations (CSI 22/23 ; 0 t)
+const SAVE_TITLE: &str = "\x1b[22;0t";
+const RESTORE_TITLE: &str = "\x1b[23;0t";
 
 #[derive(Debug, Clone, PartialEq)]
 enum UIState {
     Playing,
-    WaitingForNext { resume_at: Instant },
+    WaitingForNext {
+        resume_at: Instant,
+    },
+    /// End of history reached with --watch; HEAD is checked again at `check_at`
+    Watching {
+        check_at: Instant,
+    },
+    /// End of history reached; the last frame stays up until a key is pressed
+    Holding,
     Finished,
 }
 
+/// Defines the order in which commits are played back during animation.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum PlaybackOrder {
+    #[default]
+    Random,
+    Asc,
+    Desc,
+}
+
+/// What happens when playback runs out of commits without `--loop`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum EndBehavior {
+    /// Quit, reporting that the end of history was reached
+    #[default]
+    Exit,
+    /// Start over from the first commit
+    Loop,
+    /// Keep the last frame on screen until a key is pressed
+    Hold,
+}
+
 /// Main UI controller for the gitlogue terminal interface.
 pub struct UI<'a> {
     state: UIState,
@@ -40,8 +91,11 @@ pub struct UI<'a> {
     editor: EditorPane,
     terminal: TerminalPane,
     status_bar: StatusBarPane,
+    overlay: OverlayPane,
     engine: AnimationEngine,
     repo: Option<&'a GitRepository>,
+    repositories: Vec<(String, &'a GitRepository)>,
+    repo_index: usize,
     should_exit: Arc<AtomicBool>,
     theme: Theme,
     order: PlaybackOrder,
@@ -49,6 +103,38 @@ pub struct UI<'a> {
     commit_spec: Option<String>,
     is_range_mode: bool,
     diff_mode: Option<DiffMode>,
+    set_terminal_title: bool,
+    title_saved: bool,
+    speed_notice_until: Option<Instant>,
+    needs_redraw: bool,
+    // Growth mode, where the file tree lists each commit's whole codebase
+    growth: bool,
+    on_end: EndBehavior,
+    history_ended: bool,
+    progress: Option<Progress>,
+    // The commit being played, kept to restart it with `r`
+    current_commit: Option<CommitMetadata>,
+    // Hashes of the commits shown so far in random playback
+    played_commits: HashSet<String>,
+    // Whether the file tree and commit info column is shown, toggled with `t`
+    show_sidebar: bool,
+    // Colors the terminal can show; every drawn frame is quantized to it
+    color_depth: ColorDepth,
+    // Keep checking for new commits once history is played, instead of ending
+    watch: bool,
+    // Whether ] and [ wrap around past the last and first file of a commit
+    wrap_file_jumps: bool,
+    // Show each file's final content at once, moving on after a dwell time or a key
+    static_view: bool,
+    // How long a finished commit stays on screen before the next one, if set
+    hold_ms: Option<u64>,
+    // Where the panes were last drawn, to tell which one the mouse is over
+    file_tree_area: Rect,
+    editor_area: Rect,
+    // Query typed after `/`; only files whose path contains it are listed and animated
+    file_filter: Option<String>,
+    // Whether keys are being typed into the file filter
+    filter_input: bool,
 }
 
 impl<'a> UI<'a> {
@@ -74,11 +160,14 @@ impl<'a> UI<'a> {
             state: UIState::Playing,
             speed_ms,
             file_tree: FileTreePane::new(),
-            editor: EditorPane,
+            editor: EditorPane::new(),
             terminal: TerminalPane,
-            status_bar: StatusBarPane,
+            status_bar: StatusBarPane::default(),
+            overlay: OverlayPane,
             engine,
             repo,
+            repositories: Vec::new(),
+            repo_index: 0,
             should_exit,
             theme,
             order,
@@ -86,6 +175,26 @@ impl<'a> UI<'a> {
             commit_spec,
             is_range_mode,
             diff_mode: None,
+            set_terminal_title: false,
+            title_saved: false,
+            speed_notice_until: None,
+            needs_redraw: false,
+            growth: false,
+            on_end: EndBehavior::default(),
+            history_ended: false,
+            progress: None,
+            current_commit: None,
+            played_commits: HashSet::new(),
+            show_sidebar: true,
+            color_depth: ColorDepth::default(),
+            watch: false,
+            wrap_file_jumps: true,
+            static_view: false,
+            hold_ms: None,
+            file_tree_area: Rect::default(),
+            editor_area: Rect::default(),
+            file_filter: None,
+            filter_input: false,
         }
     }
 
@@ -94,6 +203,176 @@ impl<'a> UI<'a> {
         self.diff_mode = mode;
     }
 
+    /// Enables the changes-only view, which animates hunk lines without surrounding content.
+    pub fn set_changes_only(&mut self, changes_only: bool) {
+        self.engine.set_changes_only(changes_only);
+    }
+
+    /// Plays files backwards, removing added lines and restoring deleted ones.
+    pub fn set_reverse_file(&mut self, reverse_file: bool) {
+        self.engine.set_reverse_file(reverse_file);
+    }
+
+    /// Turns tree-sitter highlighting off, showing file content in the default foreground.
+    pub fn set_syntax_highlighting(&mut self, syntax_highlighting: bool) {
+        self.engine.set_syntax_highlighting(syntax_highlighting);
+    }
+
+    /// Sets how many files' highlights are kept for when the same content shows again.
+    pub fn set_highlight_cache_size(&mut self, size: usize) {
+        self.engine.set_highlight_cache_size(size);
+    }
+
+    /// Sets how much each typed character's delay randomly varies around the typing speed.
+    pub fn set_jitter(&mut self, jitter: f64) {
+        self.engine.set_jitter(jitter);
+    }
+
+    /// Sets how many random glyphs each typed character scrambles through before resolving.
+    pub fn set_scramble_frames(&mut self, scramble_frames: usize) {
+        self.engine.set_scramble_frames(scramble_frames);
+    }
+
+    /// Sets the line count at or below which files get an extra settle pause (0 disables).
+    pub fn set_tiny_file_lines(&mut self, tiny_file_lines: usize) {
+        self.engine.set_tiny_file_lines(tiny_file_lines);
+    }
+
+    /// Sets the extra pause after blank line insertions and deletions (0 disables).
+    pub fn set_blank_line_pause_ms(&mut self, blank_line_pause_ms: u64) {
+        self.engine.set_blank_line_pause_ms(blank_line_pause_ms);
+    }
+
+    /// Sets how long the diff stat summary shows before a commit's files are animated.
+    pub fn set_stat_preroll_ms(&mut self, stat_preroll_ms: u64) {
+        self.engine.set_stat_preroll_ms(stat_preroll_ms);
+    }
+
+    /// Sets how long an excluded file shows a card with the reason it's skipped.
+    pub fn set_excluded_card_ms(&mut self, excluded_card_ms: u64) {
+        self.engine.set_excluded_card_ms(excluded_card_ms);
+    }
+
+    /// Enables growth mode, where the file tree lists each commit's whole codebase.
+    /// `codebase` holds the file paths present before the first commit.
+    pub fn set_growth_codebase(&mut self, codebase: BTreeSet<String>) {
+        self.engine.set_growth_codebase(codebase);
+        self.growth = true;
+    }
+
+    /// Restricts animation to one file, optionally to hunks overlapping a line range.
+    pub fn set_focus(&mut self, focus: Option<FileFocus>) {
+        self.engine.set_focus(focus);
+    }
+
+    /// Sets what happens when playback runs out of commits.
+    pub fn set_on_end(&mut self, on_end: EndBehavior) {
+        self.on_end = on_end;
+    }
+
+    /// Waits for new commits on HEAD after the last one is played and plays them as
+    /// they land, instead of following the end behavior. Only the repository being played
+    /// is watched, which is why `--watch` can't be combined with `--repos`.
+    pub fn set_watch(&mut self, watch: bool) {
+        self.watch = watch;
+    }
+
+    /// Sets whether jumping past the last (or before the first) file wraps around.
+    pub fn set_wrap_file_jumps(&mut self, wrap_file_jumps: bool) {
+        self.wrap_file_jumps = wrap_file_jumps;
+    }
+
+    /// Shows each file's final content without typing, for flipping through history.
+    pub fn set_static_view(&mut self, static_view: bool) {
+        self.static_view = static_view;
+        self.engine.set_static_view(static_view);
+    }
+
+    /// Sets how long a finished commit stays on screen before the next one starts. Without
+    /// it the wait follows the typing speed.
+    pub fn set_hold_ms(&mut self, hold_ms: Option<u64>) {
+        self.hold_ms = hold_ms;
+    }
+
+    /// Sets how long the static view shows each file; 0 waits for a key.
+    pub fn set_static_dwell_ms(&mut self, static_dwell_ms: u64) {
+        self.engine.set_static_dwell_ms(static_dwell_ms);
+    }
+
+    /// Whether playback stopped because every commit had been played.
+    pub fn history_ended(&self) -> bool {
+        self.history_ended
+    }
+
+    /// Shows the current commit's short hash and subject in the terminal window title.
+    pub fn set_terminal_title(&mut self, enabled: bool) {
+        self.set_terminal_title = enabled;
+    }
+
+    /// Dims already-typed lines above the cursor while a file is being typed.
+    pub fn set_dim_typed_lines(&mut self, dim_typed_lines: bool) {
+        self.editor.set_dim_typed_lines(dim_typed_lines);
+    }
+
+    /// Sets the columns between tab stops used to expand tabs in the editor.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.engine.set_tab_width(tab_width);
+    }
+
+    /// Shows an overview of the file being edited, colored by change, next to the editor.
+    pub fn set_show_minimap(&mut self, show_minimap: bool) {
+        self.editor.set_show_minimap(show_minimap);
+    }
+
+    /// Tints the brackets enclosing the cursor in the editor.
+    pub fn set_match_brackets(&mut self, match_brackets: bool) {
+        self.editor.set_match_brackets(match_brackets);
+    }
+
+    /// Marks trailing whitespace and tabs on lines the commit adds.
+    pub fn set_show_whitespace(&mut self, show_whitespace: bool) {
+        self.editor.set_show_whitespace(show_whitespace);
+    }
+
+    /// Shows or hides the editor's line number gutter; toggled at runtime with `l`.
+    pub fn set_show_line_numbers(&mut self, show_line_numbers: bool) {
+        self.editor.set_show_line_numbers(show_line_numbers);
+    }
+
+    /// Shows or hides the file tree and commit info column; toggled at runtime with `t`.
+    pub fn set_show_sidebar(&mut self, show_sidebar: bool) {
+        self.show_sidebar = show_sidebar;
+    }
+
+    /// Limits the colors drawn to what the terminal supports, mapping theme colors to
+    /// the nearest palette entry when truecolor isn't available.
+    pub fn set_color_depth(&mut self, color_depth: ColorDepth) {
+        self.color_depth = color_depth;
+    }
+
+    /// Shows the commit date as a timestamp, as a time ago, or both.
+    pub fn set_date_display(&mut self, date_display: DateDisplay) {
+        self.status_bar.set_date_display(date_display);
+    }
+
+    /// Highlights occurrences of a search term in the editor, ignoring case unless asked not to.
+    pub fn set_highlight(&mut self, term: Option<&str>, case_sensitive: bool) {
+        self.editor.set_highlight(term, case_sensitive);
+    }
+
+    /// Clips editor lines longer than the given number of characters with a truncation marker.
+    pub fn set_max_line_length(&mut self, max_line_length: Option<usize>) {
+        self.editor.set_max_line_length(max_line_length);
+    }
+
+    /// Plays commits from several repositories round-robin, labelling each in the status bar.
+    /// The first repository must be the one the initial commit was loaded from.
+    pub fn set_repositories(&mut self, repositories: Vec<(String, &'a GitRepository)>) {
+        self.repo = repositories.first().map(|(_, repo)| *repo);
+        self.repositories = repositories;
+        self.repo_index = 0;
+    }
+
     fn setup_signal_handler(should_exit: Arc<AtomicBool>) {
         ctrlc::set_handler(move || {
             // Restore terminal state before exiting
@@ -108,15 +387,74 @@ impl<'a> UI<'a> {
             // Exit immediately for external signals (SIGTERM)
             std::process::exit(0);
         })
-        .expect("Error setting Ctrl-C handler");
+        // The handler is process-wide, so a UI created later keeps the first one's
+        .ok();
     }
 
     /// Loads a commit and starts the animation.
     pub fn load_commit(&mut self, metadata: CommitMetadata) {
-        self.engine.load_commit(&metadata);
+        self.update_terminal_title(&metadata);
+        self.update_progress(&metadata);
+        // Growth mode lists the commit's whole tree, so the files of commits that were
+        // filtered out or skipped are accounted for too. A tree that can't be read keeps
+        // the previous listing.
+        let codebase = self
+            .current_repo()
+            .filter(|_| self.growth)
+            .and_then(|repo| repo.tree_paths(&metadata.hash).ok());
+        match codebase {
+            Some(codebase) => self.engine.load_commit_in_codebase(&metadata, codebase),
+            None => self.engine.load_commit(&metadata),
+        }
+        self.current_commit = Some(metadata);
         self.state = UIState::Playing;
     }
 
+    /// Plays the current commit again from the start.
+    fn restart_commit(&mut self) {
+        if let Some(metadata) = self.current_commit.clone() {
+            self.load_commit(metadata);
+            self.needs_redraw = true;
+        }
+    }
+
+    // Working tree diffs and a single requested commit have no history to track
+    fn update_progress(&mut self, metadata: &CommitMetadata) {
+        let single_commit = self.commit_spec.is_some() && !self.is_range_mode;
+        self.progress = match self.current_repo() {
+            Some(_) if self.diff_mode.is_some() || single_commit => None,
+            Some(_) if matches!(self.order, PlaybackOrder::Random) => {
+                self.played_commits.insert(metadata.hash.clone());
+                Some(Progress::Played(self.played_commits.len()))
+            }
+            Some(repo) => repo
+                .playback_position()
+                .map(|(current, total)| Progress::Position { current, total }),
+            None => None,
+        };
+    }
+
+    fn update_terminal_title(&mut self, metadata: &CommitMetadata) {
+        if !self.set_terminal_title {
+            return;
+        }
+
+        let mut stdout = io::stdout();
+        // Save the current title on the terminal's title stack so it can be restored on exit
+        if !self.title_saved {
+            let _ = write!(stdout, "{}", SAVE_TITLE);
+            self.title_saved = true;
+        }
+
+        let short_hash = metadata.hash.get(..7).unwrap_or(&metadata.hash);
+        let subject = metadata.message.lines().next().unwrap_or("");
+        // Terminals without title support simply ignore the escape sequence
+        let _ = execute!(
+            stdout,
+            SetTitle(format!("gitlogue: {} {}", short_hash, subject))
+        );
+    }
+
     /// Runs the main UI event loop.
     pub fn run(&mut self) -> Result<()> {
         enable_raw_mode()?;
@@ -140,9 +478,57 @@ impl<'a> UI<'a> {
             DisableMouseCapture
         )?;
         terminal.show_cursor()?;
+        if self.title_saved {
+            write!(terminal.backend_mut(), "{}", RESTORE_TITLE)?;
+            terminal.backend_mut().flush()?;
+        }
         Ok(())
     }
 
+    /// Plays the loaded commit once without a terminal, drawing into `backend` as if it were
+    /// a screen of the given size. After each redraw, `on_frame` gets the backend along with
+    /// the time elapsed since playback started.
+    pub fn run_headless<B: ratatui::backend::Backend>(
+        &mut self,
+        backend: B,
+        width: u16,
+        height: u16,
+        mut on_frame: impl FnMut(&mut B, Duration) -> Result<()>,
+    ) -> Result<()> {
+        // A fixed viewport keeps ratatui from asking the backend for the real terminal size
+        let mut terminal = Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+            },
+        )?;
+        self.update_viewport(Size::new(width, height));
+
+        let started = Instant::now();
+        while !self.engine.is_finished() {
+            if self.should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Export interrupted");
+            }
+
+            if self.engine.tick() {
+                terminal.draw(|f| self.render(f))?;
+                on_frame(terminal.backend_mut(), started.elapsed())?;
+            }
+            std::thread::sleep(Duration::from_millis(8));
+        }
+
+        Ok(())
+    }
+
+    fn update_viewport(&mut self, size: Size) {
+        // Editor area: 70% (right column) × 80% (editor pane) = 56% of total height
+        let viewport_height = (size.height as f32 * 0.70 * 0.80) as usize;
+        // Editor width: 70% (right column)
+        let content_width = (size.width as f32 * 0.70) as usize;
+        self.engine.set_viewport_height(viewport_height);
+        self.engine.set_content_width(content_width);
+    }
+
     fn run_loop(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
         loop {
             // Check for Ctrl+C signal
@@ -151,16 +537,19 @@ impl<'a> UI<'a> {
             }
 
             // Update viewport dimensions for scroll calculation
-            let size = terminal.size()?;
-            // Editor area: 70% (right column) × 80% (editor pane) = 56% of total height
-            let viewport_height = (size.height as f32 * 0.70 * 0.80) as usize;
-            // Editor width: 70% (right column)
-            let content_width = (size.width as f32 * 0.70) as usize;
-            self.engine.set_viewport_height(viewport_height);
-            self.engine.set_content_width(content_width);
+            self.update_viewport(terminal.size()?);
+
+            // Hide the speed notice once it has been shown long enough
+            if self
+                .speed_notice_until
+                .is_some_and(|until| Instant::now() >= until)
+            {
+                self.speed_notice_until = None;
+                self.needs_redraw = true;
+            }
 
             // Tick the animation engine
-            let needs_redraw = self.engine.tick();
+            let needs_redraw = self.engine.tick() || std::mem::take(&mut self.needs_redraw);
 
             if needs_redraw {
                 terminal.draw(|f| self.render(f))?;
@@ -168,144 +557,372 @@ impl<'a> UI<'a> {
 
             // Poll for keyboard events at frame rate
             if event::poll(std::time::Duration::from_millis(8))? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
+                match event::read()? {
+                    Event::Key(key) if self.filter_input => self.handle_filter_key(key),
+                    // Any key but `r` (which replays the last commit) and `/` (which filters
+                    // the file tree) ends holding
+                    Event::Key(key)
+                        if self.state == UIState::Holding
+                            && !matches!(key.code, KeyCode::Char('r') | KeyCode::Char('/')) =>
+                    {
+                        self.state = UIState::Finished;
+                        continue;
+                    }
+                    Event::Key(key) => match key.code {
+                        // Escape clears a file filter before it quits
+                        KeyCode::Esc if self.file_filter.is_some() => {
+                            self.file_filter = None;
+                            self.apply_file_filter();
+                        }
                         KeyCode::Esc | KeyCode::Char('q') => {
                             self.state = UIState::Finished;
                         }
                         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                             self.state = UIState::Finished;
                         }
+                        KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_speed(-1),
+                        KeyCode::Char('-') => self.adjust_speed(1),
+                        KeyCode::Char('n') => self.skip_to_next_commit(),
+                        KeyCode::Char('r') => self.restart_commit(),
+                        KeyCode::Char(']') | KeyCode::Tab => self.jump_to_adjacent_file(true),
+                        KeyCode::Char('[') | KeyCode::BackTab => self.jump_to_adjacent_file(false),
+                        KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Right
+                            if self.static_view =>
+                        {
+                            self.advance_static_view()
+                        }
+                        KeyCode::Char('l') => {
+                            self.editor.toggle_line_numbers();
+                            self.needs_redraw = true;
+                        }
+                        KeyCode::Char('t') => {
+                            self.show_sidebar = !self.show_sidebar;
+                            self.needs_redraw = true;
+                        }
+                        KeyCode::Char('/') => {
+                            // Edit the current filter, if any, rather than starting over. The
+                            // query is typed into the file tree, so bring it back if hidden.
+                            self.file_filter.get_or_insert_with(String::new);
+                            self.filter_input = true;
+                            self.show_sidebar = true;
+                            self.apply_file_filter();
+                        }
                         _ => {}
-                    }
+                    },
+                    Event::Mouse(mouse) => self.handle_mouse(mouse),
+                    _ => {}
                 }
             }
 
-            // State machine
-            match self.state {
-                UIState::Playing => {
-                    if self.engine.is_finished() {
-                        if self.repo.is_some() {
-                            // Schedule next commit
-                            // Wait time proportional to speed (100x the typing speed)
-                            self.state = UIState::WaitingForNext {
-                                resume_at: Instant::now()
-                                    + Duration::from_millis(self.speed_ms * 100),
-                            };
-                        } else {
-                            // Single commit mode without loop - quit
-                            self.state = UIState::Finished;
-                        }
+            self.update_state()?;
+            if self.state == UIState::Finished {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Advances the playback state machine: moves on once a commit has finished playing,
+    /// loads the next one when its time comes and checks for new commits when watching.
+    fn update_state(&mut self) -> Result<()> {
+        match self.state {
+            UIState::Playing => {
+                if self.engine.is_finished() {
+                    if self.repo.is_some() {
+                        // Schedule next commit, holding the finished one on screen for the
+                        // configured time, or 100x the typing speed by default; the static
+                        // view has already dwelt on the last file. `n` skips the wait.
+                        let wait_ms = match self.hold_ms {
+                            Some(hold_ms) => hold_ms,
+                            None if self.static_view => 0,
+                            None => self.speed_ms * 100,
+                        };
+                        self.state = UIState::WaitingForNext {
+                            resume_at: Instant::now() + Duration::from_millis(wait_ms),
+                        };
+                    } else if self.on_end == EndBehavior::Hold {
+                        self.state = UIState::Holding;
+                        self.needs_redraw = true;
+                    } else {
+                        // Single commit mode without loop - quit
+                        self.state = UIState::Finished;
                     }
                 }
-                UIState::WaitingForNext { resume_at } => {
-                    if Instant::now() >= resume_at {
-                        // Handle diff mode looping
-                        if let Some(diff_mode) = self.diff_mode {
-                            if let Some(repo) = self.repo {
-                                // Refresh the working tree diff
-                                match repo.get_working_tree_diff(diff_mode) {
-                                    Ok(metadata) if !metadata.changes.is_empty() => {
-                                        self.load_commit(metadata);
-                                    }
-                                    _ => {
-                                        // No more changes, finish
-                                        self.state = UIState::Finished;
-                                    }
-                                }
-                            } else {
-                                self.state = UIState::Finished;
-                            }
-                        } else if let Some(repo) = self.repo {
-                            let result = if self.is_range_mode {
-                                match self.order {
-                                    PlaybackOrder::Random => repo.random_range_commit(),
-                                    PlaybackOrder::Asc => repo.next_range_commit_asc(),
-                                    PlaybackOrder::Desc => repo.next_range_commit_desc(),
-                                }
-                            } else if self.commit_spec.is_some() {
-                                repo.get_commit(self.commit_spec.as_ref().unwrap())
-                            } else {
-                                match self.order {
-                                    PlaybackOrder::Random => repo.random_commit(),
-                                    PlaybackOrder::Asc => repo.next_asc_commit(),
-                                    PlaybackOrder::Desc => repo.next_desc_commit(),
-                                }
-                            };
-                            match result {
-                                Ok(metadata) => {
+            }
+            UIState::WaitingForNext { resume_at } => {
+                if Instant::now() >= resume_at {
+                    // Handle diff mode looping
+                    if let Some(diff_mode) = self.diff_mode {
+                        if let Some(repo) = self.repo {
+                            // Refresh the working tree diff
+                            match repo.get_working_tree_diff(diff_mode) {
+                                Ok(metadata) if !metadata.changes.is_empty() => {
                                     self.load_commit(metadata);
                                 }
-                                Err(_) => {
-                                    if self.loop_playback {
-                                        repo.reset_index();
-                                        let restart_result = if self.is_range_mode {
-                                            match self.order {
-                                                PlaybackOrder::Random => repo.random_range_commit(),
-                                                PlaybackOrder::Asc => repo.next_range_commit_asc(),
-                                                PlaybackOrder::Desc => {
-                                                    repo.next_range_commit_desc()
-                                                }
-                                            }
-                                        } else {
-                                            match self.order {
-                                                PlaybackOrder::Random => repo.random_commit(),
-                                                PlaybackOrder::Asc => repo.next_asc_commit(),
-                                                PlaybackOrder::Desc => repo.next_desc_commit(),
-                                            }
-                                        };
-                                        match restart_result {
-                                            Ok(metadata) => {
-                                                self.load_commit(metadata);
-                                            }
-                                            Err(_) => {
-                                                self.state = UIState::Finished;
-                                            }
-                                        }
-                                    } else {
-                                        self.state = UIState::Finished;
-                                    }
+                                _ => {
+                                    // No more changes, finish
+                                    self.state = UIState::Finished;
                                 }
                             }
                         } else {
                             self.state = UIState::Finished;
                         }
+                    } else {
+                        match self.fetch_next_commit() {
+                            Some(metadata) => self.load_commit(metadata),
+                            None if self.watch => {
+                                self.state = UIState::Watching {
+                                    check_at: Instant::now() + WATCH_INTERVAL,
+                                };
+                            }
+                            None => {
+                                self.history_ended = self.walks_history();
+                                self.state = if self.on_end == EndBehavior::Hold {
+                                    UIState::Holding
+                                } else {
+                                    UIState::Finished
+                                };
+                                self.needs_redraw = true;
+                            }
+                        }
                     }
                 }
-                UIState::Finished => {
-                    break;
+            }
+            UIState::Watching { check_at } => {
+                if Instant::now() >= check_at {
+                    let has_new_commits = match self.repo {
+                        Some(repo) => repo.refresh_commits()? > 0,
+                        None => false,
+                    };
+                    self.state = if has_new_commits {
+                        UIState::WaitingForNext {
+                            resume_at: Instant::now(),
+                        }
+                    } else {
+                        UIState::Watching {
+                            check_at: Instant::now() + WATCH_INTERVAL,
+                        }
+                    };
                 }
             }
+            UIState::Holding | UIState::Finished => {}
         }
-
         Ok(())
     }
 
+    /// Whether playback walks a repository's history in asc or desc order, so running out of
+    /// commits means its end was reached (rather than the end of a range or a single commit).
+    fn walks_history(&self) -> bool {
+        self.current_repo().is_some()
+            && self.commit_spec.is_none()
+            && !self.is_range_mode
+            && !matches!(self.order, PlaybackOrder::Random)
+    }
+
+    /// Abandons the current animation and moves on to the next commit right away.
+    /// Does nothing when there is no next commit to play (single commit mode).
+    fn skip_to_next_commit(&mut self) {
+        if self.repo.is_none() || self.state == UIState::Finished {
+            return;
+        }
+        self.state = UIState::WaitingForNext {
+            resume_at: Instant::now(),
+        };
+        self.needs_redraw = true;
+    }
+
+    /// Abandons the current file and animates the next (or previous) one of the commit.
+    fn jump_to_adjacent_file(&mut self, forward: bool) {
+        if self
+            .engine
+            .jump_to_adjacent_file(forward, self.wrap_file_jumps)
+            && self.state != UIState::Finished
+        {
+            self.state = UIState::Playing;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Moves the static view on to the next file, or to the next commit after the last one.
+    fn advance_static_view(&mut self) {
+        if self.engine.jump_to_adjacent_file(true, false) {
+            if self.state != UIState::Finished {
+                self.state = UIState::Playing;
+                self.needs_redraw = true;
+            }
+        } else {
+            self.skip_to_next_commit();
+        }
+    }
+
+    /// Scrolls the pane under the wheel, or jumps to a file clicked in the file tree.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+        let delta = match mouse.kind {
+            MouseEventKind::ScrollUp => -MOUSE_SCROLL_LINES,
+            MouseEventKind::ScrollDown => MOUSE_SCROLL_LINES,
+            MouseEventKind::Down(MouseButton::Left) if self.file_tree_area.contains(position) => {
+                let file_index =
+                    self.file_tree
+                        .file_at(self.file_tree_area, mouse.row, &self.theme);
+                // Replaying a file works even after the commit has finished
+                if file_index.is_some_and(|index| self.engine.jump_to_file(index))
+                    && self.state != UIState::Finished
+                {
+                    self.state = UIState::Playing;
+                    self.needs_redraw = true;
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if self.editor_area.contains(position) {
+            self.engine.scroll_editor(delta);
+        } else if self.file_tree_area.contains(position) {
+            self.file_tree
+                .scroll_by(self.file_tree_area, delta, &self.theme);
+        } else {
+            return;
+        }
+        self.needs_redraw = true;
+    }
+
+    /// Edits the file filter while it is being typed. Enter keeps the filter and Escape
+    /// clears it, both returning keys to their usual controls.
+    fn handle_filter_key(&mut self, key: KeyEvent) {
+        let filter = self.file_filter.get_or_insert_with(String::new);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.state = UIState::Finished;
+                return;
+            }
+            KeyCode::Char(ch) => filter.push(ch),
+            KeyCode::Backspace => {
+                filter.pop();
+            }
+            KeyCode::Enter => {
+                self.filter_input = false;
+                if filter.is_empty() {
+                    self.file_filter = None;
+                }
+            }
+            KeyCode::Esc => {
+                self.filter_input = false;
+                self.file_filter = None;
+            }
+            _ => return,
+        }
+        self.apply_file_filter();
+    }
+
+    /// Lists and animates only the files matching the file filter, redrawing the tree
+    /// right away.
+    fn apply_file_filter(&mut self) {
+        self.file_tree.set_filter(self.file_filter.as_deref());
+        self.engine
+            .set_file_filter(self.file_filter.as_deref().unwrap_or_default());
+        self.needs_redraw = true;
+    }
+
+    /// Makes typing faster (negative direction) or slower, in steps of about 20%.
+    fn adjust_speed(&mut self, direction: i64) {
+        let step = (self.engine.speed_ms() / 5).max(1) as i64;
+        self.speed_ms = self.engine.adjust_speed(direction * step);
+        self.speed_notice_until = Some(Instant::now() + SPEED_NOTICE_DURATION);
+        self.needs_redraw = true;
+    }
+
+    /// Fetches the next commit to play, rotating through repositories when several are loaded.
+    fn fetch_next_commit(&mut self) -> Option<CommitMetadata> {
+        // A specific commit is replayed as requested, even if there's nothing to animate
+        if self.commit_spec.is_some() && !self.is_range_mode {
+            return self.fetch_any_next_commit();
+        }
+
+        // Skip commits that only touch excluded files (or have no files left once they are
+        // hidden), giving up after a while in case nothing else is left so playback still
+        // moves on
+        for _ in 0..MAX_EXCLUDED_ONLY_SKIPS {
+            let metadata = self.fetch_any_next_commit()?;
+            if !metadata.only_excluded_changes() && !metadata.changes.is_empty() {
+                return Some(metadata);
+            }
+        }
+        self.fetch_any_next_commit()
+    }
+
+    fn fetch_any_next_commit(&mut self) -> Option<CommitMetadata> {
+        if !self.repositories.is_empty() {
+            self.repo_index = (self.repo_index + 1) % self.repositories.len();
+        }
+
+        while let Some(repo) = self.current_repo() {
+            match self.next_commit(repo) {
+                Ok(metadata) => return Some(metadata),
+                Err(_)
+                    if !self.watch && (self.loop_playback || self.on_end == EndBehavior::Loop) =>
+                {
+                    repo.reset_index();
+                    return self.next_commit(repo).ok();
+                }
+                Err(_) if self.repositories.len() > 1 => {
+                    // This repository is exhausted, keep playing the others
+                    self.repositories.remove(self.repo_index);
+                    self.repo_index %= self.repositories.len();
+                }
+                Err(_) => return None,
+            }
+        }
+
+        None
+    }
+
+    fn current_repo(&self) -> Option<&'a GitRepository> {
+        self.repositories
+            .get(self.repo_index)
+            .map(|(_, repo)| *repo)
+            .or(self.repo)
+    }
+
+    fn next_commit(&self, repo: &GitRepository) -> Result<CommitMetadata> {
+        if self.is_range_mode {
+            match self.order {
+                PlaybackOrder::Random => repo.random_range_commit(),
+                PlaybackOrder::Asc => repo.next_range_commit_asc(),
+                PlaybackOrder::Desc => repo.next_range_commit_desc(),
+            }
+        } else if let Some(commit_spec) = &self.commit_spec {
+            repo.get_commit(commit_spec)
+        } else {
+            match self.order {
+                PlaybackOrder::Random => repo.random_commit(),
+                PlaybackOrder::Asc => repo.next_asc_commit(),
+                PlaybackOrder::Desc => repo.next_desc_commit(),
+            }
+        }
+    }
+
     fn render(&mut self, f: &mut Frame) {
         let size = f.area();
 
-        // Split horizontally: left column | right column
-        let main_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(30), // Left column (file tree + commit info)
-                Constraint::Percentage(70), // Right column (editor + terminal)
-            ])
-            .margin(0)
-            .spacing(0)
-            .split(size);
-
-        // Split left column vertically: file tree | separator | commit info
-        let left_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(80), // File tree
-                Constraint::Length(1),      // Horizontal separator
-                Constraint::Percentage(20), // Commit info
-            ])
-            .margin(0)
-            .spacing(0)
-            .split(main_layout[0]);
+        // Split horizontally: left column | right column. With the sidebar hidden,
+        // the right column takes the whole width.
+        let (left_column, right_column) = if self.show_sidebar {
+            let main_layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(30), // Left column (file tree + commit info)
+                    Constraint::Percentage(70), // Right column (editor + terminal)
+                ])
+                .margin(0)
+                .spacing(0)
+                .split(size);
+            (Some(main_layout[0]), main_layout[1])
+        } else {
+            (None, size)
+        };
 
         // Split right column vertically: editor | separator | terminal
         let right_layout = Layout::default()
@@ -317,7 +934,7 @@ impl<'a> UI<'a> {
             ])
             .margin(0)
             .spacing(0)
-            .split(main_layout[1]);
+            .split(right_column);
 
         let separator_color = self.theme.separator;
 
@@ -326,28 +943,52 @@ impl<'a> UI<'a> {
             self.file_tree.set_commit_metadata(
                 metadata,
                 self.engine.current_file_index,
+                self.engine.codebase(),
                 &self.theme,
             );
         }
 
-        // Render file tree
-        self.file_tree.render(f, left_layout[0], &self.theme);
+        self.editor_area = right_layout[0];
+        self.file_tree_area = Rect::default();
+        if let Some(left_column) = left_column {
+            // Split left column vertically: file tree | separator | commit info
+            let left_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(80), // File tree
+                    Constraint::Length(1),      // Horizontal separator
+                    Constraint::Percentage(20), // Commit info
+                ])
+                .margin(0)
+                .spacing(0)
+                .split(left_column);
 
-        // Render horizontal separator between file tree and commit info (left column)
-        let left_sep = Paragraph::new(Line::from("─".repeat(left_layout[1].width as usize))).style(
-            Style::default()
-                .fg(separator_color)
-                .bg(self.theme.background_left),
-        );
-        f.render_widget(left_sep, left_layout[1]);
-
-        // Render commit info
-        self.status_bar.render(
-            f,
-            left_layout[2],
-            self.engine.current_metadata(),
-            &self.theme,
-        );
+            // Render file tree
+            self.file_tree.render(f, left_layout[0], &self.theme);
+            self.file_tree_area = left_layout[0];
+
+            // Render horizontal separator between file tree and commit info (left column)
+            let left_sep = Paragraph::new(Line::from("─".repeat(left_layout[1].width as usize)))
+                .style(
+                    Style::default()
+                        .fg(separator_color)
+                        .bg(self.theme.background_left),
+                );
+            f.render_widget(left_sep, left_layout[1]);
+
+            // Render commit info
+            self.status_bar.render(
+                f,
+                left_layout[2],
+                self.engine.current_metadata(),
+                self.repositories
+                    .get(self.repo_index)
+                    .map(|(name, _)| name.as_str()),
+                self.progress,
+                self.speed_notice_until.map(|_| self.engine.speed_ms()),
+                &self.theme,
+            );
+        }
 
         // Render editor
         self.editor
@@ -366,51 +1007,149 @@ impl<'a> UI<'a> {
         self.terminal
             .render(f, right_layout[2], &self.engine, &self.theme);
 
-        // Render dialog if present
-        if let Some(ref title) = self.engine.dialog_title {
-            let text = &self.engine.dialog_typing_text;
-            let text_display_width = text.width();
-            let dialog_width = (text_display_width + 10).max(60).min(size.width as usize) as u16;
-            let dialog_height = 3;
-            let dialog_x = (size.width.saturating_sub(dialog_width)) / 2;
-            let dialog_y = (size.height.saturating_sub(dialog_height)) / 2;
-
-            let dialog_area = Rect {
-                x: dialog_x,
-                y: dialog_y,
-                width: dialog_width,
-                height: dialog_height,
+        // Render the diff stat summary and the file dialog over the panes
+        self.overlay
+            .render(f, size, right_layout[0], &self.engine, &self.theme);
+        if self.state == UIState::Holding {
+            let message = if self.history_ended {
+                "All commits have been played"
+            } else {
+                "Finished playing"
             };
+            self.overlay.render_notice(
+                f,
+                right_layout[0],
+                &[message, "r replays · any other key exits"],
+                &self.theme,
+            );
+        }
 
-            // Calculate content width (dialog_width - borders(2) - padding(2))
-            let content_width = dialog_width.saturating_sub(4) as usize;
-            let padding_len = content_width.saturating_sub(text_display_width);
-
-            let spans = vec![
-                Span::styled(
-                    text.clone(),
-                    Style::default().fg(self.theme.file_tree_current_file_fg),
-                ),
-                Span::styled(
-                    " ".repeat(padding_len),
-                    Style::default().bg(self.theme.editor_cursor_line_bg),
-                ),
-            ];
-
-            let dialog_text = vec![Line::from(spans)];
-
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title(title.clone())
-                .padding(Padding::horizontal(1))
-                .style(
-                    Style::default()
-                        .fg(self.theme.file_tree_current_file_fg)
-                        .bg(self.theme.editor_cursor_line_bg),
+        // Quantize the finished frame, so colors blended while drawing are covered too
+        if self.color_depth != ColorDepth::TrueColor {
+            for cell in &mut f.buffer_mut().content {
+                cell.fg = self.color_depth.quantize(cell.fg);
+                cell.bg = self.color_depth.quantize(cell.bg);
+            }
+        }
+    }
+}
+
+/// Shows a message in place of playback until a key is pressed, e.g. when there is
+/// nothing to play
+pub fn show_notice(theme: &Theme, title: &str, detail: &str) -> Result<()> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = (|| -> Result<()> {
+        loop {
+            terminal.draw(|f| {
+                let area = f.area();
+                let background =
+                    Paragraph::new("").style(Style::default().bg(theme.background_right));
+                f.render_widget(background, area);
+                OverlayPane.render_notice(
+                    f,
+                    area,
+                    &[title, detail, "Press any key to exit"],
+                    theme,
                 );
+            })?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == event::KeyEventKind::Press {
+                    return Ok(());
+                }
+            }
+        }
+    })();
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::TestRepo;
+
+    fn new_ui<'a>(
+        repo: Option<&'a GitRepository>,
+        commit_spec: Option<&str>,
+        is_range_mode: bool,
+    ) -> UI<'a> {
+        let mut ui = UI::new(
+            0,
+            repo,
+            Theme::default(),
+            PlaybackOrder::Asc,
+            false,
+            commit_spec.map(String::from),
+            is_range_mode,
+            Vec::new(),
+        );
+        ui.set_hold_ms(Some(0));
+        ui
+    }
 
-            let dialog = Paragraph::new(dialog_text).block(block);
-            f.render_widget(dialog, dialog_area);
+    // Plays the loaded commit to the end, then steps the state machine until it settles
+    fn play_to_end(ui: &mut UI) {
+        while ui.state != UIState::Finished {
+            ui.engine.tick();
+            ui.update_state().unwrap();
+            std::thread::sleep(Duration::from_millis(1));
         }
     }
+
+    #[test]
+    fn test_single_commit_does_not_reach_end_of_history() {
+        let mut ui = new_ui(None, Some("HEAD"), false);
+        ui.load_commit(crate::preview::sample_commit());
+        play_to_end(&mut ui);
+        assert!(!ui.history_ended());
+    }
+
+    #[test]
+    fn test_restart_commit_replays_it_from_the_start() {
+        let mut ui = new_ui(None, Some("HEAD"), false);
+        ui.load_commit(crate::preview::sample_commit());
+        play_to_end(&mut ui);
+        assert!(ui.engine.is_finished());
+
+        ui.restart_commit();
+        assert_eq!(ui.state, UIState::Playing);
+        assert!(!ui.engine.is_finished());
+        assert_eq!(
+            ui.current_commit
+                .as_ref()
+                .map(|commit| commit.hash.as_str()),
+            Some(crate::preview::sample_commit().hash.as_str())
+        );
+
+        play_to_end(&mut ui);
+        assert!(ui.engine.is_finished());
+    }
+
+    #[test]
+    fn test_only_history_walks_reach_end_of_history() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file.txt", "0\n", "First");
+        test_repo.commit_file("file.txt", "1\n", "Second");
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+
+        // Running out of a range isn't the end of history
+        repo.set_commit_range("HEAD~1..HEAD").unwrap();
+        let mut ui = new_ui(Some(&repo), Some("HEAD~1..HEAD"), true);
+        ui.load_commit(repo.next_range_commit_asc().unwrap());
+        play_to_end(&mut ui);
+        assert!(!ui.history_ended());
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let mut ui = new_ui(Some(&repo), None, false);
+        ui.load_commit(repo.next_asc_commit().unwrap());
+        play_to_end(&mut ui);
+        assert!(ui.history_ended());
+    }
 }
@@ -7,16 +7,21 @@ use ratatui::{
 };
 use unicode_width::UnicodeWidthStr;
 
+/// A line after wrapping: (original line index, content, is first wrap, has wrap)
+type WrappedLine<'a> = (usize, Line<'a>, bool, bool);
+
 /// A paragraph widget that wraps at character boundaries and supports line selection
 pub struct SelectableParagraph<'a> {
     lines: Vec<Line<'a>>,
     block: Option<Block<'a>>,
     selected_line: Option<usize>,
+    scroll: Option<usize>,
     selected_style: Style,
     background_style: Style,
     padding: Padding,
     dim_max_distance: Option<usize>,
     dim_min_opacity: f32,
+    dim_above_opacity: Option<f32>,
 }
 
 impl<'a> SelectableParagraph<'a> {
@@ -25,11 +30,13 @@ impl<'a> SelectableParagraph<'a> {
             lines,
             block: None,
             selected_line: None,
+            scroll: None,
             selected_style: Style::default(),
             background_style: Style::default(),
             padding: Padding::ZERO,
             dim_max_distance: None,
             dim_min_opacity: 0.6,
+            dim_above_opacity: None,
         }
     }
 
@@ -43,6 +50,12 @@ impl<'a> SelectableParagraph<'a> {
         self
     }
 
+    /// Starts at this display line instead of keeping the selected line centered
+    pub fn scroll(mut self, offset: Option<usize>) -> Self {
+        self.scroll = offset;
+        self
+    }
+
     pub fn selected_style(mut self, style: Style) -> Self {
         self.selected_style = style;
         self
@@ -64,6 +77,12 @@ impl<'a> SelectableParagraph<'a> {
         self
     }
 
+    /// Further dims every line above the selected one to the given opacity
+    pub fn dim_above(mut self, opacity: f32) -> Self {
+        self.dim_above_opacity = Some(opacity);
+        self
+    }
+
     fn apply_opacity(&self, foreground: Color, opacity: f32, background: Color) -> Color {
         match (foreground, background) {
             (Color::Rgb(fr, fg, fb), Color::Rgb(br, bg, bb)) => {
@@ -78,6 +97,16 @@ impl<'a> SelectableParagraph<'a> {
     }
 
     fn calculate_dim_opacity(&self, line_index: usize) -> f32 {
+        let distance_opacity = self.calculate_distance_opacity(line_index);
+        match (self.selected_line, self.dim_above_opacity) {
+            (Some(center_line), Some(opacity)) if line_index < center_line => {
+                distance_opacity * opacity
+            }
+            _ => distance_opacity,
+        }
+    }
+
+    fn calculate_distance_opacity(&self, line_index: usize) -> f32 {
         if let (Some(center_line), Some(max_distance)) = (self.selected_line, self.dim_max_distance)
         {
             let distance = (line_index as isize - center_line as isize).unsigned_abs();
@@ -93,6 +122,93 @@ impl<'a> SelectableParagraph<'a> {
         }
     }
 
+    /// How the paragraph lays out in `area`: the first display line shown and, for each
+    /// screen row from the top of `area`, the index of the line drawn there
+    pub fn layout(&self, area: Rect) -> (usize, Vec<Option<usize>>) {
+        let inner_area = self.content_area(area);
+        let wrapped = self.wrap_lines(inner_area);
+        let height = inner_area.height as usize;
+        let scroll_offset = self.scroll_offset(&wrapped, height);
+
+        let mut rows = vec![None; inner_area.y.saturating_sub(area.y) as usize];
+        rows.extend(
+            wrapped
+                .iter()
+                .skip(scroll_offset)
+                .take(height)
+                .map(|(original_idx, ..)| Some(*original_idx)),
+        );
+        (scroll_offset, rows)
+    }
+
+    // Area lines are drawn in, inside the block and vertical padding
+    fn content_area(&self, area: Rect) -> Rect {
+        let area = match self.block {
+            Some(ref block) => block.inner(area),
+            None => area,
+        };
+        Rect {
+            x: area.x,
+            y: area.y.saturating_add(self.padding.top),
+            width: area.width,
+            height: area
+                .height
+                .saturating_sub(self.padding.top + self.padding.bottom),
+        }
+    }
+
+    // Wrap all lines and track which wrapped line corresponds to which original line.
+    // For wrapping: first line uses full width minus left padding (no right padding when wrapping)
+    fn wrap_lines(&self, inner_area: Rect) -> Vec<WrappedLine<'a>> {
+        let first_line_width = inner_area.width.saturating_sub(self.padding.left) as usize;
+        let continuation_width = inner_area.width as usize;
+
+        let mut wrapped_lines_with_indices = Vec::new();
+        for (original_idx, line) in self.lines.iter().enumerate() {
+            let wrapped = Self::wrap_line(line, first_line_width, continuation_width);
+            let has_wrap = wrapped.len() > 1;
+            for (wrap_idx, wrapped_line) in wrapped.into_iter().enumerate() {
+                let is_first_wrap = wrap_idx == 0;
+                wrapped_lines_with_indices.push((
+                    original_idx,
+                    wrapped_line,
+                    is_first_wrap,
+                    has_wrap,
+                ));
+            }
+        }
+        wrapped_lines_with_indices
+    }
+
+    // Calculate scroll offset to keep selected line centered, unless scrolled explicitly
+    fn scroll_offset(&self, wrapped_lines: &[WrappedLine], height: usize) -> usize {
+        let total_lines = wrapped_lines.len();
+        let max_offset = total_lines.saturating_sub(height);
+        if let Some(offset) = self.scroll {
+            return offset.min(max_offset);
+        }
+
+        if let Some(selected_idx) = self.selected_line {
+            // Find the first display line of the selected original line
+            let selected_display_line = wrapped_lines
+                .iter()
+                .position(|(orig_idx, _, _, _)| *orig_idx == selected_idx)
+                .unwrap_or(0);
+
+            if total_lines <= height {
+                // All lines fit, no scrolling needed
+                0
+            } else {
+                // Keep selected line in the middle of viewport
+                let preferred_position = height / 2;
+                let offset = selected_display_line.saturating_sub(preferred_position);
+                offset.min(max_offset)
+            }
+        } else {
+            0
+        }
+    }
+
     fn wrap_line(
         line: &Line<'a>,
         first_line_width: usize,
@@ -180,35 +296,17 @@ impl<'a> SelectableParagraph<'a> {
 
 impl Widget for SelectableParagraph<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let area = match self.block {
-            Some(ref block) => {
-                let inner = block.inner(area);
-                block.clone().render(area, buf);
-                inner
-            }
-            None => area,
-        };
-
-        if area.width == 0 || area.height == 0 {
-            return;
+        if let Some(ref block) = self.block {
+            block.clone().render(area, buf);
         }
 
-        // Apply padding
-        let inner_area = Rect {
-            x: area.x,
-            y: area.y.saturating_add(self.padding.top),
-            width: area.width,
-            height: area
-                .height
-                .saturating_sub(self.padding.top + self.padding.bottom),
-        };
+        // Block, then padding
+        let inner_area = self.content_area(area);
 
         if inner_area.width == 0 || inner_area.height == 0 {
             return;
         }
 
-        // For wrapping: first line uses full width minus left padding (no right padding when wrapping)
-        let first_line_width = inner_area.width.saturating_sub(self.padding.left) as usize;
         let continuation_width = inner_area.width as usize;
         let no_wrap_content_width = inner_area
             .width
@@ -216,46 +314,8 @@ impl Widget for SelectableParagraph<'_> {
             as usize;
         let height = inner_area.height as usize;
 
-        // Wrap all lines and track which wrapped line corresponds to which original line
-        // Also track if this is the first wrapped line and if wrapping occurred
-        let mut wrapped_lines_with_indices = Vec::new();
-        for (original_idx, line) in self.lines.iter().enumerate() {
-            let wrapped = Self::wrap_line(line, first_line_width, continuation_width);
-            let has_wrap = wrapped.len() > 1;
-            for (wrap_idx, wrapped_line) in wrapped.into_iter().enumerate() {
-                let is_first_wrap = wrap_idx == 0;
-                wrapped_lines_with_indices.push((
-                    original_idx,
-                    wrapped_line,
-                    is_first_wrap,
-                    has_wrap,
-                ));
-            }
-        }
-
-        // Calculate scroll offset to keep selected line centered
-        let scroll_offset = if let Some(selected_idx) = self.selected_line {
-            // Find the first display line of the selected original line
-            let selected_display_line = wrapped_lines_with_indices
-                .iter()
-                .position(|(orig_idx, _, _, _)| *orig_idx == selected_idx)
-                .unwrap_or(0);
-
-            let total_lines = wrapped_lines_with_indices.len();
-
-            if total_lines <= height {
-                // All lines fit, no scrolling needed
-                0
-            } else {
-                // Keep selected line in the middle of viewport
-                let preferred_position = height / 2;
-                let offset = selected_display_line.saturating_sub(preferred_position);
-                let max_offset = total_lines.saturating_sub(height);
-                offset.min(max_offset)
-            }
-        } else {
-            0
-        };
+        let wrapped_lines_with_indices = self.wrap_lines(inner_area);
+        let scroll_offset = self.scroll_offset(&wrapped_lines_with_indices, height);
 
         let visible_lines: Vec<_> = wrapped_lines_with_indices
             .into_iter()
@@ -442,3 +502,29 @@ impl Widget for SelectableParagraph<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dim_above_dims_only_lines_above_the_selection() {
+        let lines = || vec![Line::from("a"); 4];
+        let paragraph = SelectableParagraph::new(lines()).selected_line(Some(2));
+        assert_eq!(paragraph.calculate_dim_opacity(0), 1.0);
+
+        let paragraph = paragraph.dim_above(0.5);
+        assert_eq!(paragraph.calculate_dim_opacity(0), 0.5);
+        assert_eq!(paragraph.calculate_dim_opacity(1), 0.5);
+        assert_eq!(paragraph.calculate_dim_opacity(2), 1.0);
+        assert_eq!(paragraph.calculate_dim_opacity(3), 1.0);
+
+        // On top of the distance dimming
+        let paragraph = SelectableParagraph::new(lines())
+            .selected_line(Some(2))
+            .dim(2, 0.6)
+            .dim_above(0.5);
+        assert!((paragraph.calculate_dim_opacity(1) - 0.4).abs() < 1e-6);
+        assert!((paragraph.calculate_dim_opacity(3) - 0.8).abs() < 1e-6);
+    }
+}